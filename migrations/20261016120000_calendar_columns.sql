@@ -0,0 +1,13 @@
+-- Calendar links and categories on tasks
+ALTER TABLE tasks ADD COLUMN scheduled_event_id INTEGER REFERENCES events(id);
+ALTER TABLE tasks ADD COLUMN task_category TEXT DEFAULT 'general';
+-- False when only the day is known and scheduled_at carries a placeholder time
+ALTER TABLE tasks ADD COLUMN scheduled_time_known BOOLEAN NOT NULL DEFAULT 1;
+
+-- Event types, recurrence and sync identity
+ALTER TABLE events ADD COLUMN event_type TEXT DEFAULT 'event';
+ALTER TABLE events ADD COLUMN recurrence_rule TEXT;
+-- Comma-separated occurrence starts excluded from recurrence_rule
+ALTER TABLE events ADD COLUMN exdates TEXT;
+-- iCalendar UID for events synced or imported from another calendar
+ALTER TABLE events ADD COLUMN uid TEXT;
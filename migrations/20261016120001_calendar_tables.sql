@@ -0,0 +1,23 @@
+-- Recurring weekly time blocks
+CREATE TABLE IF NOT EXISTS schedule_blocks (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    day_of_week INTEGER NOT NULL CHECK(day_of_week >= 0 AND day_of_week <= 6),
+    start_time TEXT NOT NULL,
+    end_time TEXT NOT NULL,
+    block_type TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT,
+    priority INTEGER DEFAULT 1,
+    created_at TEXT DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Small persisted UI state (e.g. last selection)
+CREATE TABLE IF NOT EXISTS app_state (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_schedule_blocks_day ON schedule_blocks(day_of_week, start_time);
+CREATE INDEX IF NOT EXISTS idx_tasks_category ON tasks(task_category);
+-- Synced events are upserted by their iCalendar UID
+CREATE UNIQUE INDEX IF NOT EXISTS idx_events_uid ON events(uid);
@@ -0,0 +1,71 @@
+-- External-content FTS5 indexes behind `triptych search`, kept in step by triggers
+CREATE VIRTUAL TABLE IF NOT EXISTS tasks_fts USING fts5(
+    description, natural_language_input, tags,
+    content='tasks', content_rowid='id'
+);
+
+CREATE TRIGGER IF NOT EXISTS tasks_fts_ai AFTER INSERT ON tasks BEGIN
+    INSERT INTO tasks_fts(rowid, description, natural_language_input, tags)
+    VALUES (new.id, new.description, new.natural_language_input, new.tags);
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_fts_ad AFTER DELETE ON tasks BEGIN
+    INSERT INTO tasks_fts(tasks_fts, rowid, description, natural_language_input, tags)
+    VALUES ('delete', old.id, old.description, old.natural_language_input, old.tags);
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_fts_au AFTER UPDATE OF description, natural_language_input, tags ON tasks BEGIN
+    INSERT INTO tasks_fts(tasks_fts, rowid, description, natural_language_input, tags)
+    VALUES ('delete', old.id, old.description, old.natural_language_input, old.tags);
+    INSERT INTO tasks_fts(rowid, description, natural_language_input, tags)
+    VALUES (new.id, new.description, new.natural_language_input, new.tags);
+END;
+
+CREATE VIRTUAL TABLE IF NOT EXISTS events_fts USING fts5(
+    title, description, location,
+    content='events', content_rowid='id'
+);
+
+CREATE TRIGGER IF NOT EXISTS events_fts_ai AFTER INSERT ON events BEGIN
+    INSERT INTO events_fts(rowid, title, description, location)
+    VALUES (new.id, new.title, new.description, new.location);
+END;
+
+CREATE TRIGGER IF NOT EXISTS events_fts_ad AFTER DELETE ON events BEGIN
+    INSERT INTO events_fts(events_fts, rowid, title, description, location)
+    VALUES ('delete', old.id, old.title, old.description, old.location);
+END;
+
+CREATE TRIGGER IF NOT EXISTS events_fts_au AFTER UPDATE OF title, description, location ON events BEGIN
+    INSERT INTO events_fts(events_fts, rowid, title, description, location)
+    VALUES ('delete', old.id, old.title, old.description, old.location);
+    INSERT INTO events_fts(rowid, title, description, location)
+    VALUES (new.id, new.title, new.description, new.location);
+END;
+
+CREATE VIRTUAL TABLE IF NOT EXISTS emails_fts USING fts5(
+    subject, sender, body_text,
+    content='emails', content_rowid='id'
+);
+
+CREATE TRIGGER IF NOT EXISTS emails_fts_ai AFTER INSERT ON emails BEGIN
+    INSERT INTO emails_fts(rowid, subject, sender, body_text)
+    VALUES (new.id, new.subject, new.sender, new.body_text);
+END;
+
+CREATE TRIGGER IF NOT EXISTS emails_fts_ad AFTER DELETE ON emails BEGIN
+    INSERT INTO emails_fts(emails_fts, rowid, subject, sender, body_text)
+    VALUES ('delete', old.id, old.subject, old.sender, old.body_text);
+END;
+
+CREATE TRIGGER IF NOT EXISTS emails_fts_au AFTER UPDATE OF subject, sender, body_text ON emails BEGIN
+    INSERT INTO emails_fts(emails_fts, rowid, subject, sender, body_text)
+    VALUES ('delete', old.id, old.subject, old.sender, old.body_text);
+    INSERT INTO emails_fts(rowid, subject, sender, body_text)
+    VALUES (new.id, new.subject, new.sender, new.body_text);
+END;
+
+-- Index rows that existed before the triggers
+INSERT INTO tasks_fts(tasks_fts) VALUES ('rebuild');
+INSERT INTO events_fts(events_fts) VALUES ('rebuild');
+INSERT INTO emails_fts(emails_fts) VALUES ('rebuild');
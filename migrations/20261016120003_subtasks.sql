@@ -0,0 +1,3 @@
+-- Subtasks point at their parent task; deleting a parent deletes its subtasks
+ALTER TABLE tasks ADD COLUMN parent_id INTEGER REFERENCES tasks(id) ON DELETE CASCADE;
+CREATE INDEX IF NOT EXISTS idx_tasks_parent ON tasks(parent_id);
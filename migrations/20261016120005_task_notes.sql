@@ -0,0 +1,32 @@
+-- Free-form, multi-line context attached to a task
+ALTER TABLE tasks ADD COLUMN notes TEXT;
+
+-- Rebuild the task search index so notes are searchable too
+DROP TRIGGER IF EXISTS tasks_fts_ai;
+DROP TRIGGER IF EXISTS tasks_fts_ad;
+DROP TRIGGER IF EXISTS tasks_fts_au;
+DROP TABLE IF EXISTS tasks_fts;
+
+CREATE VIRTUAL TABLE tasks_fts USING fts5(
+    description, natural_language_input, tags, notes,
+    content='tasks', content_rowid='id'
+);
+
+CREATE TRIGGER tasks_fts_ai AFTER INSERT ON tasks BEGIN
+    INSERT INTO tasks_fts(rowid, description, natural_language_input, tags, notes)
+    VALUES (new.id, new.description, new.natural_language_input, new.tags, new.notes);
+END;
+
+CREATE TRIGGER tasks_fts_ad AFTER DELETE ON tasks BEGIN
+    INSERT INTO tasks_fts(tasks_fts, rowid, description, natural_language_input, tags, notes)
+    VALUES ('delete', old.id, old.description, old.natural_language_input, old.tags, old.notes);
+END;
+
+CREATE TRIGGER tasks_fts_au AFTER UPDATE OF description, natural_language_input, tags, notes ON tasks BEGIN
+    INSERT INTO tasks_fts(tasks_fts, rowid, description, natural_language_input, tags, notes)
+    VALUES ('delete', old.id, old.description, old.natural_language_input, old.tags, old.notes);
+    INSERT INTO tasks_fts(rowid, description, natural_language_input, tags, notes)
+    VALUES (new.id, new.description, new.natural_language_input, new.tags, new.notes);
+END;
+
+INSERT INTO tasks_fts(tasks_fts) VALUES ('rebuild');
@@ -0,0 +1,3 @@
+-- When a task was completed, as local wall-clock time like scheduled_at;
+-- NULL while open and for tasks completed before this was recorded
+ALTER TABLE tasks ADD COLUMN completed_at TEXT;
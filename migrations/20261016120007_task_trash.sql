@@ -0,0 +1,4 @@
+-- Deleted tasks move to the trash until it is emptied; NULL for live tasks
+ALTER TABLE tasks ADD COLUMN deleted_at TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_tasks_deleted_at ON tasks(deleted_at);
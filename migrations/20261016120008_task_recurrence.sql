@@ -0,0 +1,2 @@
+-- RRULE a task repeats by; completing it adds the next occurrence
+ALTER TABLE tasks ADD COLUMN recurrence_rule TEXT;
@@ -1,14 +1,22 @@
 #![allow(dead_code)]
-use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Timelike, Utc};
+use chrono::{
+    DateTime, Datelike, Duration, Months, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::Arc;
 
+use crate::display::DisplayConfig;
+use crate::migrations;
 use crate::nlp::{NLPParser, ParsedItem, Priority};
+use crate::paths;
+use crate::recurrence::{RecurrenceRule, parse_exdates};
+use crate::sync::{AppEvent, SyncStatus};
 use sqlx::{
     FromRow,
     migrate::MigrateDatabase,
-    sqlite::{Sqlite, SqlitePool},
+    sqlite::{Sqlite, SqliteConnection, SqlitePool},
 };
 
 // TOML import/export types
@@ -35,7 +43,147 @@ fn default_priority() -> i32 {
     1
 }
 
-const DB_URL: &str = "sqlite:todo.db";
+/// Weekly template imported when `schedule import` is given no file
+const DEFAULT_SCHEDULE: &str = include_str!("default_schedule.toml");
+
+impl ScheduleToml {
+    /// Read a schedule file: JSON when it ends in `.json`, TOML otherwise
+    pub fn from_file(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let content = std::fs::read_to_string(path)?;
+        let is_json = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        if is_json {
+            Ok(serde_json::from_str(&content)?)
+        } else {
+            Ok(toml::from_str(&content)?)
+        }
+    }
+
+    /// The built-in weekly template
+    pub fn builtin() -> Self {
+        toml::from_str(DEFAULT_SCHEDULE).expect("built-in schedule template is valid TOML")
+    }
+}
+
+// Full database backup types
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DatabaseBackup {
+    pub exported_at: DateTime<Utc>,
+    pub tasks: Vec<TaskRecord>,
+    /// (task id, depends-on id) pairs, using the `id`s in `tasks`
+    #[serde(default)]
+    pub dependencies: Vec<(i64, i64)>,
+    pub events: Vec<EventRecord>,
+    pub schedule_blocks: Vec<BlockRecord>,
+}
+
+#[derive(Debug, Deserialize, Serialize, FromRow)]
+pub struct TaskRecord {
+    /// Only used to relink `parent_id` on import; imported tasks get new IDs
+    #[serde(default)]
+    pub id: Option<i64>,
+    #[serde(default)]
+    pub parent_id: Option<i64>,
+    pub description: String,
+    pub completed: bool,
+    pub item_order: Option<i64>,
+    pub scheduled_at: Option<DateTime<Utc>>,
+    pub priority: i32,
+    pub tags: Option<String>,
+    pub natural_language_input: Option<String>,
+    pub task_category: Option<String>,
+    #[serde(default = "default_time_known")]
+    pub scheduled_time_known: bool,
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub completed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub recurrence_rule: Option<String>,
+}
+
+fn default_time_known() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize, Serialize, FromRow)]
+pub struct EventRecord {
+    pub title: String,
+    pub description: Option<String>,
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub location: Option<String>,
+    pub calendar_id: Option<String>,
+    pub event_type: Option<String>,
+    pub recurrence_rule: Option<String>,
+    #[serde(default)]
+    pub exdates: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, FromRow)]
+pub struct BlockRecord {
+    pub day_of_week: i32,
+    pub start_time: String,
+    pub end_time: String,
+    pub block_type: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub priority: i32,
+}
+
+/// Rows written by `import_database`, per table, plus rows skipped as duplicates
+#[derive(Debug, Default)]
+pub struct ImportSummary {
+    pub tasks: usize,
+    pub events: usize,
+    pub schedule_blocks: usize,
+    pub skipped: usize,
+}
+
+/// Blocks written by `import_schedule`, plus exact duplicates it skipped
+#[derive(Debug, Default)]
+pub struct ScheduleImportSummary {
+    pub imported: usize,
+    pub duplicates: usize,
+}
+
+/// What a `compact` pass changed. Steps that failed are listed in `warnings`
+/// and the remaining steps still run.
+#[derive(Debug, Default)]
+pub struct CompactSummary {
+    /// Tasks whose `item_order` changed while closing gaps
+    pub renumbered: u64,
+    /// Dangling event links and stale UI state removed
+    pub orphans_removed: u64,
+    /// Database file size before and after VACUUM, in bytes
+    pub size_before: Option<i64>,
+    pub size_after: Option<i64>,
+    pub warnings: Vec<String>,
+}
+
+/// Turn free text into an FTS5 MATCH expression. Each word becomes a quoted
+/// prefix term, so FTS syntax typed by the user ("report:", "AND", quotes,
+/// parentheses) is searched for literally instead of failing to parse.
+/// Returns None when nothing searchable is left.
+fn fts_match_query(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split_whitespace()
+        .filter(|word| word.chars().any(char::is_alphanumeric))
+        .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+        .collect();
+
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// Most hits `App::search` returns
+const SEARCH_LIMIT: i64 = 50;
+/// Most tag suggestions shown while typing a `#` word
+const TAG_COMPLETION_LIMIT: usize = 5;
+const SELECTED_TASK_KEY: &str = "selected_task_id";
+const SORT_MODE_KEY: &str = "sort_mode";
+/// How far, in weeks, the calendar can scroll from today (about 10 years)
+pub const MAX_WEEK_OFFSET: i64 = 520;
 
 #[derive(Clone, FromRow, Debug)]
 pub struct TimelineEntry {
@@ -59,12 +207,203 @@ pub struct Event {
     pub location: Option<String>,
     pub calendar_id: Option<String>,
     pub created_at: DateTime<Utc>,
+    pub event_type: Option<String>,
+    pub recurrence_rule: Option<String>,
+    /// Comma-separated occurrence starts excluded from `recurrence_rule`
+    pub exdates: Option<String>,
+    /// iCalendar UID for events synced or imported from another calendar
+    pub uid: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchKind {
+    Task,
+    Event,
+    Email,
+}
+
+impl SearchKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SearchKind::Task => "task",
+            SearchKind::Event => "event",
+            SearchKind::Email => "email",
+        }
+    }
+}
+
+/// One full-text search hit across tasks, events and emails
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchResult {
+    pub kind: SearchKind,
+    pub id: i64,
+    /// Task description, event title or email subject
+    pub title: String,
+    /// When it happens or happened: scheduled time, event start or receipt
+    pub at: Option<DateTime<Utc>>,
+    pub completed: bool,
+}
+
+/// kind, id, title, at, completed
+type SearchRow = (String, i64, String, Option<DateTime<Utc>>, bool);
+
+/// title, start_time, end_time, recurrence_rule, exdates
+type RecurringEventRow = (
+    String,
+    DateTime<Utc>,
+    DateTime<Utc>,
+    Option<String>,
+    Option<String>,
+);
+
+/// The slice of an event that falls on one calendar day
+#[derive(Debug, Clone)]
+pub struct EventSegment {
+    pub date: NaiveDate,
+    pub start: NaiveTime,
+    /// None when the event runs past midnight into the next day
+    pub end: Option<NaiveTime>,
+    pub title: String,
+}
+
+/// Everything the calendar shows for a range of days, already in local
+/// wall-clock time and filtered to those days
+#[derive(Debug, Clone, Default)]
+pub struct CalendarData {
+    pub schedule_blocks: Vec<(NaiveDate, ScheduleBlock)>,
+    /// Open tasks with a known time: (day, time, description, priority)
+    pub scheduled_tasks: Vec<(NaiveDate, NaiveTime, String, i32)>,
+    /// Date-only tasks and all-day events, shown in the calendar's all-day row
+    pub all_day: Vec<(NaiveDate, String, i32)>,
+    pub events: Vec<EventSegment>,
+}
+
+/// What kind of entry a line of the day agenda is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgendaKind {
+    Block,
+    Event,
+    Task,
+}
+
+/// One timed line of the day agenda
+#[derive(Debug, Clone)]
+pub struct AgendaEntry {
+    pub start: NaiveTime,
+    pub end: Option<NaiveTime>,
+    pub title: String,
+    pub kind: AgendaKind,
+}
+
+/// A single day's schedule blocks, events and open tasks in time order, with
+/// date-only tasks and all-day events kept apart as "anytime"
+#[derive(Debug, Clone, Default)]
+pub struct Agenda {
+    pub timed: Vec<AgendaEntry>,
+    pub anytime: Vec<String>,
+}
+
+/// Split an event into per-day segments, keeping only days in `days`.
+/// Zero-length events (e.g. converted tasks) are shown as one hour long.
+pub fn event_day_segments(
+    title: &str,
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+    days: &[NaiveDate],
+) -> Vec<EventSegment> {
+    let end = if end <= start {
+        start + Duration::hours(1)
+    } else {
+        end
+    };
+
+    let mut segments = Vec::new();
+    let mut date = start.date();
+    while date <= end.date() {
+        let seg_start = if date == start.date() {
+            start.time()
+        } else {
+            NaiveTime::MIN
+        };
+        let seg_end = if date == end.date() {
+            Some(end.time())
+        } else {
+            None
+        };
+
+        // An event ending exactly at midnight has no segment on that day
+        let empty = seg_end == Some(NaiveTime::MIN) && date != start.date();
+        if !empty && days.contains(&date) {
+            segments.push(EventSegment {
+                date,
+                start: seg_start,
+                end: seg_end,
+                title: title.to_string(),
+            });
+        }
+
+        date = match date.succ_opt() {
+            Some(next) => next,
+            None => break,
+        };
+    }
+
+    segments
+}
+
+/// Task list order, shared by the TUI and `triptych list`
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum SortMode {
+    /// The user's own order (`item_order`)
+    #[default]
+    Manual,
+    /// Highest priority first, manual order within a priority
+    Priority,
+    /// Soonest scheduled first, unscheduled tasks last
+    DueDate,
+    /// By description, ignoring case
+    Alphabetical,
+}
+
+impl SortMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "manual" => Some(SortMode::Manual),
+            "priority" => Some(SortMode::Priority),
+            "due" | "date" => Some(SortMode::DueDate),
+            "alpha" | "alphabetical" => Some(SortMode::Alphabetical),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SortMode::Manual => "manual",
+            SortMode::Priority => "priority",
+            SortMode::DueDate => "due",
+            SortMode::Alphabetical => "alpha",
+        }
+    }
+
+    /// The mode after this one when cycling with `o`
+    pub fn next(self) -> Self {
+        match self {
+            SortMode::Manual => SortMode::Priority,
+            SortMode::Priority => SortMode::DueDate,
+            SortMode::DueDate => SortMode::Alphabetical,
+            SortMode::Alphabetical => SortMode::Manual,
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ViewMode {
     TodoList,
+    /// Weekly grid
     Calendar,
+    Day,
+    Month,
 }
 
 #[derive(Debug, Clone, FromRow)]
@@ -79,7 +418,104 @@ pub struct ScheduleBlock {
     pub priority: i32,
 }
 
-#[derive(Clone, FromRow, Debug)]
+/// Something a scheduled task overlaps
+#[derive(Debug, Clone)]
+pub enum Conflict {
+    /// A schedule block of a different type than the task's category
+    Block(ScheduleBlock),
+    /// Another incomplete task in the same time window
+    Task { id: i64, description: String },
+}
+
+impl std::fmt::Display for Conflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Conflict::Block(block) => write!(
+                f,
+                "{} block \"{}\" ({}-{})",
+                block.block_type, block.title, block.start_time, block.end_time
+            ),
+            Conflict::Task { id, description } => write!(f, "task #{} \"{}\"", id, description),
+        }
+    }
+}
+
+/// What `App::add_dependency` did
+#[derive(Debug, Clone, PartialEq)]
+pub enum DependencyChange {
+    Added,
+    /// The task already depended on it
+    Unchanged,
+    /// No task with this ID
+    NotFound(i64),
+    /// The new link would close a loop. Holds the existing chain of
+    /// dependencies leading from the prerequisite back to the task.
+    Cycle(Vec<i64>),
+}
+
+/// Tasks have no duration of their own; like the calendar, treat each as an hour
+const TASK_SLOT_MINUTES: i64 = 60;
+
+/// How many days, starting today, `auto_schedule` searches for a free block
+const AUTO_SCHEDULE_DAYS: i64 = 7;
+
+/// The [start, end) window an open task with a known time occupies
+fn task_window(task: &Task) -> Option<(NaiveDateTime, NaiveDateTime)> {
+    if task.completed || !task.scheduled_time_known {
+        return None;
+    }
+    let start = task.scheduled_at?.naive_utc();
+    Some((start, start + Duration::minutes(TASK_SLOT_MINUTES)))
+}
+
+/// Blocks and other tasks overlapping `task`'s window. A block matching the
+/// task's category is where the auto-scheduler would put it, so it isn't a
+/// conflict. Blocks repeat weekly, so every day the window touches is checked,
+/// which also catches blocks straddling either end of the window.
+fn detect_conflicts(task: &Task, blocks: &[ScheduleBlock], others: &[Task]) -> Vec<Conflict> {
+    let Some((start, end)) = task_window(task) else {
+        return Vec::new();
+    };
+    let category = task.task_category.as_deref().unwrap_or("general");
+    let mut conflicts = Vec::new();
+
+    let mut day = start.date();
+    while day.and_time(NaiveTime::MIN) < end {
+        let dow = day.weekday().num_days_from_monday() as i32;
+        for block in blocks
+            .iter()
+            .filter(|b| b.day_of_week == dow && b.block_type != category)
+        {
+            let (Some(block_start), Some(block_end)) = (
+                parse_time_string(&block.start_time),
+                parse_time_string(&block.end_time),
+            ) else {
+                continue;
+            };
+            if day.and_time(block_start) < end && start < day.and_time(block_end) {
+                conflicts.push(Conflict::Block(block.clone()));
+            }
+        }
+        let Some(next) = day.succ_opt() else { break };
+        day = next;
+    }
+
+    for other in others.iter().filter(|other| other.id != task.id) {
+        if let Some((other_start, other_end)) = task_window(other)
+            && other_start < end
+            && start < other_end
+        {
+            conflicts.push(Conflict::Task {
+                id: other.id,
+                description: other.description.clone(),
+            });
+        }
+    }
+
+    conflicts
+}
+
+#[derive(Clone, FromRow, Debug, Serialize, Deserialize)]
 pub struct Task {
     pub id: i64,
     pub description: String,
@@ -87,21 +523,52 @@ pub struct Task {
     pub item_order: Option<i64>,
     pub scheduled_at: Option<DateTime<Utc>>,
     pub priority: i32,
+    // Raw JSON column; serialized output carries the parsed list instead
+    #[serde(skip_serializing, default)]
     pub tags: Option<String>,
     pub natural_language_input: Option<String>,
     pub task_category: Option<String>,
+    /// False when only the day is known and `scheduled_at` carries a placeholder time
+    pub scheduled_time_known: bool,
+    /// The task this is a subtask of
+    pub parent_id: Option<i64>,
+    /// Multi-line context beyond the one-line description
+    pub notes: Option<String>,
+    /// When the task was marked done, in local wall-clock time like `scheduled_at`
+    pub completed_at: Option<DateTime<Utc>>,
+    /// When the task was moved to the trash; None for live tasks
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// RRULE the task repeats by, starting from `scheduled_at`
+    pub recurrence_rule: Option<String>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct EnhancedTaskInfo {
     pub task: Task,
     pub tags: Vec<String>,
     pub is_scheduled: bool,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoveDirection {
+    Up,
+    Down,
+}
+
 pub enum InputMode {
     Normal,
     Editing,
+    /// Typing a subtask for the selected task
+    AddingSubtask,
+    /// Editing the selected task's notes; Enter inserts a line break
+    EditingNotes,
+    Rescheduling,
+    /// Asking which block type to plan the selected task into
+    Planning,
+    /// Typing a full-text search; hits replace the task list as you type
+    Searching,
+    /// Asking whether to move every completed task to the trash
+    ConfirmingClear,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -127,6 +594,9 @@ pub struct BlockFormState {
     pub end_time: String,
     pub title: String,
     pub active_field: BlockFormField,
+    pub block_types: Vec<String>,
+    /// The block being edited; `None` when the form creates a new one
+    pub editing: Option<i64>,
 }
 
 impl BlockFormState {
@@ -146,31 +616,63 @@ impl BlockFormState {
         "project",
     ];
 
-    pub fn new_at(time_slot: usize) -> Self {
-        let start_hour = 7 + time_slot;
-        let end_hour = start_hour + 1;
+    /// A block filling the calendar slot `minutes` long starting at `start`;
+    /// the day's last slot ends at 23:59
+    pub fn new_at(start: NaiveTime, minutes: u32) -> Self {
+        let (end, wrapped) = start.overflowing_add_signed(Duration::minutes(minutes as i64));
+        let end_time = if wrapped != 0 {
+            "23:59".to_string()
+        } else {
+            end.format("%H:%M").to_string()
+        };
         Self {
             block_type: "deepwork".to_string(),
-            start_time: format!("{:02}:00", start_hour),
-            end_time: format!("{:02}:00", end_hour),
+            start_time: start.format("%H:%M").to_string(),
+            end_time,
             title: String::new(),
             active_field: BlockFormField::BlockType,
+            block_types: Self::BLOCK_TYPES.iter().map(|t| t.to_string()).collect(),
+            editing: None,
+        }
+    }
+
+    /// The form pre-filled with an existing block, starting on its title
+    pub fn editing(block: &ScheduleBlock) -> Self {
+        Self {
+            block_type: block.block_type.clone(),
+            start_time: block.start_time.clone(),
+            end_time: block.end_time.clone(),
+            title: block.title.clone(),
+            active_field: BlockFormField::Title,
+            block_types: Self::BLOCK_TYPES.iter().map(|t| t.to_string()).collect(),
+            editing: Some(block.id),
+        }
+    }
+
+    /// Add user-defined block types (e.g. from imported schedules) to the cycle
+    pub fn with_block_types(mut self, types: &[String]) -> Self {
+        for block_type in types {
+            if !self.block_types.contains(block_type) {
+                self.block_types.push(block_type.clone());
+            }
         }
+        self
     }
 
     pub fn cycle_block_type(&mut self, forward: bool) {
-        let current_idx = Self::BLOCK_TYPES
+        let current_idx = self
+            .block_types
             .iter()
             .position(|t| *t == self.block_type)
             .unwrap_or(0);
         let new_idx = if forward {
-            (current_idx + 1) % Self::BLOCK_TYPES.len()
+            (current_idx + 1) % self.block_types.len()
         } else if current_idx == 0 {
-            Self::BLOCK_TYPES.len() - 1
+            self.block_types.len() - 1
         } else {
             current_idx - 1
         };
-        self.block_type = Self::BLOCK_TYPES[new_idx].to_string();
+        self.block_type = self.block_types[new_idx].clone();
     }
 
     pub fn next_field(&mut self) {
@@ -198,154 +700,1028 @@ pub struct App {
     pub selected: usize,
     pub input_mode: InputMode,
     pub view_mode: ViewMode,
-    pub calendar_week_offset: Option<i64>,
-    pub selected_day: usize,
+    pub sort_mode: SortMode,
+    /// Selected calendar date; each calendar view shows the day, week or
+    /// month containing it
+    pub calendar_anchor: NaiveDate,
     pub selected_time_slot: usize,
     pub calendar_input_mode: CalendarInputMode,
     pub block_form: BlockFormState,
     pub task_picker_selected: usize,
     pub input_buffer: String,
     nlp_parser: Arc<NLPParser>,
-    pub cached_schedule_blocks: Vec<(NaiveDate, ScheduleBlock)>,
-    pub cached_scheduled_tasks: Vec<(NaiveDate, NaiveTime, String, i32)>,
+    /// Calendar contents for `visible_days`, refreshed by `refresh_calendar_data`
+    pub calendar_data: CalendarData,
+    pub cached_block_types: Vec<String>,
+    /// Tags in use with their task counts, loaded when the add-task editor
+    /// opens, for `#` completion
+    pub cached_tags: Vec<(String, usize)>,
     pub status_message: Option<(String, std::time::Instant)>,
+    pub display: DisplayConfig,
+    pub input_error: Option<String>,
+    /// Tasks overlapping a schedule block or another task, flagged in the list
+    pub conflicting_task_ids: HashSet<i64>,
+    /// Tasks with an incomplete dependency, dimmed and locked in the list
+    pub blocked_task_ids: HashSet<i64>,
+    /// How far each task is indented under its parent in the list
+    pub task_depths: HashMap<i64, usize>,
+    /// Hits for the query typed in `InputMode::Searching`
+    pub search_results: Vec<SearchResult>,
+    pub search_selected: usize,
+    pending_selection: Option<i64>,
+    /// Most recent destructive TUI actions, newest last, capped at `UNDO_LIMIT`
+    undo_stack: Vec<UndoEntry>,
+    /// Background sync progress for the footer; only the TUI runs the workers
+    pub sync_status: Option<Arc<SyncStatus>>,
 }
 
-fn parse_time_string(time_str: &str) -> Option<NaiveTime> {
-    if time_str.contains(':') {
-        let parts: Vec<&str> = time_str.split(':').collect();
-        if parts.len() >= 2 {
-            let hour: u32 = parts[0].parse().ok()?;
-            let minute: u32 = parts[1].parse().ok()?;
-            let second: u32 = if parts.len() > 2 {
-                parts[2].parse().ok()?
-            } else {
-                0
-            };
-            NaiveTime::from_hms_opt(hour, minute, second)
-        } else {
-            None
-        }
-    } else {
-        None
-    }
+/// How many actions `u` can step back through
+pub const UNDO_LIMIT: usize = 20;
+
+/// What it takes to reverse one destructive TUI action
+#[derive(Debug, Clone)]
+pub enum UndoEntry {
+    /// A task moved to the trash, restored along with its subtasks
+    Deleted { id: i64, description: String },
+    /// (id, completed, completed_at) of each task a toggle changed, before it
+    /// ran, and the next occurrences it added for recurring tasks
+    Completion {
+        description: String,
+        previous: Vec<(i64, bool, Option<DateTime<Utc>>)>,
+        spawned: Vec<i64>,
+    },
 }
 
-impl App {
-    pub async fn new(pool: SqlitePool) -> Self {
-        let nlp_parser = Arc::new(NLPParser::new().await);
+/// Where a new task lands in the `item_order` sequence
+#[derive(Debug, Clone, Copy)]
+pub enum InsertPosition {
+    Top,
+    After(i64),
+    End,
+}
 
-        Self {
-            db_pool: pool,
-            tasks: Vec::new(),
-            selected: 0,
-            input_mode: InputMode::Normal,
-            view_mode: ViewMode::TodoList,
-            calendar_week_offset: None,
-            selected_day: 0,
-            selected_time_slot: 0,
-            calendar_input_mode: CalendarInputMode::Navigate,
-            block_form: BlockFormState::new_at(0),
-            task_picker_selected: 0,
-            input_buffer: String::new(),
-            nlp_parser,
-            cached_schedule_blocks: Vec::new(),
-            cached_scheduled_tasks: Vec::new(),
-            status_message: None,
+/// Reserve a collision-free `item_order` for a new task. Both the TUI and the
+/// daemon insert through this, inside the same write transaction as the INSERT,
+/// so concurrent writers can't hand out the same order twice.
+pub async fn reserve_item_order(
+    conn: &mut SqliteConnection,
+    position: InsertPosition,
+) -> Result<i64, sqlx::Error> {
+    let target = match position {
+        InsertPosition::Top => 0,
+        InsertPosition::After(order) => order + 1,
+        InsertPosition::End => {
+            return sqlx::query_scalar("SELECT COALESCE(MAX(item_order), -1) + 1 FROM tasks")
+                .fetch_one(&mut *conn)
+                .await;
         }
-    }
+    };
 
-    pub async fn refresh_calendar_data(&mut self) {
-        let today = chrono::Local::now().naive_local().date();
-        let week_offset = self.calendar_week_offset.unwrap_or(0);
-        let start_of_week = today + Duration::weeks(week_offset)
-            - Duration::days(today.weekday().num_days_from_monday() as i64);
+    sqlx::query("UPDATE tasks SET item_order = item_order + 1 WHERE item_order >= ?")
+        .bind(target)
+        .execute(&mut *conn)
+        .await?;
 
-        let days: Vec<NaiveDate> = (0..7).map(|i| start_of_week + Duration::days(i)).collect();
+    Ok(target)
+}
 
-        self.cached_schedule_blocks = self
-            .get_week_schedule_internal(&days)
-            .await
-            .unwrap_or_default();
+/// Keyword-based category used to match tasks to schedule block types
+pub fn classify_description(description: &str) -> &'static str {
+    let lower = description.to_lowercase();
+
+    if lower.contains("leetcode")
+        || lower.contains("project")
+        || lower.contains("code")
+        || lower.contains("implement")
+        || lower.contains("study")
+        || lower.contains("homework")
+    {
+        return "deepwork";
+    }
 
-        self.cached_scheduled_tasks = self
-            .get_scheduled_tasks_internal(&days)
-            .await
-            .unwrap_or_default();
+    if lower.contains("schedule") || lower.contains("call") || lower.contains("quick") {
+        return "admin";
     }
 
-    async fn get_week_schedule_internal(
-        &self,
-        days: &[NaiveDate],
-    ) -> Result<Vec<(NaiveDate, ScheduleBlock)>, sqlx::Error> {
-        let blocks = sqlx::query_as::<_, ScheduleBlock>(
-            "SELECT id, day_of_week, start_time, end_time, block_type, title, description, priority FROM schedule_blocks"
-        )
-        .fetch_all(&self.db_pool)
-        .await?;
+    if lower.contains("read")
+        || lower.contains("watch")
+        || lower.contains("learn")
+        || lower.contains("review")
+    {
+        return "learning";
+    }
 
-        let mut result = Vec::new();
-        for block in blocks {
-            for day in days {
-                if day.weekday().num_days_from_monday() == block.day_of_week as u32 {
-                    result.push((*day, block.clone()));
-                    break;
-                }
+    "general"
+}
+
+/// The parser resolves "3pm" to an instant; tasks store the wall-clock time
+/// in the planning timezone (see `timezone`), like every other scheduled_at
+fn wall_clock(at: DateTime<Utc>) -> DateTime<Utc> {
+    crate::timezone::wall_clock(at).and_utc()
+}
+
+/// A parsed item's task columns: (title, scheduled_at, time known, priority, tags)
+pub fn item_fields(item: ParsedItem) -> (String, Option<DateTime<Utc>>, bool, i32, Vec<String>) {
+    match item {
+        ParsedItem::Task(nlp_task) => (
+            nlp_task.title,
+            nlp_task.due_date.map(wall_clock),
+            nlp_task.has_time,
+            nlp_task.priority.to_level(),
+            nlp_task.tags,
+        ),
+        ParsedItem::Event(event) => (
+            event.title,
+            Some(wall_clock(event.start_time)),
+            event.has_time,
+            1,
+            event.tags,
+        ),
+        ParsedItem::Email(email) => {
+            let title = email.task_title();
+            let mut tags = email.tags;
+            if !tags.iter().any(|tag| tag.eq_ignore_ascii_case("email")) {
+                tags.push("email".to_string());
             }
+            (
+                title,
+                email.scheduled_time.map(wall_clock),
+                email.has_time,
+                email.priority.to_level(),
+                tags,
+            )
         }
-
-        Ok(result)
     }
+}
 
-    async fn get_scheduled_tasks_internal(
-        &self,
-        days: &[NaiveDate],
-    ) -> Result<Vec<(NaiveDate, NaiveTime, String, i32)>, sqlx::Error> {
-        let start = days[0].and_hms_opt(0, 0, 0).unwrap().and_utc();
-        let end = days[days.len() - 1]
-            .and_hms_opt(23, 59, 59)
-            .unwrap()
-            .and_utc();
+/// Re-parse `description` and overwrite a task's text, priority, tags and
+/// schedule while keeping its `item_order` and completion state. Shared by
+/// the CLI/TUI and the daemon so both paths parse edits identically.
+pub async fn update_task_from_input(
+    db: &SqlitePool,
+    nlp: &NLPParser,
+    id: i64,
+    description: &str,
+) -> Result<bool, sqlx::Error> {
+    let parse_result = nlp
+        .parse(description)
+        .await
+        .map_err(|e| sqlx::Error::Protocol(format!("NLP parsing failed: {}", e)))?;
+
+    // An edit rewrites one task, so only the primary item applies
+    let (task_title, scheduled_at, time_known, priority_value, tags_list) =
+        item_fields(parse_result.item);
+
+    let tags_json = if tags_list.is_empty() {
+        None
+    } else {
+        Some(serde_json::to_string(&tags_list).unwrap_or_default())
+    };
+
+    let rows_affected = sqlx::query(
+        "UPDATE tasks SET description = ?, priority = ?, natural_language_input = ?, tags = ?, scheduled_at = ?, scheduled_time_known = ?, task_category = ? WHERE id = ? AND deleted_at IS NULL",
+    )
+    .bind(&task_title)
+    .bind(priority_value)
+    .bind(description)
+    .bind(tags_json)
+    .bind(scheduled_at)
+    .bind(time_known)
+    .bind(classify_description(&task_title))
+    .bind(id)
+    .execute(db)
+    .await?
+    .rows_affected();
+
+    Ok(rows_affected > 0)
+}
 
-        let tasks = sqlx::query_as::<_, Task>(
-            r#"
-            SELECT id, description, completed, item_order, scheduled_at, priority, tags, natural_language_input, task_category
-            FROM tasks
-            WHERE scheduled_at >= ? AND scheduled_at < ?
-            AND completed = 0
-            ORDER BY scheduled_at
-            "#
-        )
-        .bind(start)
-        .bind(end)
-        .fetch_all(&self.db_pool)
-        .await?;
+/// All tasks in `sort` order, each followed by its subtasks (also in `sort`
+/// order). Shared by the TUI/CLI and the daemon.
+pub async fn load_task_list(db: &SqlitePool, sort: SortMode) -> Result<Vec<Task>, sqlx::Error> {
+    let query = format!(
+        "SELECT id, description, completed, item_order, scheduled_at, priority, tags, natural_language_input, task_category, scheduled_time_known, parent_id, notes, completed_at, deleted_at, recurrence_rule FROM tasks WHERE deleted_at IS NULL ORDER BY {}",
+        App::order_by_clause(sort)
+    );
+    let tasks = sqlx::query_as::<_, Task>(&query).fetch_all(db).await?;
+    Ok(nest_subtasks(tasks))
+}
 
-        Ok(tasks
-            .iter()
-            .filter_map(|t| {
-                t.scheduled_at.map(|dt| {
-                    (
-                        dt.date_naive(),
-                        dt.time(),
-                        t.description.clone(),
-                        t.priority,
-                    )
-                })
-            })
-            .collect())
+/// The chain of dependency edges leading from `from` to `to`, both included,
+/// if there is one. A task counts as reaching itself.
+fn dependency_path(edges: &[(i64, i64)], from: i64, to: i64) -> Option<Vec<i64>> {
+    let mut came_from: HashMap<i64, i64> = HashMap::new();
+    let mut queue = std::collections::VecDeque::from([from]);
+    let mut seen = HashSet::from([from]);
+
+    while let Some(current) = queue.pop_front() {
+        if current == to {
+            let mut path = vec![current];
+            let mut step = current;
+            while let Some(&previous) = came_from.get(&step) {
+                path.push(previous);
+                step = previous;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for &(task, depends_on) in edges {
+            if task == current && seen.insert(depends_on) {
+                came_from.insert(depends_on, current);
+                queue.push_back(depends_on);
+            }
+        }
     }
+    None
+}
 
-    pub async fn next_week(&mut self) {
-        let offset = self.calendar_week_offset.unwrap_or(0);
-        self.calendar_week_offset = Some(offset + 1);
-        self.refresh_calendar_data().await;
+/// Reorder a sorted list so every subtask sits directly below its parent,
+/// keeping the sort among siblings. Tasks whose parent isn't in the list are
+/// treated as top-level.
+fn nest_subtasks(tasks: Vec<Task>) -> Vec<Task> {
+    let ids: HashSet<i64> = tasks.iter().map(|t| t.id).collect();
+    let mut children: HashMap<i64, Vec<usize>> = HashMap::new();
+    let mut roots = Vec::new();
+    for (i, task) in tasks.iter().enumerate() {
+        match task.parent_id.filter(|parent| ids.contains(parent)) {
+            Some(parent) => children.entry(parent).or_default().push(i),
+            None => roots.push(i),
+        }
     }
 
-    pub async fn prev_week(&mut self) {
-        let offset = self.calendar_week_offset.unwrap_or(0);
-        self.calendar_week_offset = Some(offset - 1);
-        self.refresh_calendar_data().await;
+    let mut order = Vec::with_capacity(tasks.len());
+    let mut placed = vec![false; tasks.len()];
+    let mut stack: Vec<usize> = roots.into_iter().rev().collect();
+    while let Some(i) = stack.pop() {
+        if std::mem::replace(&mut placed[i], true) {
+            continue;
+        }
+        order.push(i);
+        if let Some(subtasks) = children.get(&tasks[i].id) {
+            stack.extend(subtasks.iter().rev());
+        }
+    }
+    // Tasks caught in a parent cycle have no top-level ancestor; keep them last
+    order.extend((0..tasks.len()).filter(|i| !placed[*i]));
+
+    let mut slots: Vec<Option<Task>> = tasks.into_iter().map(Some).collect();
+    order.into_iter().filter_map(|i| slots[i].take()).collect()
+}
+
+/// How many levels each task is nested under other tasks in the same list
+pub fn subtask_depths<'a>(tasks: impl IntoIterator<Item = &'a Task>) -> HashMap<i64, usize> {
+    let parents: HashMap<i64, Option<i64>> = tasks
+        .into_iter()
+        .map(|task| (task.id, task.parent_id))
+        .collect();
+
+    parents
+        .iter()
+        .map(|(&id, &parent)| {
+            let mut depth = 0;
+            let mut current = parent;
+            while let Some(parent) = current
+                && let Some(&grandparent) = parents.get(&parent)
+                && depth < parents.len()
+            {
+                depth += 1;
+                current = grandparent;
+            }
+            (id, depth)
+        })
+        .collect()
+}
+
+/// Pair each task with its parsed tag list
+pub fn enhance_tasks(tasks: &[Task]) -> Vec<EnhancedTaskInfo> {
+    tasks
+        .iter()
+        .map(|task| EnhancedTaskInfo {
+            task: task.clone(),
+            tags: parse_tags(task.tags.as_deref()),
+            is_scheduled: task.scheduled_at.is_some(),
+        })
+        .collect()
+}
+
+fn parse_tags(json: Option<&str>) -> Vec<String> {
+    json.and_then(|json| serde_json::from_str(json).ok())
+        .unwrap_or_default()
+}
+
+/// A tag as typed on the command line: "#Work" and "work" are the same tag
+fn normalize_tag(tag: &str) -> String {
+    tag.trim().trim_start_matches('#').to_string()
+}
+
+/// Drop repeats of a tag (ignoring case), keeping the first spelling
+fn dedupe_tags(tags: Vec<String>) -> Vec<String> {
+    let mut unique: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        if !unique.iter().any(|seen| seen.eq_ignore_ascii_case(&tag)) {
+            unique.push(tag);
+        }
+    }
+    unique
+}
+
+/// Keep tasks carrying every tag in `tags` (case-insensitive, leading '#'
+/// optional) and, if given, one priority level
+pub fn filter_task_list(
+    list: Vec<EnhancedTaskInfo>,
+    tags: &[String],
+    priority: Option<i32>,
+) -> Vec<EnhancedTaskInfo> {
+    let wanted: Vec<String> = tags
+        .iter()
+        .map(|t| t.trim_start_matches('#').to_lowercase())
+        .collect();
+
+    list.into_iter()
+        .filter(|enhanced| {
+            wanted
+                .iter()
+                .all(|w| enhanced.tags.iter().any(|t| t.to_lowercase() == *w))
+        })
+        .filter(|enhanced| priority.is_none_or(|p| enhanced.task.priority == p))
+        .collect()
+}
+
+pub async fn complete_task(db: &SqlitePool, id: i64) -> Result<bool, sqlx::Error> {
+    set_task_completed(db, id, true).await
+}
+
+/// Mark a task done, along with all of its subtasks, or reopen just that task.
+/// Either way its ancestors are then rolled up. Completion is stamped in
+/// `completed_at` (tasks already done keep their stamp) and cleared on reopen.
+/// Completing an open recurring task adds its next occurrence. Returns false
+/// if there is no such task.
+pub async fn set_task_completed(
+    db: &SqlitePool,
+    id: i64,
+    completed: bool,
+) -> Result<bool, sqlx::Error> {
+    let mut tx = db.begin().await?;
+    let recurring = if completed {
+        sqlx::query_as::<_, Task>(
+            "SELECT id, description, completed, item_order, scheduled_at, priority, tags, natural_language_input, task_category, scheduled_time_known, parent_id, notes, completed_at, deleted_at, recurrence_rule
+             FROM tasks WHERE id = ? AND completed = false AND deleted_at IS NULL AND recurrence_rule IS NOT NULL",
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+    } else {
+        None
+    };
+    let rows_affected = if completed {
+        sqlx::query(
+            "WITH RECURSIVE subtree(id) AS (
+                 SELECT ?
+                 UNION SELECT tasks.id FROM tasks JOIN subtree ON tasks.parent_id = subtree.id
+             )
+             UPDATE tasks SET completed = true, completed_at = COALESCE(completed_at, ?)
+             WHERE id IN (SELECT id FROM subtree) AND deleted_at IS NULL",
+        )
+        .bind(id)
+        .bind(completion_stamp())
+        .execute(&mut *tx)
+        .await?
+        .rows_affected()
+    } else {
+        sqlx::query("UPDATE tasks SET completed = false, completed_at = NULL WHERE id = ? AND deleted_at IS NULL")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected()
+    };
+    if rows_affected == 0 {
+        return Ok(false);
+    }
+    if let Some(task) = recurring {
+        spawn_next_occurrence(&mut tx, &task).await?;
+    }
+
+    let parent = parent_of(&mut tx, id).await?;
+    roll_up_completion(&mut tx, parent).await?;
+    tx.commit().await?;
+    Ok(true)
+}
+
+/// Insert the occurrence of a recurring task that follows it, right after it
+/// in the list. An overdue task skips ahead to the first occurrence after now
+/// rather than spawning another overdue one. Returns the new task's id, or
+/// None when the rule has run out (UNTIL has passed) or can't be parsed.
+async fn spawn_next_occurrence(
+    conn: &mut SqliteConnection,
+    task: &Task,
+) -> Result<Option<i64>, sqlx::Error> {
+    let (Some(rule), Some(scheduled_at)) = (
+        task.recurrence_rule
+            .as_deref()
+            .and_then(RecurrenceRule::parse),
+        task.scheduled_at,
+    ) else {
+        return Ok(None);
+    };
+    let scheduled_at = scheduled_at.naive_utc();
+    let now = completion_stamp().naive_utc();
+    let Some(next) = rule.next_after(scheduled_at, scheduled_at.max(now)) else {
+        return Ok(None);
+    };
+
+    let position = match task.item_order {
+        Some(order) => InsertPosition::After(order),
+        None => InsertPosition::End,
+    };
+    let item_order = reserve_item_order(conn, position).await?;
+    let id = sqlx::query(
+        "INSERT INTO tasks (description, completed, item_order, scheduled_at, priority, tags, natural_language_input, task_category, scheduled_time_known, parent_id, notes, recurrence_rule)
+         VALUES (?, false, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    )
+    .bind(&task.description)
+    .bind(item_order)
+    .bind(next.and_utc())
+    .bind(task.priority)
+    .bind(&task.tags)
+    .bind(&task.natural_language_input)
+    .bind(&task.task_category)
+    .bind(task.scheduled_time_known)
+    .bind(task.parent_id)
+    .bind(&task.notes)
+    .bind(&task.recurrence_rule)
+    .execute(&mut *conn)
+    .await?
+    .last_insert_rowid();
+
+    Ok(Some(id))
+}
+
+/// The current local wall-clock time, stored the way `scheduled_at` is
+fn completion_stamp() -> DateTime<Utc> {
+    crate::timezone::now().naive_local().and_utc()
+}
+
+async fn parent_of(conn: &mut SqliteConnection, id: i64) -> Result<Option<i64>, sqlx::Error> {
+    Ok(
+        sqlx::query_scalar::<_, Option<i64>>("SELECT parent_id FROM tasks WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&mut *conn)
+            .await?
+            .flatten(),
+    )
+}
+
+/// Walk up from `parent`, marking each task that still has live subtasks done
+/// exactly when all of them are. Completing the last open subtask completes
+/// the parent; reopening or adding one reopens it.
+async fn roll_up_completion(
+    conn: &mut SqliteConnection,
+    mut parent: Option<i64>,
+) -> Result<(), sqlx::Error> {
+    let mut seen = HashSet::new();
+    while let Some(id) = parent
+        && seen.insert(id)
+    {
+        sqlx::query(
+            "UPDATE tasks
+             SET completed = NOT EXISTS (
+                     SELECT 1 FROM tasks WHERE parent_id = ?1 AND completed = false AND deleted_at IS NULL
+                 ),
+                 completed_at = CASE
+                     WHEN EXISTS (
+                         SELECT 1 FROM tasks WHERE parent_id = ?1 AND completed = false AND deleted_at IS NULL
+                     ) THEN NULL
+                     ELSE COALESCE(completed_at, ?2)
+                 END
+             WHERE id = ?1 AND EXISTS (SELECT 1 FROM tasks WHERE parent_id = ?1 AND deleted_at IS NULL)",
+        )
+        .bind(id)
+        .bind(completion_stamp())
+        .execute(&mut *conn)
+        .await?;
+        parent = parent_of(conn, id).await?;
+    }
+    Ok(())
+}
+
+/// Tasks waiting on at least one incomplete dependency. Trashed tasks don't
+/// block anything.
+pub async fn blocked_task_ids(db: &SqlitePool) -> Result<HashSet<i64>, sqlx::Error> {
+    let ids: Vec<i64> = sqlx::query_scalar(
+        "SELECT DISTINCT d.task_id FROM task_dependencies d
+         JOIN tasks t ON t.id = d.depends_on_id
+         WHERE t.completed = false AND t.deleted_at IS NULL",
+    )
+    .fetch_all(db)
+    .await?;
+    Ok(ids.into_iter().collect())
+}
+
+/// Move a task and all of its subtasks to the trash, stamped with the same
+/// `deleted_at` so `restore_task` brings them back together. The parent it
+/// leaves behind is rolled up. Returns false if there is no such live task.
+pub async fn remove_task(db: &SqlitePool, id: i64) -> Result<bool, sqlx::Error> {
+    let mut tx = db.begin().await?;
+    let removed = trash_task(&mut tx, id).await?;
+    tx.commit().await?;
+    Ok(removed)
+}
+
+/// `remove_task` within the caller's transaction
+async fn trash_task(conn: &mut SqliteConnection, id: i64) -> Result<bool, sqlx::Error> {
+    let parent = parent_of(conn, id).await?;
+    let rows_affected = sqlx::query(
+        "WITH RECURSIVE subtree(id) AS (
+             SELECT id FROM tasks WHERE id = ? AND deleted_at IS NULL
+             UNION SELECT tasks.id FROM tasks JOIN subtree ON tasks.parent_id = subtree.id
+         )
+         UPDATE tasks SET deleted_at = ? WHERE id IN (SELECT id FROM subtree) AND deleted_at IS NULL",
+    )
+    .bind(id)
+    .bind(completion_stamp())
+    .execute(&mut *conn)
+    .await?
+    .rows_affected();
+
+    roll_up_completion(conn, parent).await?;
+    Ok(rows_affected > 0)
+}
+
+/// Move every completed task to the trash, returning how many were moved
+pub async fn clear_completed(db: &SqlitePool) -> Result<u64, sqlx::Error> {
+    let rows_affected = sqlx::query(
+        "UPDATE tasks SET deleted_at = ? WHERE completed = true AND deleted_at IS NULL",
+    )
+    .bind(completion_stamp())
+    .execute(db)
+    .await?
+    .rows_affected();
+
+    Ok(rows_affected)
+}
+
+/// Bring a trashed task back along with the subtasks trashed with it. Trashed
+/// ancestors are restored too so it reappears where it was. Returns false if
+/// the task isn't in the trash.
+pub async fn restore_task(db: &SqlitePool, id: i64) -> Result<bool, sqlx::Error> {
+    let mut tx = db.begin().await?;
+    let Some(deleted_at) =
+        sqlx::query_scalar::<_, Option<DateTime<Utc>>>("SELECT deleted_at FROM tasks WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .flatten()
+    else {
+        return Ok(false);
+    };
+
+    sqlx::query(
+        "WITH RECURSIVE subtree(id) AS (
+             SELECT ?
+             UNION SELECT tasks.id FROM tasks JOIN subtree ON tasks.parent_id = subtree.id
+         )
+         UPDATE tasks SET deleted_at = NULL WHERE id IN (SELECT id FROM subtree) AND deleted_at = ?",
+    )
+    .bind(id)
+    .bind(deleted_at)
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query(
+        "WITH RECURSIVE ancestors(id) AS (
+             SELECT parent_id FROM tasks WHERE id = ?
+             UNION SELECT tasks.parent_id FROM tasks JOIN ancestors ON tasks.id = ancestors.id
+         )
+         UPDATE tasks SET deleted_at = NULL WHERE id IN (SELECT id FROM ancestors)",
+    )
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+
+    let parent = parent_of(&mut tx, id).await?;
+    roll_up_completion(&mut tx, parent).await?;
+    tx.commit().await?;
+    Ok(true)
+}
+
+/// Permanently delete everything in the trash, returning how many tasks were
+/// removed. Live subtasks of a trashed task are kept as top-level tasks.
+pub async fn empty_trash(db: &SqlitePool) -> Result<u64, sqlx::Error> {
+    let mut tx = db.begin().await?;
+    sqlx::query(
+        "UPDATE tasks SET parent_id = NULL
+         WHERE deleted_at IS NULL
+           AND parent_id IN (SELECT id FROM tasks WHERE deleted_at IS NOT NULL)",
+    )
+    .execute(&mut *tx)
+    .await?;
+    // Counted up front: rows removed by ON DELETE CASCADE aren't in rows_affected
+    let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM tasks WHERE deleted_at IS NOT NULL")
+        .fetch_one(&mut *tx)
+        .await?;
+    sqlx::query("DELETE FROM tasks WHERE deleted_at IS NOT NULL")
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+
+    Ok(count as u64)
+}
+
+/// Parse a schedule block's "HH:MM" or "HH:MM:SS" time
+pub fn parse_time_string(time_str: &str) -> Option<NaiveTime> {
+    if time_str.contains(':') {
+        let parts: Vec<&str> = time_str.split(':').collect();
+        if parts.len() >= 2 {
+            let hour: u32 = parts[0].parse().ok()?;
+            let minute: u32 = parts[1].parse().ok()?;
+            let second: u32 = if parts.len() > 2 {
+                parts[2].parse().ok()?
+            } else {
+                0
+            };
+            NaiveTime::from_hms_opt(hour, minute, second)
+        } else {
+            None
+        }
+    } else {
+        None
+    }
+}
+
+impl App {
+    pub async fn new(pool: SqlitePool) -> Self {
+        let nlp_parser = Arc::new(NLPParser::new().await);
+        let display = DisplayConfig::from_env();
+        let today = display.logical_date(crate::timezone::now().naive_local());
+
+        Self {
+            db_pool: pool,
+            tasks: Vec::new(),
+            selected: 0,
+            input_mode: InputMode::Normal,
+            view_mode: ViewMode::TodoList,
+            sort_mode: SortMode::default(),
+            calendar_anchor: today,
+            selected_time_slot: 0,
+            calendar_input_mode: CalendarInputMode::Navigate,
+            block_form: BlockFormState::new_at(
+                display.calendar_slot_time(0),
+                display.calendar_slot_minutes,
+            ),
+            task_picker_selected: 0,
+            input_buffer: String::new(),
+            nlp_parser,
+            calendar_data: CalendarData::default(),
+            cached_block_types: Vec::new(),
+            cached_tags: Vec::new(),
+            status_message: None,
+            display,
+            input_error: None,
+            conflicting_task_ids: HashSet::new(),
+            blocked_task_ids: HashSet::new(),
+            task_depths: HashMap::new(),
+            search_results: Vec::new(),
+            search_selected: 0,
+            pending_selection: None,
+            undo_stack: Vec::new(),
+            sync_status: None,
+        }
+    }
+
+    pub async fn refresh_calendar_data(&mut self) {
+        let days = self.visible_days();
+        self.calendar_data = self.get_calendar_data(&days).await.unwrap_or_default();
+        self.cached_block_types = self.list_block_types().await.unwrap_or_default();
+    }
+
+    /// Schedule blocks, timed tasks, all-day items and event segments for
+    /// `days`, which must be consecutive and in order
+    pub async fn get_calendar_data(&self, days: &[NaiveDate]) -> Result<CalendarData, sqlx::Error> {
+        if days.is_empty() {
+            return Ok(CalendarData::default());
+        }
+
+        Ok(CalendarData {
+            schedule_blocks: self.get_week_schedule_internal(days).await?,
+            scheduled_tasks: self.get_scheduled_tasks_internal(days).await?,
+            all_day: self.get_all_day_internal(days).await?,
+            events: self.get_week_events_internal(days).await?,
+        })
+    }
+
+    async fn get_week_events_internal(
+        &self,
+        days: &[NaiveDate],
+    ) -> Result<Vec<EventSegment>, sqlx::Error> {
+        self.query_week_events(days, false).await
+    }
+
+    /// Day segments of either the timed or the all-day events overlapping `days`
+    async fn query_week_events(
+        &self,
+        days: &[NaiveDate],
+        all_day: bool,
+    ) -> Result<Vec<EventSegment>, sqlx::Error> {
+        let start = days[0].and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let end = (days[days.len() - 1] + Duration::days(1))
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc();
+
+        // Anything overlapping the week, including events that began earlier,
+        // plus every recurring series that has started by the end of the week
+        let events: Vec<RecurringEventRow> = sqlx::query_as(
+            "SELECT title, start_time, end_time, recurrence_rule, exdates FROM events
+             WHERE start_time < ? AND (end_time >= ? OR recurrence_rule IS NOT NULL)
+               AND (COALESCE(event_type, 'event') = 'all_day') = ?
+             ORDER BY start_time",
+        )
+        .bind(end)
+        .bind(start)
+        .bind(all_day)
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        let mut segments = Vec::new();
+        for (title, event_start, event_end, rule, exdates) in &events {
+            let (event_start, event_end) = (event_start.naive_utc(), event_end.naive_utc());
+
+            // Rules we can't evaluate fall back to the single stored occurrence
+            let Some(rule) = rule.as_deref().and_then(RecurrenceRule::parse) else {
+                segments.extend(event_day_segments(title, event_start, event_end, days));
+                continue;
+            };
+
+            let duration = event_end - event_start;
+            let exdates = parse_exdates(exdates.as_deref());
+            for occurrence in rule.occurrences_between(
+                event_start,
+                duration.max(Duration::hours(1)),
+                start.naive_utc(),
+                end.naive_utc(),
+                &exdates,
+            ) {
+                segments.extend(event_day_segments(
+                    title,
+                    occurrence,
+                    occurrence + duration,
+                    days,
+                ));
+            }
+        }
+
+        segments.sort_by_key(|segment| (segment.date, segment.start));
+        Ok(segments)
+    }
+
+    /// Distinct block types actually present in the schedule
+    pub async fn list_block_types(&self) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar("SELECT DISTINCT block_type FROM schedule_blocks ORDER BY block_type")
+            .fetch_all(&self.db_pool)
+            .await
+    }
+
+    async fn get_week_schedule_internal(
+        &self,
+        days: &[NaiveDate],
+    ) -> Result<Vec<(NaiveDate, ScheduleBlock)>, sqlx::Error> {
+        let blocks = sqlx::query_as::<_, ScheduleBlock>(
+            "SELECT id, day_of_week, start_time, end_time, block_type, title, description, priority FROM schedule_blocks"
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        let mut result = Vec::new();
+        for block in blocks {
+            for day in days {
+                if day.weekday().num_days_from_monday() == block.day_of_week as u32 {
+                    result.push((*day, block.clone()));
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    async fn get_scheduled_tasks_internal(
+        &self,
+        days: &[NaiveDate],
+    ) -> Result<Vec<(NaiveDate, NaiveTime, String, i32)>, sqlx::Error> {
+        let mut scheduled = Vec::new();
+        for &day in days {
+            for task in self.get_tasks_for_day(day).await? {
+                if !task.scheduled_time_known {
+                    continue;
+                }
+                if let Some(dt) = task.scheduled_at.filter(|_| !task.completed) {
+                    scheduled.push((day, dt.time(), task.description, task.priority));
+                }
+            }
+        }
+        Ok(scheduled)
+    }
+
+    async fn get_all_day_internal(
+        &self,
+        days: &[NaiveDate],
+    ) -> Result<Vec<(NaiveDate, String, i32)>, sqlx::Error> {
+        let mut all_day = Vec::new();
+        for &day in days {
+            for task in self.get_tasks_for_day(day).await? {
+                if !task.completed && !task.scheduled_time_known {
+                    all_day.push((day, task.description, task.priority));
+                }
+            }
+        }
+
+        for segment in self.query_week_events(days, true).await? {
+            all_day.push((segment.date, segment.title, 0));
+        }
+        all_day.sort_by_key(|(date, _, _)| *date);
+        Ok(all_day)
+    }
+
+    /// Tasks scheduled on `date`, ordered by time and then highest priority first.
+    ///
+    /// Scheduled times are stored as local wall-clock values in UTC columns, so a
+    /// day runs from local midnight to the next local midnight in stored terms.
+    /// Keep day filtering here rather than re-deriving the boundaries per feature.
+    pub async fn get_tasks_for_day(&self, date: NaiveDate) -> Result<Vec<Task>, sqlx::Error> {
+        let start = date.and_time(NaiveTime::MIN).and_utc();
+        let end = start + Duration::days(1);
+
+        sqlx::query_as::<_, Task>(
+            r#"
+            SELECT id, description, completed, item_order, scheduled_at, priority, tags, natural_language_input, task_category, scheduled_time_known, parent_id, notes, completed_at, deleted_at, recurrence_rule
+            FROM tasks
+            WHERE scheduled_at >= ? AND scheduled_at < ? AND deleted_at IS NULL
+            ORDER BY scheduled_at ASC, priority DESC, id ASC
+            "#,
+        )
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.db_pool)
+        .await
+    }
+
+    /// Full-text search over task descriptions, event titles and email
+    /// subjects/bodies. Every word is matched as a prefix, and hits are
+    /// ordered most recent first, then by relevance; undated tasks come last.
+    pub async fn search(&self, query: &str) -> Result<Vec<SearchResult>, sqlx::Error> {
+        let Some(fts_query) = fts_match_query(query) else {
+            return Ok(Vec::new());
+        };
+
+        let rows: Vec<SearchRow> = sqlx::query_as(
+            r#"
+            SELECT kind, id, title, at, completed FROM (
+                SELECT 'task' AS kind, t.id AS id, t.description AS title, t.scheduled_at AS at,
+                       t.completed AS completed, bm25(tasks_fts) AS rank
+                FROM tasks_fts JOIN tasks t ON t.id = tasks_fts.rowid
+                WHERE tasks_fts MATCH ?1 AND t.deleted_at IS NULL
+                UNION ALL
+                SELECT 'event', e.id, e.title, e.start_time, 0, bm25(events_fts)
+                FROM events_fts JOIN events e ON e.id = events_fts.rowid
+                WHERE events_fts MATCH ?1
+                UNION ALL
+                SELECT 'email', m.id, m.subject, m.received_at, 0, bm25(emails_fts)
+                FROM emails_fts JOIN emails m ON m.id = emails_fts.rowid
+                WHERE emails_fts MATCH ?1
+            )
+            ORDER BY at IS NULL, at DESC, rank
+            LIMIT ?2
+            "#,
+        )
+        .bind(fts_query)
+        .bind(SEARCH_LIMIT)
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(kind, id, title, at, completed)| SearchResult {
+                kind: match kind.as_str() {
+                    "event" => SearchKind::Event,
+                    "email" => SearchKind::Email,
+                    _ => SearchKind::Task,
+                },
+                id,
+                title,
+                at,
+                completed,
+            })
+            .collect())
+    }
+
+    /// Monday of the week containing the selected date
+    pub fn week_start(&self) -> NaiveDate {
+        self.calendar_anchor
+            - Duration::days(self.calendar_anchor.weekday().num_days_from_monday() as i64)
+    }
+
+    /// Column of the selected date in the weekly grid (0 = Monday)
+    pub fn selected_day(&self) -> usize {
+        self.calendar_anchor.weekday().num_days_from_monday() as usize
+    }
+
+    /// Days the current calendar view needs data for: the selected week, or
+    /// every whole week overlapping the selected month
+    pub fn visible_days(&self) -> Vec<NaiveDate> {
+        let (start, count) = match self.view_mode {
+            ViewMode::Month => {
+                let first = self
+                    .calendar_anchor
+                    .with_day(1)
+                    .unwrap_or(self.calendar_anchor);
+                let last = first
+                    .checked_add_months(Months::new(1))
+                    .map(|next| next - Duration::days(1))
+                    .unwrap_or(first);
+                let start = first - Duration::days(first.weekday().num_days_from_monday() as i64);
+                let end = last + Duration::days(6 - last.weekday().num_days_from_monday() as i64);
+                (start, (end - start).num_days() + 1)
+            }
+            _ => (self.week_start(), 7),
+        };
+        (0..count).map(|i| start + Duration::days(i)).collect()
+    }
+
+    /// Move the selected date, staying within MAX_WEEK_OFFSET weeks of today
+    fn set_anchor(&mut self, date: NaiveDate) {
+        let today = self
+            .display
+            .logical_date(crate::timezone::now().naive_local());
+        let limit = Duration::weeks(MAX_WEEK_OFFSET);
+        self.calendar_anchor = date.clamp(today - limit, today + limit);
+    }
+
+    /// Move the selected date by `days`. Jumps beyond chrono's date range
+    /// clamp to MAX_WEEK_OFFSET like any other instead of being ignored
+    pub fn shift_anchor_days(&mut self, days: i64) {
+        let target = Duration::try_days(days)
+            .and_then(|delta| self.calendar_anchor.checked_add_signed(delta))
+            .unwrap_or(if days > 0 {
+                NaiveDate::MAX
+            } else {
+                NaiveDate::MIN
+            });
+        self.set_anchor(target);
+    }
+
+    pub fn shift_anchor_months(&mut self, months: i32) {
+        let anchor = self.calendar_anchor;
+        let target = if months >= 0 {
+            anchor
+                .checked_add_months(Months::new(months.unsigned_abs()))
+                .unwrap_or(NaiveDate::MAX)
+        } else {
+            anchor
+                .checked_sub_months(Months::new(months.unsigned_abs()))
+                .unwrap_or(NaiveDate::MIN)
+        };
+        self.set_anchor(target);
+    }
+
+    pub async fn next_week(&mut self) {
+        self.shift_anchor_days(7);
+        self.refresh_calendar_data().await;
+    }
+
+    pub async fn prev_week(&mut self) {
+        self.shift_anchor_days(-7);
+        self.refresh_calendar_data().await;
+    }
+
+    /// Step forward by the current view's unit: a day, a week or a month
+    pub async fn next_period(&mut self) {
+        self.shift_period(1).await;
+    }
+
+    pub async fn prev_period(&mut self) {
+        self.shift_period(-1).await;
+    }
+
+    async fn shift_period(&mut self, delta: i32) {
+        match self.view_mode {
+            ViewMode::Day => self.shift_anchor_days(delta as i64),
+            ViewMode::Month => self.shift_anchor_months(delta),
+            _ => self.shift_anchor_days(delta as i64 * 7),
+        }
+        self.refresh_calendar_data().await;
+    }
+
+    /// Cycle week → day → month → week, keeping the selected date
+    pub async fn cycle_calendar_view(&mut self) {
+        self.view_mode = match self.view_mode {
+            ViewMode::Calendar => ViewMode::Day,
+            ViewMode::Day => ViewMode::Month,
+            _ => ViewMode::Calendar,
+        };
+        self.refresh_calendar_data().await;
+    }
+
+    pub async fn open_day_view(&mut self) {
+        self.view_mode = ViewMode::Day;
+        self.refresh_calendar_data().await;
     }
 
     pub async fn toggle_to_calendar(&mut self) {
@@ -361,31 +1737,7 @@ impl App {
     }
 
     pub fn classify_task(&self, description: &str) -> &str {
-        let lower = description.to_lowercase();
-
-        if lower.contains("leetcode")
-            || lower.contains("project")
-            || lower.contains("code")
-            || lower.contains("implement")
-            || lower.contains("study")
-            || lower.contains("homework")
-        {
-            return "deepwork";
-        }
-
-        if lower.contains("schedule") || lower.contains("call") || lower.contains("quick") {
-            return "admin";
-        }
-
-        if lower.contains("read")
-            || lower.contains("watch")
-            || lower.contains("learn")
-            || lower.contains("review")
-        {
-            return "learning";
-        }
-
-        "general"
+        classify_description(description)
     }
 
     pub async fn get_week_schedule(
@@ -411,227 +1763,1356 @@ impl App {
             schedule_by_day.push((day, blocks));
         }
 
-        Ok(schedule_by_day)
+        Ok(schedule_by_day)
+    }
+
+    pub async fn build() -> Result<Self, sqlx::Error> {
+        let db_url = paths::database_url().map_err(sqlx::Error::Io)?;
+        if !Sqlite::database_exists(&db_url).await.unwrap_or(false) {
+            if let Some(legacy) = paths::legacy_database_path() {
+                tracing::warn!(
+                    "⚠️  Starting a new database at {}; move {} there to keep its data",
+                    paths::database_path().display(),
+                    legacy.display()
+                );
+            }
+            Sqlite::create_database(&db_url).await?;
+        }
+
+        let db_pool = SqlitePool::connect(&db_url).await?;
+        migrations::adopt_unversioned_schema(&db_pool).await?;
+        migrations::MIGRATOR.run(&db_pool).await?;
+
+        let app = Self::new(db_pool).await;
+
+        // Status goes to stderr (and is silenced by --quiet) so machine-readable
+        // stdout (e.g. `list --json`) stays clean
+        if app.nlp_parser.is_ollama_available() {
+            tracing::info!("✓ NLP parsing ready");
+        } else {
+            tracing::warn!("⚠️  Ollama unavailable - limited parsing");
+        }
+
+        Ok(app)
+    }
+
+    pub fn nlp_parser_ref(&self) -> Arc<NLPParser> {
+        Arc::clone(&self.nlp_parser)
+    }
+
+    pub fn is_ollama_available(&self) -> bool {
+        self.nlp_parser.is_ollama_available()
+    }
+
+    /// ORDER BY clause for a sort mode. Each arm is a fixed string, so nothing
+    /// user-supplied is ever interpolated into the query.
+    pub fn order_by_clause(mode: SortMode) -> &'static str {
+        match mode {
+            SortMode::Manual => "item_order ASC, id ASC",
+            SortMode::Priority => "priority DESC, item_order ASC, id ASC",
+            SortMode::DueDate => "scheduled_at IS NULL, scheduled_at ASC, item_order ASC, id ASC",
+            SortMode::Alphabetical => "description COLLATE NOCASE ASC, item_order ASC, id ASC",
+        }
+    }
+
+    pub async fn load_tasks(&mut self) -> Result<(), sqlx::Error> {
+        self.tasks = load_task_list(&self.db_pool, self.sort_mode).await?;
+        self.task_depths = subtask_depths(&self.tasks);
+        self.blocked_task_ids = blocked_task_ids(&self.db_pool).await?;
+
+        let blocks = self.load_schedule_blocks().await?;
+        self.conflicting_task_ids = self
+            .tasks
+            .iter()
+            .filter(|task| !detect_conflicts(task, &blocks, &self.tasks).is_empty())
+            .map(|task| task.id)
+            .collect();
+
+        if let Some(task_id) = self.pending_selection.take() {
+            // Remembered task may have been deleted or cleared since last run
+            self.selected = self.tasks.iter().position(|t| t.id == task_id).unwrap_or(0);
+        } else if self.selected >= self.tasks.len() {
+            self.selected = self.tasks.len().saturating_sub(1);
+        }
+        Ok(())
+    }
+
+    async fn load_schedule_blocks(&self) -> Result<Vec<ScheduleBlock>, sqlx::Error> {
+        sqlx::query_as::<_, ScheduleBlock>(
+            "SELECT id, day_of_week, start_time, end_time, block_type, title, description, priority FROM schedule_blocks",
+        )
+        .fetch_all(&self.db_pool)
+        .await
+    }
+
+    /// Schedule blocks and other open tasks that overlap `task`
+    pub async fn find_conflicts(&self, task: &Task) -> Result<Vec<Conflict>, sqlx::Error> {
+        let Some((start, end)) = task_window(task) else {
+            return Ok(Vec::new());
+        };
+
+        // Any task starting less than one slot before `end` can overlap
+        let nearby = sqlx::query_as::<_, Task>(
+            "SELECT id, description, completed, item_order, scheduled_at, priority, tags, natural_language_input, task_category, scheduled_time_known, parent_id, notes, completed_at, deleted_at, recurrence_rule
+             FROM tasks WHERE scheduled_at > ? AND scheduled_at < ? AND deleted_at IS NULL",
+        )
+        .bind((start - Duration::minutes(TASK_SLOT_MINUTES)).and_utc())
+        .bind(end.and_utc())
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        let blocks = self.load_schedule_blocks().await?;
+        Ok(detect_conflicts(task, &blocks, &nearby))
+    }
+
+    /// Load the task selected in the previous session; applied on the next `load_tasks`
+    pub async fn restore_selection(&mut self) -> Result<(), sqlx::Error> {
+        self.pending_selection = self
+            .get_state(SELECTED_TASK_KEY)
+            .await?
+            .and_then(|v| v.parse().ok());
+        Ok(())
+    }
+
+    /// Persist the currently selected task so it can be restored after a restart
+    pub async fn save_selection(&self) -> Result<(), sqlx::Error> {
+        match self.tasks.get(self.selected) {
+            Some(task) => {
+                self.set_state(SELECTED_TASK_KEY, &task.id.to_string())
+                    .await
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Load the sort order saved by the TUI
+    pub async fn restore_sort_mode(&mut self) -> Result<(), sqlx::Error> {
+        if let Some(mode) = self
+            .get_state(SORT_MODE_KEY)
+            .await?
+            .and_then(|name| SortMode::from_name(&name))
+        {
+            self.sort_mode = mode;
+        }
+        Ok(())
+    }
+
+    /// Cycle manual, priority, due-date and alphabetical order, remember the
+    /// choice and keep the cursor on the same task
+    pub async fn cycle_sort_mode(&mut self) -> Result<(), sqlx::Error> {
+        self.sort_mode = self.sort_mode.next();
+        self.set_state(SORT_MODE_KEY, self.sort_mode.name()).await?;
+
+        let selected_id = self.tasks.get(self.selected).map(|t| t.id);
+        self.load_tasks().await?;
+        self.select_task(selected_id);
+
+        self.status_message = Some((
+            format!("Sorted by {}", self.sort_mode.name()),
+            std::time::Instant::now(),
+        ));
+        Ok(())
+    }
+
+    pub fn start_search(&mut self) {
+        self.input_buffer.clear();
+        self.search_results.clear();
+        self.search_selected = 0;
+        self.input_mode = InputMode::Searching;
+    }
+
+    /// Re-run the search for the text typed so far
+    pub async fn refresh_search(&mut self) -> Result<(), sqlx::Error> {
+        self.search_results = self.search(&self.input_buffer).await?;
+        self.search_selected = 0;
+        Ok(())
+    }
+
+    pub fn next_search_result(&mut self) {
+        if self.search_selected + 1 < self.search_results.len() {
+            self.search_selected += 1;
+        }
+    }
+
+    pub fn previous_search_result(&mut self) {
+        self.search_selected = self.search_selected.saturating_sub(1);
+    }
+
+    /// Go to the highlighted search hit: a task is selected in the list and an
+    /// event opens the calendar on its day. Emails have no view to open yet.
+    pub async fn open_search_result(&mut self) {
+        let Some(result) = self.search_results.get(self.search_selected).cloned() else {
+            return;
+        };
+
+        match result.kind {
+            SearchKind::Task => self.select_task(Some(result.id)),
+            SearchKind::Event => {
+                if let Some(at) = result.at {
+                    self.calendar_anchor = self.display.logical_date(at.naive_utc());
+                }
+                self.toggle_to_calendar().await;
+            }
+            SearchKind::Email => {
+                self.status_message = Some((
+                    format!("Email: {}", result.title),
+                    std::time::Instant::now(),
+                ));
+            }
+        }
+    }
+
+    /// Replace a task's notes; blank text clears them. Returns false if there
+    /// is no such task.
+    pub async fn set_notes(&mut self, id: i64, text: &str) -> Result<bool, sqlx::Error> {
+        let text = text.trim_end();
+        let notes = (!text.trim().is_empty()).then_some(text);
+
+        let rows_affected =
+            sqlx::query("UPDATE tasks SET notes = ? WHERE id = ? AND deleted_at IS NULL")
+                .bind(notes)
+                .bind(id)
+                .execute(&self.db_pool)
+                .await?
+                .rows_affected();
+
+        if rows_affected > 0 {
+            self.load_tasks().await?;
+        }
+        Ok(rows_affected > 0)
+    }
+
+    /// Make a task repeat by `rule` (an RRULE, validated by the caller), or
+    /// stop it repeating with None. Returns false if there is no such task.
+    pub async fn set_recurrence(
+        &mut self,
+        id: i64,
+        rule: Option<&str>,
+    ) -> Result<bool, sqlx::Error> {
+        let rows_affected =
+            sqlx::query("UPDATE tasks SET recurrence_rule = ? WHERE id = ? AND deleted_at IS NULL")
+                .bind(rule)
+                .bind(id)
+                .execute(&self.db_pool)
+                .await?
+                .rows_affected();
+
+        if rows_affected > 0 {
+            self.load_tasks().await?;
+        }
+        Ok(rows_affected > 0)
+    }
+
+    /// Open the selected task's notes in the editor
+    pub fn start_editing_notes(&mut self) {
+        let Some(task) = self.tasks.get(self.selected) else {
+            return;
+        };
+        self.input_buffer = task.notes.clone().unwrap_or_default();
+        self.input_mode = InputMode::EditingNotes;
+    }
+
+    /// Save the editor's text as the selected task's notes
+    pub async fn save_notes_from_input(&mut self) -> Result<(), sqlx::Error> {
+        let Some(task_id) = self.tasks.get(self.selected).map(|task| task.id) else {
+            return Ok(());
+        };
+        let text = std::mem::take(&mut self.input_buffer);
+        self.set_notes(task_id, &text).await?;
+        self.select_task(Some(task_id));
+        Ok(())
+    }
+
+    /// Record that `task_id` can't start until `depends_on_id` is done,
+    /// refusing links that would make a task wait on itself
+    pub async fn add_dependency(
+        &mut self,
+        task_id: i64,
+        depends_on_id: i64,
+    ) -> Result<DependencyChange, sqlx::Error> {
+        for id in [task_id, depends_on_id] {
+            if self.get_task_by_id(id).await?.is_none() {
+                return Ok(DependencyChange::NotFound(id));
+            }
+        }
+
+        let mut tx = self.db_pool.begin_with("BEGIN IMMEDIATE").await?;
+        let edges: Vec<(i64, i64)> =
+            sqlx::query_as("SELECT task_id, depends_on_id FROM task_dependencies")
+                .fetch_all(&mut *tx)
+                .await?;
+        if edges.contains(&(task_id, depends_on_id)) {
+            return Ok(DependencyChange::Unchanged);
+        }
+        if let Some(path) = dependency_path(&edges, depends_on_id, task_id) {
+            return Ok(DependencyChange::Cycle(path));
+        }
+
+        sqlx::query("INSERT INTO task_dependencies (task_id, depends_on_id) VALUES (?, ?)")
+            .bind(task_id)
+            .bind(depends_on_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        self.load_tasks().await?;
+        Ok(DependencyChange::Added)
+    }
+
+    /// Drop a dependency. Returns false if there was none.
+    pub async fn remove_dependency(
+        &mut self,
+        task_id: i64,
+        depends_on_id: i64,
+    ) -> Result<bool, sqlx::Error> {
+        let rows_affected =
+            sqlx::query("DELETE FROM task_dependencies WHERE task_id = ? AND depends_on_id = ?")
+                .bind(task_id)
+                .bind(depends_on_id)
+                .execute(&self.db_pool)
+                .await?
+                .rows_affected();
+
+        if rows_affected > 0 {
+            self.load_tasks().await?;
+        }
+        Ok(rows_affected > 0)
+    }
+
+    /// Incomplete tasks that `task_id` is waiting on, as (id, description)
+    pub async fn open_blockers(&self, task_id: i64) -> Result<Vec<(i64, String)>, sqlx::Error> {
+        sqlx::query_as(
+            "SELECT t.id, t.description FROM task_dependencies d
+             JOIN tasks t ON t.id = d.depends_on_id
+             WHERE d.task_id = ? AND t.completed = false AND t.deleted_at IS NULL
+             ORDER BY t.id",
+        )
+        .bind(task_id)
+        .fetch_all(&self.db_pool)
+        .await
+    }
+
+    /// Reload what a sync worker or another process may have changed,
+    /// keeping the same task selected
+    pub async fn reload_after_change(&mut self, event: AppEvent) -> Result<(), sqlx::Error> {
+        if event == AppEvent::DatabaseChanged {
+            let selected = self.tasks.get(self.selected).map(|task| task.id);
+            self.load_tasks().await?;
+            self.select_task(selected);
+        }
+        if self.view_mode != ViewMode::TodoList {
+            self.refresh_calendar_data().await;
+        }
+        Ok(())
+    }
+
+    fn select_task(&mut self, id: Option<i64>) {
+        if let Some(index) = id.and_then(|id| self.tasks.iter().position(|t| t.id == id)) {
+            self.selected = index;
+        }
+    }
+
+    async fn get_state(&self, key: &str) -> Result<Option<String>, sqlx::Error> {
+        sqlx::query_scalar("SELECT value FROM app_state WHERE key = ?")
+            .bind(key)
+            .fetch_optional(&self.db_pool)
+            .await
+    }
+
+    async fn set_state(&self, key: &str, value: &str) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO app_state (key, value) VALUES (?, ?) ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+        )
+        .bind(key)
+        .bind(value)
+        .execute(&self.db_pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn add_task(&mut self, description: &str) -> Result<(), sqlx::Error> {
+        let position = if self.tasks.is_empty() {
+            InsertPosition::End
+        } else if self.selected == 0 {
+            InsertPosition::Top
+        } else {
+            InsertPosition::After(
+                self.tasks[self.selected]
+                    .item_order
+                    .unwrap_or(self.tasks.len() as i64),
+            )
+        };
+
+        self.insert_parsed_tasks(description, position, None).await
+    }
+
+    /// Add `description` as a subtask of `parent_id`, after its existing
+    /// subtasks. A completed parent is reopened. Returns false if the parent
+    /// doesn't exist.
+    pub async fn add_subtask(
+        &mut self,
+        parent_id: i64,
+        description: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let Some(parent) = self.get_task_by_id(parent_id).await? else {
+            return Ok(false);
+        };
+
+        let last_subtask: Option<i64> = sqlx::query_scalar(
+            "SELECT MAX(item_order) FROM tasks WHERE parent_id = ? AND deleted_at IS NULL",
+        )
+        .bind(parent_id)
+        .fetch_one(&self.db_pool)
+        .await?;
+        let position = match last_subtask.or(parent.item_order) {
+            Some(order) => InsertPosition::After(order),
+            None => InsertPosition::End,
+        };
+
+        self.insert_parsed_tasks(description, position, Some(parent_id))
+            .await?;
+        Ok(true)
+    }
+
+    /// Parse `description` and insert every item as consecutive tasks from
+    /// `position`, optionally as subtasks of `parent_id`. The first is selected.
+    async fn insert_parsed_tasks(
+        &mut self,
+        description: &str,
+        mut position: InsertPosition,
+        parent_id: Option<i64>,
+    ) -> Result<(), sqlx::Error> {
+        let parse_result = self
+            .nlp_parser
+            .parse(description)
+            .await
+            .map_err(|e| sqlx::Error::Protocol(format!("NLP parsing failed: {}", e)))?;
+
+        // Multi-item inputs become consecutive tasks; the first is selected
+        let items = parse_result.into_items();
+        let added = items.len();
+        let mut first_id = None;
+        let mut tx = self.db_pool.begin_with("BEGIN IMMEDIATE").await?;
+        for item in items {
+            let (task_title, scheduled_at, time_known, priority_value, tags_list) =
+                item_fields(item);
+
+            let tags_json = if tags_list.is_empty() {
+                None
+            } else {
+                Some(serde_json::to_string(&tags_list).unwrap_or_default())
+            };
+
+            let category = self.classify_task(&task_title).to_string();
+
+            let new_order = reserve_item_order(&mut tx, position).await?;
+            let id = sqlx::query(
+                "INSERT INTO tasks (description, completed, item_order, priority, natural_language_input, tags, scheduled_at, scheduled_time_known, task_category, parent_id) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"
+            )
+            .bind(&task_title)
+            .bind(false)
+            .bind(new_order)
+            .bind(priority_value)
+            .bind(description)
+            .bind(tags_json)
+            .bind(scheduled_at)
+            .bind(time_known)
+            .bind(&category)
+            .bind(parent_id)
+            .execute(&mut *tx)
+            .await?
+            .last_insert_rowid();
+
+            first_id.get_or_insert(id);
+            position = InsertPosition::After(new_order);
+        }
+        roll_up_completion(&mut tx, parent_id).await?;
+        tx.commit().await?;
+        let task_id = first_id.unwrap_or_default();
+
+        if added > 1 {
+            self.status_message =
+                Some((format!("Added {} tasks", added), std::time::Instant::now()));
+        }
+
+        self.load_tasks().await?;
+
+        self.selected = self.tasks.iter().position(|t| t.id == task_id).unwrap_or(0);
+
+        if let Some(task) = self.tasks.get(self.selected) {
+            let conflicts = self.find_conflicts(task).await?;
+            if let Some(first) = conflicts.first() {
+                self.status_message = Some((
+                    format!("⚠ Conflicts with {}", first),
+                    std::time::Instant::now(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Swap the selected task with its neighboring sibling (the next task up
+    /// or down with the same parent, carrying its subtasks along) and keep
+    /// the cursor on it
+    pub async fn move_task(&mut self, direction: MoveDirection) -> Result<(), sqlx::Error> {
+        if self.tasks.is_empty() {
+            return Ok(());
+        }
+        if self.sort_mode != SortMode::Manual {
+            self.status_message = Some((
+                "Switch to manual order (o) to reorder tasks".to_string(),
+                std::time::Instant::now(),
+            ));
+            return Ok(());
+        }
+
+        let from = self.selected;
+        let parent_id = self.tasks[from].parent_id;
+        let is_sibling = |i: &usize| self.tasks[*i].parent_id == parent_id;
+        let to = match direction {
+            MoveDirection::Up => (0..from).rev().find(is_sibling),
+            MoveDirection::Down => (from + 1..self.tasks.len()).find(is_sibling),
+        };
+        let Some(to) = to else {
+            return Ok(());
+        };
+        let moved_id = self.tasks[from].id;
+
+        let mut tx = self.db_pool.begin().await?;
+        match (self.tasks[from].item_order, self.tasks[to].item_order) {
+            (Some(from_order), Some(to_order)) if from_order != to_order => {
+                sqlx::query("UPDATE tasks SET item_order = ? WHERE id = ?")
+                    .bind(to_order)
+                    .bind(self.tasks[from].id)
+                    .execute(&mut *tx)
+                    .await?;
+                sqlx::query("UPDATE tasks SET item_order = ? WHERE id = ?")
+                    .bind(from_order)
+                    .bind(self.tasks[to].id)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+            _ => {
+                // Tied or missing orders can't be swapped; renumber in display
+                // order instead, trading the two siblings' positions
+                for (order, task) in self.tasks.iter().enumerate() {
+                    let order = match order {
+                        o if o == from => to,
+                        o if o == to => from,
+                        o => o,
+                    };
+                    sqlx::query("UPDATE tasks SET item_order = ? WHERE id = ?")
+                        .bind(order as i64)
+                        .bind(task.id)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+            }
+        }
+        tx.commit().await?;
+
+        self.load_tasks().await?;
+        self.selected = self
+            .tasks
+            .iter()
+            .position(|t| t.id == moved_id)
+            .unwrap_or(to);
+        Ok(())
+    }
+
+    pub async fn delete_task(&mut self) -> Result<(), sqlx::Error> {
+        if self.tasks.is_empty() {
+            return Ok(());
+        }
+
+        let task = &self.tasks[self.selected];
+        let (task_id, description) = (task.id, task.description.clone());
+
+        if remove_task(&self.db_pool, task_id).await? {
+            self.push_undo(UndoEntry::Deleted {
+                id: task_id,
+                description: description.clone(),
+            });
+            self.status_message = Some((
+                format!("🗑 Moved \"{}\" to the trash (u: undo)", description),
+                std::time::Instant::now(),
+            ));
+        }
+        self.load_tasks().await?;
+        Ok(())
+    }
+
+    fn push_undo(&mut self, entry: UndoEntry) {
+        if self.undo_stack.len() == UNDO_LIMIT {
+            self.undo_stack.remove(0);
+        }
+        self.undo_stack.push(entry);
+    }
+
+    /// Reverse the most recent deletion or completion toggle
+    pub async fn undo(&mut self) -> Result<(), sqlx::Error> {
+        let Some(entry) = self.undo_stack.pop() else {
+            self.status_message = Some(("Nothing to undo".to_string(), std::time::Instant::now()));
+            return Ok(());
+        };
+
+        let mut restored = None;
+        let message = match entry {
+            UndoEntry::Deleted { id, description } => {
+                if !restore_task(&self.db_pool, id).await? {
+                    self.status_message = Some((
+                        format!("\"{}\" is no longer in the trash", description),
+                        std::time::Instant::now(),
+                    ));
+                    return Ok(());
+                }
+                restored = Some(id);
+                format!("↶ Restored \"{}\"", description)
+            }
+            UndoEntry::Completion {
+                description,
+                previous,
+                spawned,
+            } => {
+                let mut tx = self.db_pool.begin().await?;
+                // Added by the toggle itself, so gone for good rather than trashed
+                for id in &spawned {
+                    sqlx::query("DELETE FROM tasks WHERE id = ?")
+                        .bind(id)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+                for (id, completed, completed_at) in &previous {
+                    sqlx::query("UPDATE tasks SET completed = ?, completed_at = ? WHERE id = ?")
+                        .bind(completed)
+                        .bind(completed_at)
+                        .bind(id)
+                        .execute(&mut *tx)
+                        .await?;
+                }
+                tx.commit().await?;
+                format!("↶ Undid toggling \"{}\"", description)
+            }
+        };
+
+        self.load_tasks().await?;
+        self.select_task(restored);
+        self.status_message = Some((message, std::time::Instant::now()));
+        Ok(())
+    }
+
+    pub async fn toggle_completed(&mut self) -> Result<(), sqlx::Error> {
+        if self.tasks.is_empty() {
+            return Ok(());
+        }
+
+        let task = &self.tasks[self.selected];
+        let (task_id, completing) = (task.id, !task.completed);
+        let description = task.description.clone();
+        let blockers = if completing {
+            self.open_blockers(task_id).await?
+        } else {
+            Vec::new()
+        };
+        let before: HashMap<i64, (bool, Option<DateTime<Utc>>)> = self
+            .tasks
+            .iter()
+            .map(|task| (task.id, (task.completed, task.completed_at)))
+            .collect();
+        set_task_completed(&self.db_pool, task_id, completing).await?;
+
+        self.load_tasks().await?;
+        // Subtasks and ancestors change along with the task, so remember
+        // everything that did
+        let previous = self
+            .tasks
+            .iter()
+            .filter_map(|task| {
+                let &(completed, completed_at) = before.get(&task.id)?;
+                (completed != task.completed || completed_at != task.completed_at).then_some((
+                    task.id,
+                    completed,
+                    completed_at,
+                ))
+            })
+            .collect();
+        let spawned = self
+            .tasks
+            .iter()
+            .filter(|task| !before.contains_key(&task.id))
+            .map(|task| task.id)
+            .collect();
+        self.push_undo(UndoEntry::Completion {
+            description,
+            previous,
+            spawned,
+        });
+        if let Some((id, description)) = blockers.first() {
+            self.status_message = Some((
+                format!(
+                    "⚠ Completed while blocked by task #{} \"{}\"",
+                    id, description
+                ),
+                std::time::Instant::now(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Turn a scheduled task into a calendar event: `TASK_SLOT_MINUTES` long
+    /// from its time, since tasks have no duration of their own, or all day for
+    /// a date-only task. The task is linked to the event through
+    /// `scheduled_event_id` and moved to the trash with its subtasks. Returns
+    /// the event id, or None if there is no such task or it has no date.
+    pub async fn convert_task_to_event(&self, task_id: i64) -> Result<Option<i64>, sqlx::Error> {
+        let Some(task) = self.get_task_by_id(task_id).await? else {
+            return Ok(None);
+        };
+        let Some(scheduled_at) = task.scheduled_at else {
+            return Ok(None);
+        };
+
+        let (start, end, event_type) = if task.scheduled_time_known {
+            (
+                scheduled_at,
+                scheduled_at + Duration::minutes(TASK_SLOT_MINUTES),
+                "event",
+            )
+        } else {
+            let start = scheduled_at.date_naive().and_time(NaiveTime::MIN).and_utc();
+            (start, start + Duration::days(1), "all_day")
+        };
+
+        let mut tx = self.db_pool.begin().await?;
+        let event_id = sqlx::query(
+            "INSERT INTO events (title, description, start_time, end_time, event_type, created_at)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&task.description)
+        .bind(task.notes.as_deref().unwrap_or("Converted from task"))
+        .bind(start)
+        .bind(end)
+        .bind(event_type)
+        .bind(chrono::Utc::now())
+        .execute(&mut *tx)
+        .await?
+        .last_insert_rowid();
+
+        sqlx::query("UPDATE tasks SET scheduled_event_id = ? WHERE id = ?")
+            .bind(event_id)
+            .bind(task_id)
+            .execute(&mut *tx)
+            .await?;
+        trash_task(&mut tx, task_id).await?;
+        tx.commit().await?;
+
+        Ok(Some(event_id))
+    }
+
+    /// Convert the selected task into an event, reporting the outcome in the
+    /// status line
+    pub async fn convert_selected_task(&mut self) -> Result<(), sqlx::Error> {
+        let Some(task) = self.tasks.get(self.selected) else {
+            return Ok(());
+        };
+        let (task_id, description) = (task.id, task.description.clone());
+
+        let message = match self.convert_task_to_event(task_id).await? {
+            Some(_) => {
+                self.load_tasks().await?;
+                self.refresh_calendar_data().await;
+                format!(
+                    "◆ Converted \"{}\" to an event; the task is in the trash",
+                    description
+                )
+            }
+            None => format!("\"{}\" has no date to make an event from", description),
+        };
+        self.status_message = Some((message, std::time::Instant::now()));
+        Ok(())
+    }
+
+    pub async fn get_enhanced_task_list(&mut self) -> Result<Vec<EnhancedTaskInfo>, sqlx::Error> {
+        self.load_tasks().await?;
+        Ok(enhance_tasks(&self.tasks))
+    }
+
+    /// Enhanced task list narrowed by `filter_task_list`
+    pub async fn get_filtered_task_list(
+        &mut self,
+        tags: &[String],
+        priority: Option<i32>,
+    ) -> Result<Vec<EnhancedTaskInfo>, sqlx::Error> {
+        let list = self.get_enhanced_task_list().await?;
+        Ok(filter_task_list(list, tags, priority))
+    }
+
+    pub async fn complete_task_by_id(&mut self, id: i64) -> Result<bool, sqlx::Error> {
+        complete_task(&self.db_pool, id).await
+    }
+
+    pub async fn update_task_text(
+        &mut self,
+        id: i64,
+        description: &str,
+    ) -> Result<bool, sqlx::Error> {
+        let updated =
+            update_task_from_input(&self.db_pool, &self.nlp_parser, id, description).await?;
+        if updated {
+            self.load_tasks().await?;
+        }
+        Ok(updated)
+    }
+
+    pub async fn set_priority(&mut self, id: i64, priority: i32) -> Result<bool, sqlx::Error> {
+        let rows_affected =
+            sqlx::query("UPDATE tasks SET priority = ? WHERE id = ? AND deleted_at IS NULL")
+                .bind(priority)
+                .bind(id)
+                .execute(&self.db_pool)
+                .await?
+                .rows_affected();
+
+        if rows_affected > 0 {
+            self.load_tasks().await?;
+        }
+        Ok(rows_affected > 0)
     }
 
-    pub async fn build() -> Result<Self, sqlx::Error> {
-        if !Sqlite::database_exists(DB_URL).await.unwrap_or(false) {
-            Sqlite::create_database(DB_URL).await?;
+    /// Step the selected task through low -> medium -> high -> urgent -> low
+    pub async fn cycle_priority(&mut self) -> Result<(), sqlx::Error> {
+        if self.tasks.is_empty() {
+            return Ok(());
         }
 
-        let db_pool = SqlitePool::connect(DB_URL).await?;
-        sqlx::migrate!("./migrations").run(&db_pool).await?;
+        let task = &self.tasks[self.selected];
+        let task_id = task.id;
+        let next = Priority::from_level((task.priority + 1) % 4);
+        self.set_priority(task_id, next.to_level()).await?;
+        // In priority order the task moves, so follow it
+        self.select_task(Some(task_id));
+        Ok(())
+    }
+
+    pub async fn remove_task_by_id(&mut self, id: i64) -> Result<bool, sqlx::Error> {
+        remove_task(&self.db_pool, id).await
+    }
 
-        let app = Self::new(db_pool).await;
+    pub async fn clear_completed_tasks(&mut self) -> Result<u64, sqlx::Error> {
+        clear_completed(&self.db_pool).await
+    }
 
-        if app.nlp_parser.is_ollama_available() {
-            println!("✓ NLP parsing ready");
+    /// Completed tasks that clearing would move to the trash
+    pub async fn completed_task_count(&self) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(
+            "SELECT COUNT(*) FROM tasks WHERE completed = true AND deleted_at IS NULL",
+        )
+        .fetch_one(&self.db_pool)
+        .await
+    }
+
+    /// Ask for confirmation before clearing, unless there's nothing to clear
+    pub fn start_clearing_completed(&mut self) {
+        if self.tasks.iter().any(|task| task.completed) {
+            self.input_mode = InputMode::ConfirmingClear;
         } else {
-            println!("⚠️  Ollama unavailable - limited parsing");
+            self.status_message = Some((
+                "🧹 No completed tasks to clear".to_string(),
+                std::time::Instant::now(),
+            ));
         }
+    }
 
-        Ok(app)
+    /// Move every completed task to the trash once the user has confirmed
+    pub async fn confirm_clear_completed(&mut self) -> Result<(), sqlx::Error> {
+        self.input_mode = InputMode::Normal;
+        let count = self.clear_completed_tasks().await?;
+        self.status_message = Some((
+            format!(
+                "🧹 Moved {} completed task{} to the trash",
+                count,
+                if count == 1 { "" } else { "s" }
+            ),
+            std::time::Instant::now(),
+        ));
+        self.load_tasks().await?;
+        Ok(())
     }
 
-    pub fn nlp_parser_ref(&self) -> Arc<NLPParser> {
-        Arc::clone(&self.nlp_parser)
+    /// Open the add-task editor with the tags in use ready for completion
+    pub async fn start_adding_task(&mut self) {
+        self.input_mode = InputMode::Editing;
+        self.input_buffer.clear();
+        self.cached_tags = self.tag_counts().await.unwrap_or_default();
     }
 
-    pub async fn load_tasks(&mut self) -> Result<(), sqlx::Error> {
-        self.tasks = sqlx::query_as::<_, Task>(
-            "SELECT id, description, completed, item_order, scheduled_at, priority, tags, natural_language_input, task_category FROM tasks ORDER BY item_order ASC",
+    /// The `#` word being typed at the end of the input, without the '#'
+    fn tag_being_typed(&self) -> Option<&str> {
+        let word = self.input_buffer.rsplit(char::is_whitespace).next()?;
+        word.strip_prefix('#')
+    }
+
+    /// Known tags starting with the `#` word being typed, most used first
+    pub fn tag_completions(&self) -> Vec<&(String, usize)> {
+        let Some(prefix) = self.tag_being_typed() else {
+            return Vec::new();
+        };
+        let prefix = prefix.to_lowercase();
+        self.cached_tags
+            .iter()
+            .filter(|(tag, _)| {
+                let tag = tag.to_lowercase();
+                tag.starts_with(&prefix) && tag != prefix
+            })
+            .take(TAG_COMPLETION_LIMIT)
+            .collect()
+    }
+
+    /// Replace the `#` word being typed with the top completion. Returns
+    /// false when there's nothing to complete.
+    pub fn complete_tag(&mut self) -> bool {
+        let Some(tag) = self.tag_completions().first().map(|(tag, _)| tag.clone()) else {
+            return false;
+        };
+        let typed = self.tag_being_typed().map_or(0, str::len);
+        self.input_buffer.truncate(self.input_buffer.len() - typed);
+        self.input_buffer.push_str(&tag);
+        self.input_buffer.push(' ');
+        true
+    }
+
+    /// Every tag on a live task with how many tasks carry it, most used
+    /// first. Tags differing only in case count as one.
+    pub async fn tag_counts(&self) -> Result<Vec<(String, usize)>, sqlx::Error> {
+        let rows: Vec<Option<String>> = sqlx::query_scalar(
+            "SELECT tags FROM tasks WHERE tags IS NOT NULL AND deleted_at IS NULL",
         )
         .fetch_all(&self.db_pool)
         .await?;
 
-        if self.selected >= self.tasks.len() {
-            self.selected = self.tasks.len().saturating_sub(1);
+        let mut counts: Vec<(String, usize)> = Vec::new();
+        for tags in rows.iter().map(|json| parse_tags(json.as_deref())) {
+            for tag in dedupe_tags(tags) {
+                match counts
+                    .iter_mut()
+                    .find(|(seen, _)| seen.eq_ignore_ascii_case(&tag))
+                {
+                    Some((_, count)) => *count += 1,
+                    None => counts.push((tag, 1)),
+                }
+            }
         }
-        Ok(())
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Ok(counts)
     }
 
-    pub async fn add_task(&mut self, description: &str) -> Result<(), sqlx::Error> {
-        let parse_result = self
-            .nlp_parser
-            .parse(description)
-            .await
-            .map_err(|e| sqlx::Error::Protocol(format!("NLP parsing failed: {}", e)))?;
-
-        let (task_title, scheduled_at, priority_value, tags_list) = match parse_result.item {
-            ParsedItem::Task(nlp_task) => {
-                let priority = match nlp_task.priority {
-                    Priority::Urgent => 3,
-                    Priority::High => 2,
-                    Priority::Medium => 1,
-                    Priority::Low => 0,
-                };
+    /// Rename a tag on every task, trashed ones included, merging it into
+    /// `new` where a task already has both. Returns how many tasks changed.
+    pub async fn rename_tag(&mut self, old: &str, new: &str) -> Result<u64, sqlx::Error> {
+        let (old, new) = (normalize_tag(old), normalize_tag(new));
+        let count = self
+            .rewrite_tags(|tags| {
+                tags.into_iter()
+                    .map(|tag| {
+                        if tag.eq_ignore_ascii_case(&old) {
+                            new.clone()
+                        } else {
+                            tag
+                        }
+                    })
+                    .collect()
+            })
+            .await?;
+        self.load_tasks().await?;
+        Ok(count)
+    }
 
-                (nlp_task.title, nlp_task.due_date, priority, nlp_task.tags)
-            }
-            ParsedItem::Event(event) => (event.title, Some(event.start_time), 1, event.tags),
-        };
+    /// Remove a tag from every task, trashed ones included. Returns how many
+    /// tasks changed.
+    pub async fn delete_tag(&mut self, tag: &str) -> Result<u64, sqlx::Error> {
+        let tag = normalize_tag(tag);
+        let count = self
+            .rewrite_tags(|tags| {
+                tags.into_iter()
+                    .filter(|t| !t.eq_ignore_ascii_case(&tag))
+                    .collect()
+            })
+            .await?;
+        self.load_tasks().await?;
+        Ok(count)
+    }
 
-        let new_order: i64;
-        if self.tasks.is_empty() {
-            new_order = 0;
-        } else if self.selected == 0 {
-            sqlx::query("UPDATE tasks SET item_order = item_order + 1 WHERE item_order >= 0")
-                .execute(&self.db_pool)
+    /// Apply `transform` to every task's tags in one transaction, saving only
+    /// the tasks whose tags actually changed
+    async fn rewrite_tags(
+        &self,
+        transform: impl Fn(Vec<String>) -> Vec<String>,
+    ) -> Result<u64, sqlx::Error> {
+        let mut tx = self.db_pool.begin().await?;
+        let rows: Vec<(i64, Option<String>)> =
+            sqlx::query_as("SELECT id, tags FROM tasks WHERE tags IS NOT NULL")
+                .fetch_all(&mut *tx)
                 .await?;
-            new_order = 0;
-        } else {
-            let current_order = self.tasks[self.selected]
-                .item_order
-                .unwrap_or(self.tasks.len() as i64);
 
-            sqlx::query("UPDATE tasks SET item_order = item_order + 1 WHERE item_order > ?")
-                .bind(current_order)
-                .execute(&self.db_pool)
+        let mut changed = 0;
+        for (id, json) in rows {
+            let tags = parse_tags(json.as_deref());
+            let rewritten = transform(tags.clone());
+            if rewritten == tags {
+                continue;
+            }
+            let rewritten = dedupe_tags(rewritten);
+            let json = (!rewritten.is_empty())
+                .then(|| serde_json::to_string(&rewritten).unwrap_or_default());
+            sqlx::query("UPDATE tasks SET tags = ? WHERE id = ?")
+                .bind(json)
+                .bind(id)
+                .execute(&mut *tx)
                 .await?;
-
-            new_order = current_order + 1;
+            changed += 1;
         }
+        tx.commit().await?;
+        Ok(changed)
+    }
 
-        let tags_json = if tags_list.is_empty() {
-            None
-        } else {
-            Some(serde_json::to_string(&tags_list).unwrap_or_default())
-        };
-
-        let category = self.classify_task(&task_title).to_string();
-
-        sqlx::query(
-            "INSERT INTO tasks (description, completed, item_order, priority, natural_language_input, tags, scheduled_at, task_category) VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
+    /// Tasks in the trash, most recently trashed first
+    pub async fn trashed_tasks(&self) -> Result<Vec<Task>, sqlx::Error> {
+        sqlx::query_as::<_, Task>(
+            "SELECT id, description, completed, item_order, scheduled_at, priority, tags, natural_language_input, task_category, scheduled_time_known, parent_id, notes, completed_at, deleted_at, recurrence_rule FROM tasks WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC, item_order ASC",
         )
-        .bind(&task_title)
-        .bind(false)
-        .bind(new_order)
-        .bind(priority_value)
-        .bind(description)
-        .bind(tags_json)
-        .bind(scheduled_at)
-        .bind(&category)
-        .execute(&self.db_pool)
-        .await?;
+        .fetch_all(&self.db_pool)
+        .await
+    }
 
-        self.load_tasks().await?;
+    pub async fn restore_task_by_id(&mut self, id: i64) -> Result<bool, sqlx::Error> {
+        restore_task(&self.db_pool, id).await
+    }
 
-        self.selected = self
-            .tasks
-            .iter()
-            .position(|t| t.item_order == Some(new_order))
-            .unwrap_or(0);
+    pub async fn empty_trash(&mut self) -> Result<u64, sqlx::Error> {
+        empty_trash(&self.db_pool).await
+    }
 
-        Ok(())
+    pub async fn reschedule_task(
+        &mut self,
+        id: i64,
+        when: DateTime<Utc>,
+        time_known: bool,
+    ) -> Result<bool, sqlx::Error> {
+        let rows_affected =
+            sqlx::query("UPDATE tasks SET scheduled_at = ?, scheduled_time_known = ? WHERE id = ? AND deleted_at IS NULL")
+                .bind(when)
+                .bind(time_known)
+                .bind(id)
+                .execute(&self.db_pool)
+                .await?
+                .rows_affected();
+
+        self.load_tasks().await?;
+        Ok(rows_affected > 0)
     }
 
-    pub async fn delete_task(&mut self) -> Result<(), sqlx::Error> {
+    /// Parse the input buffer as a date/time and move the selected task there.
+    /// Returns false (with `input_error` set) when the input has no temporal value.
+    pub async fn reschedule_selected_from_input(&mut self) -> Result<bool, sqlx::Error> {
         if self.tasks.is_empty() {
-            return Ok(());
+            return Ok(true);
         }
 
+        let parse_result = self
+            .nlp_parser
+            .parse(self.input_buffer.trim())
+            .await
+            .map_err(|e| sqlx::Error::Protocol(format!("NLP parsing failed: {}", e)))?;
+
+        let (_, when, time_known, _, _) = item_fields(parse_result.item);
+
+        let Some(when) = when else {
+            self.input_error = Some(format!(
+                "No date/time found in \"{}\"",
+                self.input_buffer.trim()
+            ));
+            return Ok(false);
+        };
+
         let task_id = self.tasks[self.selected].id;
+        self.reschedule_task(task_id, when, time_known).await?;
+        self.input_error = None;
+        Ok(true)
+    }
+
+    /// Schedule a task at the start of the next free block of
+    /// `preferred_block_type` (the task's category when None) within the next
+    /// AUTO_SCHEDULE_DAYS days. A block is free when no other open task
+    /// overlaps it. Returns the new start time, or None without changing
+    /// anything when no block is free.
+    pub async fn auto_schedule(
+        &mut self,
+        task_id: i64,
+        preferred_block_type: Option<&str>,
+    ) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
+        let Some(task) = self.get_task_by_id(task_id).await? else {
+            return Ok(None);
+        };
+        let block_type = preferred_block_type
+            .map(str::to_string)
+            .or(task.task_category)
+            .unwrap_or_else(|| "general".to_string());
 
-        sqlx::query("DELETE FROM tasks WHERE id = ?")
+        let now = crate::timezone::now().naive_local();
+        let today = now.date();
+        let blocks = self.load_schedule_blocks().await?;
+
+        let mut candidates: Vec<(NaiveDateTime, NaiveDateTime)> = (0..AUTO_SCHEDULE_DAYS)
+            .map(|offset| today + Duration::days(offset))
+            .flat_map(|day| {
+                let dow = day.weekday().num_days_from_monday() as i32;
+                let block_type = block_type.as_str();
+                blocks
+                    .iter()
+                    .filter(move |b| b.day_of_week == dow && b.block_type == block_type)
+                    .filter_map(move |b| {
+                        let start = parse_time_string(&b.start_time)?;
+                        let end = parse_time_string(&b.end_time)?;
+                        Some((day.and_time(start), day.and_time(end)))
+                    })
+            })
+            .filter(|(start, _)| *start > now)
+            .collect();
+        if candidates.is_empty() {
+            return Ok(None);
+        }
+        candidates.sort();
+
+        let window_end = (today + Duration::days(AUTO_SCHEDULE_DAYS)).and_time(NaiveTime::MIN);
+        let others = sqlx::query_as::<_, Task>(
+            "SELECT id, description, completed, item_order, scheduled_at, priority, tags, natural_language_input, task_category, scheduled_time_known, parent_id, notes, completed_at, deleted_at, recurrence_rule
+             FROM tasks WHERE id != ? AND scheduled_at >= ? AND scheduled_at < ? AND deleted_at IS NULL",
+        )
+        .bind(task_id)
+        .bind((now - Duration::minutes(TASK_SLOT_MINUTES)).and_utc())
+        .bind(window_end.and_utc())
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        let free = candidates.into_iter().find(|(start, end)| {
+            !others.iter().any(|other| {
+                task_window(other).is_some_and(|(other_start, other_end)| {
+                    other_start < *end && *start < other_end
+                })
+            })
+        });
+        let Some((start, _)) = free else {
+            return Ok(None);
+        };
+
+        let scheduled_at = start.and_utc();
+        sqlx::query("UPDATE tasks SET scheduled_at = ?, scheduled_time_known = 1 WHERE id = ?")
+            .bind(scheduled_at)
             .bind(task_id)
             .execute(&self.db_pool)
             .await?;
+
         self.load_tasks().await?;
-        Ok(())
+        self.select_task(Some(task_id));
+        Ok(Some(scheduled_at))
     }
 
-    pub async fn toggle_completed(&mut self) -> Result<(), sqlx::Error> {
-        if self.tasks.is_empty() {
-            return Ok(());
+    /// Open the block-type prompt for the selected task, prefilled with its category
+    pub async fn start_planning(&mut self) {
+        let Some(task) = self.tasks.get(self.selected) else {
+            return;
+        };
+        if task.completed || task.scheduled_at.is_some() {
+            self.status_message = Some((
+                "Task is already scheduled or completed".to_string(),
+                std::time::Instant::now(),
+            ));
+            return;
         }
 
-        let task = &self.tasks[self.selected];
-        let new_status = !task.completed;
-
-        sqlx::query("UPDATE tasks SET completed = ? WHERE id = ?")
-            .bind(new_status)
-            .bind(task.id)
-            .execute(&self.db_pool)
-            .await?;
+        self.input_buffer = task.task_category.clone().unwrap_or_default();
+        self.cached_block_types = self.list_block_types().await.unwrap_or_default();
+        self.input_error = None;
+        self.input_mode = InputMode::Planning;
+    }
 
-        self.load_tasks().await?;
-        Ok(())
+    /// Plan the selected task into the block type typed at the prompt. Returns
+    /// false, with `input_error` set, when no block of that type is free.
+    pub async fn plan_selected_from_input(&mut self) -> Result<bool, sqlx::Error> {
+        let Some(task_id) = self.tasks.get(self.selected).map(|t| t.id) else {
+            return Ok(true);
+        };
+        let block_type = self.input_buffer.trim().to_string();
+        let preferred = (!block_type.is_empty()).then_some(block_type.as_str());
+
+        match self.auto_schedule(task_id, preferred).await? {
+            Some(when) => {
+                let msg = format!(
+                    "Planned for {} {}",
+                    self.display.format_day(when.date_naive()),
+                    self.display.format_time(when.time())
+                );
+                self.status_message = Some((msg, std::time::Instant::now()));
+                self.input_error = None;
+                Ok(true)
+            }
+            None => {
+                self.input_error = Some(format!(
+                    "No free \"{}\" block in the next {} days",
+                    preferred.unwrap_or("category"),
+                    AUTO_SCHEDULE_DAYS
+                ));
+                Ok(false)
+            }
+        }
     }
 
-    pub async fn convert_task_to_event(&self, task_id: i64) -> Result<Option<i64>, sqlx::Error> {
-        if let Some(task) = self.get_task_by_id(task_id).await?
-            && let Some(scheduled_time) = task.scheduled_at
-        {
-            let event_result = sqlx::query(
-                    "INSERT INTO events (title, description, start_time, end_time, created_at) VALUES (?, ?, ?, ?, ?)"
-                )
-                .bind(&task.description)
-                .bind("Converted from task")
-                .bind(scheduled_time)
-                .bind(scheduled_time)
-                .bind(chrono::Utc::now())
-                .execute(&self.db_pool)
-                .await?;
+    /// Logical "tomorrow", honoring the configured day-start hour
+    fn tomorrow(&self) -> NaiveDate {
+        self.display
+            .logical_date(crate::timezone::now().naive_local())
+            + Duration::days(1)
+    }
 
-            return Ok(Some(event_result.last_insert_rowid()));
+    /// Whether an incomplete task is past due at local time `now`. Date-only
+    /// tasks are due at the end of their (logical) day.
+    pub fn is_overdue(&self, task: &Task, now: NaiveDateTime) -> bool {
+        match task.scheduled_at {
+            Some(scheduled) if !task.completed => {
+                if task.scheduled_time_known {
+                    scheduled.naive_utc() < now
+                } else {
+                    self.display.logical_date(scheduled.naive_utc())
+                        < self.display.logical_date(now)
+                }
+            }
+            _ => false,
         }
-        Ok(None)
     }
 
-    pub async fn get_enhanced_task_list(&mut self) -> Result<Vec<EnhancedTaskInfo>, sqlx::Error> {
-        self.load_tasks().await?;
+    /// Number of loaded tasks that are overdue at local time `now`
+    pub fn overdue_count(&self, now: NaiveDateTime) -> usize {
+        self.tasks
+            .iter()
+            .filter(|task| self.is_overdue(task, now))
+            .count()
+    }
 
-        let mut enhanced_tasks = Vec::new();
+    /// Incomplete tasks scheduled before tomorrow begins: today's plus anything overdue
+    pub async fn rollover_candidates(&self) -> Result<Vec<Task>, sqlx::Error> {
+        let tomorrow_start = self
+            .tomorrow()
+            .and_hms_opt(self.display.day_start_hour, 0, 0)
+            .unwrap()
+            .and_utc();
 
-        for task in &self.tasks {
-            let tags: Vec<String> = if let Some(tags_json) = &task.tags {
-                serde_json::from_str(tags_json).unwrap_or_default()
-            } else {
-                Vec::new()
-            };
+        sqlx::query_as::<_, Task>(
+            "SELECT id, description, completed, item_order, scheduled_at, priority, tags, natural_language_input, task_category, scheduled_time_known, parent_id, notes, completed_at, deleted_at, recurrence_rule FROM tasks WHERE scheduled_at < ? AND completed = 0 AND deleted_at IS NULL ORDER BY scheduled_at",
+        )
+        .bind(tomorrow_start)
+        .fetch_all(&self.db_pool)
+        .await
+    }
 
-            enhanced_tasks.push(EnhancedTaskInfo {
-                task: task.clone(),
-                tags,
-                is_scheduled: task.scheduled_at.is_some(),
-            });
+    /// Postpone today's and overdue incomplete tasks to tomorrow at `at`
+    pub async fn rollover_to_tomorrow(&mut self, at: NaiveTime) -> Result<u64, sqlx::Error> {
+        let target = self.tomorrow().and_time(at).and_utc();
+        let candidates = self.rollover_candidates().await?;
+
+        let mut tx = self.db_pool.begin().await?;
+        for task in &candidates {
+            sqlx::query("UPDATE tasks SET scheduled_at = ? WHERE id = ?")
+                .bind(target)
+                .bind(task.id)
+                .execute(&mut *tx)
+                .await?;
         }
+        tx.commit().await?;
 
-        Ok(enhanced_tasks)
+        self.load_tasks().await?;
+        Ok(candidates.len() as u64)
     }
 
-    pub async fn complete_task_by_id(&mut self, id: i64) -> Result<bool, sqlx::Error> {
-        let rows_affected = sqlx::query("UPDATE tasks SET completed = true WHERE id = ?")
-            .bind(id)
-            .execute(&self.db_pool)
-            .await?
-            .rows_affected();
-
-        Ok(rows_affected > 0)
+    /// Everything planned for `day`, merged into one chronological list.
+    /// Blocks whose times can't be parsed are left out rather than guessed at.
+    pub async fn agenda(&self, day: NaiveDate) -> Result<Agenda, sqlx::Error> {
+        let data = self.get_calendar_data(&[day]).await?;
+
+        let mut timed: Vec<AgendaEntry> = data
+            .schedule_blocks
+            .into_iter()
+            .filter_map(|(_, block)| {
+                Some(AgendaEntry {
+                    start: parse_time_string(&block.start_time)?,
+                    end: parse_time_string(&block.end_time),
+                    title: block.title,
+                    kind: AgendaKind::Block,
+                })
+            })
+            .collect();
+        timed.extend(data.events.into_iter().map(|segment| AgendaEntry {
+            start: segment.start,
+            end: segment.end,
+            title: segment.title,
+            kind: AgendaKind::Event,
+        }));
+        timed.extend(
+            data.scheduled_tasks
+                .into_iter()
+                .map(|(_, time, description, _)| AgendaEntry {
+                    start: time,
+                    end: None,
+                    title: description,
+                    kind: AgendaKind::Task,
+                }),
+        );
+        // Stable sort keeps blocks ahead of events and tasks at the same time
+        timed.sort_by_key(|entry| entry.start);
+
+        Ok(Agenda {
+            timed,
+            anytime: data
+                .all_day
+                .into_iter()
+                .map(|(_, title, _)| title)
+                .collect(),
+        })
     }
 
-    pub async fn remove_task_by_id(&mut self, id: i64) -> Result<bool, sqlx::Error> {
-        let rows_affected = sqlx::query("DELETE FROM tasks WHERE id = ?")
-            .bind(id)
-            .execute(&self.db_pool)
-            .await?
-            .rows_affected();
+    /// Tasks completed since the start of the logical local day, and how many
+    /// were completed since the start of the week (Monday)
+    pub async fn completion_summary(&self) -> Result<(Vec<Task>, i64), sqlx::Error> {
+        let today = self
+            .display
+            .logical_date(crate::timezone::now().naive_local());
+        let day_start = |date: NaiveDate| {
+            date.and_hms_opt(self.display.day_start_hour, 0, 0)
+                .unwrap()
+                .and_utc()
+        };
+        let week_start = today - Duration::days(today.weekday().num_days_from_monday() as i64);
 
-        Ok(rows_affected > 0)
-    }
+        let done_today = sqlx::query_as::<_, Task>(
+            "SELECT id, description, completed, item_order, scheduled_at, priority, tags, natural_language_input, task_category, scheduled_time_known, parent_id, notes, completed_at, deleted_at, recurrence_rule FROM tasks WHERE completed = 1 AND completed_at >= ? AND deleted_at IS NULL ORDER BY completed_at",
+        )
+        .bind(day_start(today))
+        .fetch_all(&self.db_pool)
+        .await?;
 
-    pub async fn clear_completed_tasks(&mut self) -> Result<u64, sqlx::Error> {
-        let rows_affected = sqlx::query("DELETE FROM tasks WHERE completed = true")
-            .execute(&self.db_pool)
-            .await?
-            .rows_affected();
+        let done_this_week: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM tasks WHERE completed = 1 AND completed_at >= ? AND deleted_at IS NULL",
+        )
+        .bind(day_start(week_start))
+        .fetch_one(&self.db_pool)
+        .await?;
 
-        Ok(rows_affected)
+        Ok((done_today, done_this_week))
     }
 
     pub async fn get_task_by_id(&self, id: i64) -> Result<Option<Task>, sqlx::Error> {
         let task = sqlx::query_as::<_, Task>(
-            "SELECT id, description, completed, item_order, scheduled_at, priority, tags, natural_language_input, task_category FROM tasks WHERE id = ?",
+            "SELECT id, description, completed, item_order, scheduled_at, priority, tags, natural_language_input, task_category, scheduled_time_known, parent_id, notes, completed_at, deleted_at, recurrence_rule FROM tasks WHERE id = ? AND deleted_at IS NULL",
         )
         .bind(id)
         .fetch_optional(&self.db_pool)
@@ -646,84 +3127,164 @@ impl App {
     }
 
     pub fn calendar_move_down(&mut self) {
-        if self.selected_time_slot < 15 {
+        if self.selected_time_slot + 1 < self.display.calendar_slot_count() {
             self.selected_time_slot += 1;
         }
     }
 
     pub fn calendar_move_left(&mut self) {
-        self.selected_day = self.selected_day.saturating_sub(1);
+        if self.selected_day() > 0 {
+            self.shift_anchor_days(-1);
+        }
     }
 
     pub fn calendar_move_right(&mut self) {
-        if self.selected_day < 6 {
-            self.selected_day += 1;
+        if self.selected_day() < 6 {
+            self.shift_anchor_days(1);
         }
     }
 
+    /// Select a week grid cell by day column (0 = Monday) and slot
+    pub fn select_calendar_cell(&mut self, day: usize, slot: usize) {
+        self.shift_anchor_days(day as i64 - self.selected_day() as i64);
+        self.selected_time_slot = slot.min(self.display.calendar_slot_count().saturating_sub(1));
+    }
+
     pub fn selected_cell_date(&self) -> NaiveDate {
-        let today = chrono::Local::now().naive_local().date();
-        let week_offset = self.calendar_week_offset.unwrap_or(0);
-        let start_of_week = today + Duration::weeks(week_offset)
-            - Duration::days(today.weekday().num_days_from_monday() as i64);
-        start_of_week + Duration::days(self.selected_day as i64)
+        self.calendar_anchor
     }
 
     pub fn selected_cell_time(&self) -> NaiveTime {
-        let hour = 7 + self.selected_time_slot as u32;
-        NaiveTime::from_hms_opt(hour, 0, 0).unwrap()
+        self.display.calendar_slot_time(self.selected_time_slot)
     }
 
-    // Schedule block creation
-    pub async fn create_schedule_block(&mut self) -> Result<(), sqlx::Error> {
-        let day_of_week = self.selected_cell_date().weekday().num_days_from_monday() as i32;
+    /// Find the incomplete task scheduled within the selected calendar cell's slot
+    async fn task_id_at_selected_cell(&self) -> Result<Option<i64>, sqlx::Error> {
+        let start = self
+            .selected_cell_date()
+            .and_time(self.selected_cell_time())
+            .and_utc();
+        let end = start + Duration::minutes(self.display.calendar_slot_minutes as i64);
 
-        // Validate times
-        if Self::validate_time_format(&self.block_form.start_time).is_err() {
-            self.status_message = Some((
-                "Invalid start time format".to_string(),
-                std::time::Instant::now(),
-            ));
-            return Ok(());
-        }
-        if Self::validate_time_format(&self.block_form.end_time).is_err() {
+        sqlx::query_scalar(
+            "SELECT id FROM tasks WHERE scheduled_at >= ? AND scheduled_at < ? AND completed = 0 AND deleted_at IS NULL ORDER BY scheduled_at LIMIT 1",
+        )
+        .bind(start)
+        .bind(end)
+        .fetch_optional(&self.db_pool)
+        .await
+    }
+
+    /// Move a scheduled task by whole days, keeping its time of day
+    pub async fn shift_task_days(&mut self, id: i64, delta: i64) -> Result<bool, sqlx::Error> {
+        let Some(scheduled_at) = self.get_task_by_id(id).await?.and_then(|t| t.scheduled_at) else {
+            return Ok(false);
+        };
+
+        sqlx::query("UPDATE tasks SET scheduled_at = ? WHERE id = ?")
+            .bind(scheduled_at + Duration::days(delta))
+            .bind(id)
+            .execute(&self.db_pool)
+            .await?;
+
+        self.load_tasks().await?;
+        self.refresh_calendar_data().await;
+        Ok(true)
+    }
+
+    /// Shift the task under the calendar cursor by `delta` days and follow it
+    pub async fn shift_selected_task_days(&mut self, delta: i64) -> Result<(), sqlx::Error> {
+        let Some(task_id) = self.task_id_at_selected_cell().await? else {
             self.status_message = Some((
-                "Invalid end time format".to_string(),
+                "No task at this time".to_string(),
                 std::time::Instant::now(),
             ));
             return Ok(());
-        }
+        };
 
-        // Check for conflicts
-        if self
-            .has_block_conflict(
-                day_of_week,
-                &self.block_form.start_time,
-                &self.block_form.end_time,
-            )
-            .await?
-        {
-            self.status_message = Some((
-                "Block overlaps with existing block".to_string(),
-                std::time::Instant::now(),
-            ));
+        // Follow the task, crossing into the adjacent week when needed
+        self.shift_anchor_days(delta);
+
+        self.shift_task_days(task_id, delta).await?;
+        Ok(())
+    }
+
+    /// Write the block form: insert a block on the selected day, or update the
+    /// block being edited. Bad or reversed times and overlaps with other blocks
+    /// keep the form open with a status message.
+    pub async fn save_schedule_block(&mut self) -> Result<(), sqlx::Error> {
+        let day_of_week = self.selected_cell_date().weekday().num_days_from_monday() as i32;
+        let form = &self.block_form;
+
+        let problem = if Self::validate_time_format(&form.start_time).is_err() {
+            Some("Invalid start time format")
+        } else if Self::validate_time_format(&form.end_time).is_err() {
+            Some("Invalid end time format")
+        } else if Self::time_to_minutes(&form.start_time) >= Self::time_to_minutes(&form.end_time) {
+            Some("The block must start before it ends")
+        } else if self
+            .has_block_conflict(day_of_week, &form.start_time, &form.end_time, form.editing)
+            .await?
+        {
+            Some("Block overlaps with existing block")
+        } else {
+            None
+        };
+        if let Some(problem) = problem {
+            self.status_message = Some((problem.to_string(), std::time::Instant::now()));
             return Ok(());
         }
 
-        sqlx::query(
-            "INSERT INTO schedule_blocks (day_of_week, start_time, end_time, block_type, title) VALUES (?, ?, ?, ?, ?)"
-        )
-        .bind(day_of_week)
-        .bind(&self.block_form.start_time)
-        .bind(&self.block_form.end_time)
-        .bind(&self.block_form.block_type)
-        .bind(&self.block_form.title)
-        .execute(&self.db_pool)
-        .await?;
+        let message = match form.editing {
+            Some(id) => {
+                sqlx::query(
+                    "UPDATE schedule_blocks SET start_time = ?, end_time = ?, block_type = ?, title = ? WHERE id = ?",
+                )
+                .bind(&form.start_time)
+                .bind(&form.end_time)
+                .bind(&form.block_type)
+                .bind(&form.title)
+                .bind(id)
+                .execute(&self.db_pool)
+                .await?;
+                "Block updated"
+            }
+            None => {
+                sqlx::query(
+                    "INSERT INTO schedule_blocks (day_of_week, start_time, end_time, block_type, title) VALUES (?, ?, ?, ?, ?)"
+                )
+                .bind(day_of_week)
+                .bind(&form.start_time)
+                .bind(&form.end_time)
+                .bind(&form.block_type)
+                .bind(&form.title)
+                .execute(&self.db_pool)
+                .await?;
+                "Block created"
+            }
+        };
 
         self.refresh_calendar_data().await;
         self.calendar_input_mode = CalendarInputMode::Navigate;
-        self.status_message = Some(("Block created".to_string(), std::time::Instant::now()));
+        self.status_message = Some((message.to_string(), std::time::Instant::now()));
+        Ok(())
+    }
+
+    /// Open the block form on the block covering the selected cell
+    pub async fn edit_block_at_selected_cell(&mut self) -> Result<(), sqlx::Error> {
+        match self.block_at_selected_cell().await? {
+            Some(block) => {
+                self.block_form =
+                    BlockFormState::editing(&block).with_block_types(&self.cached_block_types);
+                self.calendar_input_mode = CalendarInputMode::BlockForm;
+            }
+            None => {
+                self.status_message = Some((
+                    "No block at this time (n: new block)".to_string(),
+                    std::time::Instant::now(),
+                ));
+            }
+        }
         Ok(())
     }
 
@@ -746,7 +3307,7 @@ impl App {
         let time = self.selected_cell_time();
         let datetime = date.and_time(time).and_utc();
 
-        sqlx::query("UPDATE tasks SET scheduled_at = ? WHERE id = ?")
+        sqlx::query("UPDATE tasks SET scheduled_at = ?, scheduled_time_known = 1 WHERE id = ?")
             .bind(datetime)
             .bind(task_id)
             .execute(&self.db_pool)
@@ -768,8 +3329,9 @@ impl App {
             .and_time(self.selected_cell_time())
             .and_utc();
         let category = self.classify_task(description).to_string();
-        let new_order = self.tasks.len() as i64;
 
+        let mut tx = self.db_pool.begin_with("BEGIN IMMEDIATE").await?;
+        let new_order = reserve_item_order(&mut tx, InsertPosition::End).await?;
         sqlx::query(
             "INSERT INTO tasks (description, completed, item_order, priority, scheduled_at, task_category) VALUES (?, ?, ?, ?, ?, ?)"
         )
@@ -779,8 +3341,9 @@ impl App {
         .bind(1i32)
         .bind(scheduled_at)
         .bind(&category)
-        .execute(&self.db_pool)
+        .execute(&mut *tx)
         .await?;
+        tx.commit().await?;
 
         self.load_tasks().await?;
         self.refresh_calendar_data().await;
@@ -810,19 +3373,16 @@ impl App {
         let task_id = task.id;
 
         if let Some(slot) = self.find_next_available_slot(&task_category).await? {
-            sqlx::query("UPDATE tasks SET scheduled_at = ? WHERE id = ?")
+            sqlx::query("UPDATE tasks SET scheduled_at = ?, scheduled_time_known = 1 WHERE id = ?")
                 .bind(slot)
                 .bind(task_id)
                 .execute(&self.db_pool)
                 .await?;
 
-            let local_time = slot.with_timezone(&chrono::Local);
             let msg = format!(
-                "Scheduled for {}",
-                local_time
-                    .format("%a %m/%d %I:%M%p")
-                    .to_string()
-                    .to_lowercase()
+                "Scheduled for {} {}",
+                self.display.format_day(slot.date_naive()),
+                self.display.format_time(slot.time())
             );
             self.status_message = Some((msg, std::time::Instant::now()));
         } else {
@@ -840,7 +3400,7 @@ impl App {
         &self,
         task_category: &str,
     ) -> Result<Option<DateTime<Utc>>, sqlx::Error> {
-        let now = chrono::Local::now();
+        let now = crate::timezone::now();
         let today = now.naive_local().date();
 
         // Look at current week + next week (14 days)
@@ -853,25 +3413,15 @@ impl App {
         .fetch_all(&self.db_pool)
         .await?;
 
-        // Get all scheduled tasks in this range
-        let range_start = days[0].and_hms_opt(0, 0, 0).unwrap().and_utc();
-        let range_end = days[days.len() - 1]
-            .and_hms_opt(23, 59, 59)
-            .unwrap()
-            .and_utc();
-
-        let scheduled_tasks = sqlx::query_as::<_, Task>(
-            "SELECT id, description, completed, item_order, scheduled_at, priority, tags, natural_language_input, task_category FROM tasks WHERE scheduled_at >= ? AND scheduled_at < ? AND completed = 0"
-        )
-        .bind(range_start)
-        .bind(range_end)
-        .fetch_all(&self.db_pool)
-        .await?;
-
-        let occupied_slots: Vec<(NaiveDate, u32)> = scheduled_tasks
-            .iter()
-            .filter_map(|t| t.scheduled_at.map(|dt| (dt.date_naive(), dt.time().hour())))
-            .collect();
+        // Hours already taken by incomplete scheduled tasks
+        let mut occupied_slots: Vec<(NaiveDate, u32)> = Vec::new();
+        for &day in &days {
+            for task in self.get_tasks_for_day(day).await? {
+                if let Some(dt) = task.scheduled_at.filter(|_| !task.completed) {
+                    occupied_slots.push((day, dt.time().hour()));
+                }
+            }
+        }
 
         // Strategy 1: Find a matching block type with a free hour
         for day in &days {
@@ -909,10 +3459,10 @@ impl App {
             }
         }
 
-        // Strategy 2: Find any free hour (7am-11pm) not inside a different-type block
+        // Strategy 2: Find any free hour the calendar shows, not inside a different-type block
         for day in &days {
             let dow = day.weekday().num_days_from_monday() as i32;
-            for hour in 7u32..23 {
+            for hour in self.display.calendar_hours.clone() {
                 // Skip past hours for today
                 if *day == today && hour <= now.hour() {
                     continue;
@@ -1026,145 +3576,599 @@ impl App {
             if !days.contains(&day_num) {
                 days.push(day_num);
             }
-        }
+        }
+
+        if days.is_empty() {
+            return Err(format!("Invalid day name: {}", name).into());
+        }
+
+        days.sort();
+        Ok(days)
+    }
+
+    /// Convert day number to name (Monday-first: Mon=0, Sun=6)
+    fn day_number_to_name(num: i32) -> String {
+        match num {
+            0 => "monday",
+            1 => "tuesday",
+            2 => "wednesday",
+            3 => "thursday",
+            4 => "friday",
+            5 => "saturday",
+            6 => "sunday",
+            _ => "unknown",
+        }
+        .to_string()
+    }
+
+    /// Check if a block would overlap with existing blocks other than `ignore`
+    /// (the block itself, when editing)
+    pub async fn has_block_conflict(
+        &self,
+        day_of_week: i32,
+        start_time: &str,
+        end_time: &str,
+        ignore: Option<i64>,
+    ) -> Result<bool, sqlx::Error> {
+        let new_start = Self::time_to_minutes(start_time).unwrap_or(0);
+        let new_end = Self::time_to_minutes(end_time).unwrap_or(0);
+
+        let existing = sqlx::query_as::<_, ScheduleBlock>(
+            "SELECT id, day_of_week, start_time, end_time, block_type, title, description, priority
+             FROM schedule_blocks WHERE day_of_week = ?",
+        )
+        .bind(day_of_week)
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        for block in existing.into_iter().filter(|b| Some(b.id) != ignore) {
+            let block_start = Self::time_to_minutes(&block.start_time).unwrap_or(0);
+            let block_end = Self::time_to_minutes(&block.end_time).unwrap_or(0);
+
+            // Check overlap: NOT (new_end <= block_start OR new_start >= block_end)
+            if !(new_end <= block_start || new_start >= block_end) {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Import blocks from a schedule. With `replace`, existing blocks are
+    /// deleted first; otherwise the schedule is merged in, skipping blocks that
+    /// already exist with the same day, start, end and title. Blocks that
+    /// overlap a different block are skipped with a warning. The whole import
+    /// runs in one transaction, so a bad entry leaves the schedule unchanged.
+    pub async fn import_schedule(
+        &mut self,
+        schedule: ScheduleToml,
+        replace: bool,
+    ) -> Result<ScheduleImportSummary, Box<dyn std::error::Error>> {
+        // Flag types we've never seen so typos like "deepwrok" don't slip in silently
+        let mut known_types: Vec<String> = BlockFormState::BLOCK_TYPES
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+        known_types.extend(self.list_block_types().await?);
+        for block in &schedule.blocks {
+            if !known_types.contains(&block.block_type) {
+                tracing::info!("Note: new block type '{}'", block.block_type);
+                known_types.push(block.block_type.clone());
+            }
+        }
+
+        let mut summary = ScheduleImportSummary::default();
+        let mut tx = self.db_pool.begin_with("BEGIN IMMEDIATE").await?;
+
+        if replace {
+            sqlx::query("DELETE FROM schedule_blocks")
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        // (day, start minute, end minute, title) of every block already in place
+        let mut existing: Vec<(i32, u32, u32, String)> = sqlx::query_as::<_, ScheduleBlock>(
+            "SELECT id, day_of_week, start_time, end_time, block_type, title, description, priority FROM schedule_blocks",
+        )
+        .fetch_all(&mut *tx)
+        .await?
+        .into_iter()
+        .map(|b| {
+            (
+                b.day_of_week,
+                Self::time_to_minutes(&b.start_time).unwrap_or(0),
+                Self::time_to_minutes(&b.end_time).unwrap_or(0),
+                b.title,
+            )
+        })
+        .collect();
+
+        for block in schedule.blocks {
+            // Parse day name(s) - supports compound days like "monday_wednesday"
+            let days = Self::parse_days(&block.day)?;
+
+            // Validate time format
+            Self::validate_time_format(&block.start)?;
+            Self::validate_time_format(&block.end)?;
+            let start = Self::time_to_minutes(&block.start).unwrap_or(0);
+            let end = Self::time_to_minutes(&block.end).unwrap_or(0);
+            if start >= end {
+                return Err(format!(
+                    "Block '{}' must start before it ends ({}-{})",
+                    block.title, block.start, block.end
+                )
+                .into());
+            }
+
+            // Create a block for each day
+            for day_of_week in days {
+                let duplicate = existing.iter().any(|(day, s, e, title)| {
+                    *day == day_of_week && *s == start && *e == end && *title == block.title
+                });
+                if duplicate {
+                    summary.duplicates += 1;
+                    continue;
+                }
+
+                // Check overlap: NOT (end <= other_start OR start >= other_end)
+                let overlaps = existing
+                    .iter()
+                    .any(|(day, s, e, _)| *day == day_of_week && !(end <= *s || start >= *e));
+                if overlaps {
+                    let day_name = Self::day_number_to_name(day_of_week);
+                    tracing::warn!(
+                        "Warning: Skipping overlapping block '{}' on {}",
+                        block.title,
+                        day_name
+                    );
+                    continue;
+                }
+
+                sqlx::query(
+                    "INSERT INTO schedule_blocks (day_of_week, start_time, end_time, block_type, title, description, priority)
+                     VALUES (?, ?, ?, ?, ?, ?, ?)",
+                )
+                .bind(day_of_week)
+                .bind(&block.start)
+                .bind(&block.end)
+                .bind(&block.block_type)
+                .bind(&block.title)
+                .bind(&block.description)
+                .bind(block.priority)
+                .execute(&mut *tx)
+                .await?;
+
+                existing.push((day_of_week, start, end, block.title.clone()));
+                summary.imported += 1;
+            }
+        }
+
+        tx.commit().await?;
+        self.refresh_calendar_data().await;
+        Ok(summary)
+    }
+
+    pub async fn export_schedule_to_toml(
+        &self,
+        path: &Path,
+    ) -> Result<usize, Box<dyn std::error::Error>> {
+        let blocks = sqlx::query_as::<_, ScheduleBlock>(
+            "SELECT id, day_of_week, start_time, end_time, block_type, title, description, priority
+             FROM schedule_blocks ORDER BY day_of_week, start_time",
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        let block_defs: Vec<BlockDefinition> = blocks
+            .iter()
+            .map(|b| BlockDefinition {
+                day: Self::day_number_to_name(b.day_of_week),
+                block_type: b.block_type.clone(),
+                start: b.start_time.clone(),
+                end: b.end_time.clone(),
+                title: b.title.clone(),
+                description: b.description.clone(),
+                priority: b.priority,
+            })
+            .collect();
+
+        let schedule = ScheduleToml { blocks: block_defs };
+        let toml_string = toml::to_string_pretty(&schedule)?;
+        std::fs::write(path, toml_string)?;
+
+        Ok(blocks.len())
+    }
+
+    /// Dump tasks, events and schedule blocks to a single JSON file
+    pub async fn export_database(
+        &self,
+        path: &Path,
+    ) -> Result<DatabaseBackup, Box<dyn std::error::Error>> {
+        let tasks = sqlx::query_as::<_, TaskRecord>(
+            "SELECT id, parent_id, description, completed, item_order, scheduled_at,
+                    COALESCE(priority, 0) AS priority, tags, natural_language_input, task_category,
+                    scheduled_time_known, notes, completed_at, recurrence_rule
+             FROM tasks WHERE deleted_at IS NULL ORDER BY item_order ASC, id ASC",
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        let dependencies: Vec<(i64, i64)> = sqlx::query_as(
+            "SELECT task_id, depends_on_id FROM task_dependencies
+             WHERE task_id NOT IN (SELECT id FROM tasks WHERE deleted_at IS NOT NULL)
+               AND depends_on_id NOT IN (SELECT id FROM tasks WHERE deleted_at IS NOT NULL)
+             ORDER BY task_id, depends_on_id",
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        let events = sqlx::query_as::<_, EventRecord>(
+            "SELECT title, description, start_time, end_time, location, calendar_id,
+                    event_type, recurrence_rule, exdates
+             FROM events ORDER BY start_time, id",
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        let schedule_blocks = sqlx::query_as::<_, BlockRecord>(
+            "SELECT day_of_week, start_time, end_time, block_type, title, description,
+                    COALESCE(priority, 1) AS priority
+             FROM schedule_blocks ORDER BY day_of_week, start_time",
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        let backup = DatabaseBackup {
+            exported_at: Utc::now(),
+            tasks,
+            dependencies,
+            events,
+            schedule_blocks,
+        };
+        std::fs::write(path, serde_json::to_string_pretty(&backup)?)?;
+
+        Ok(backup)
+    }
+
+    /// Load a backup written by `export_database`. Rows whose natural key already
+    /// exists are skipped; any bad row rolls back the whole import.
+    pub async fn import_database(
+        &mut self,
+        path: &Path,
+    ) -> Result<ImportSummary, Box<dyn std::error::Error>> {
+        let content = std::fs::read_to_string(path)?;
+        let backup: DatabaseBackup = serde_json::from_str(&content)?;
+        let mut summary = ImportSummary::default();
+
+        // Dropping the transaction on an early return rolls it back
+        let mut tx = self.db_pool.begin_with("BEGIN IMMEDIATE").await?;
+
+        // Shift imported orders past existing tasks so the backup keeps its
+        // relative order; on an empty database the orders are kept as-is
+        let order_offset: i64 = sqlx::query_scalar(
+            "SELECT COALESCE(MAX(item_order) + 1, 0) FROM tasks WHERE item_order IS NOT NULL",
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        // Backup ID -> ID in this database, for relinking subtasks afterwards
+        let mut task_ids = HashMap::new();
+
+        for (i, task) in backup.tasks.iter().enumerate() {
+            if task.description.trim().is_empty() {
+                return Err(format!("Task #{}: description is empty", i + 1).into());
+            }
+            if !(Priority::Low.to_level()..=Priority::Urgent.to_level()).contains(&task.priority) {
+                return Err(format!("Task #{}: invalid priority {}", i + 1, task.priority).into());
+            }
+
+            let existing: Option<i64> = sqlx::query_scalar(
+                "SELECT id FROM tasks WHERE description = ? AND scheduled_at IS ? AND deleted_at IS NULL LIMIT 1",
+            )
+            .bind(&task.description)
+            .bind(task.scheduled_at)
+            .fetch_optional(&mut *tx)
+            .await?;
+            if let Some(existing) = existing {
+                if let Some(id) = task.id {
+                    task_ids.insert(id, existing);
+                }
+                summary.skipped += 1;
+                continue;
+            }
+
+            let new_id = sqlx::query(
+                "INSERT INTO tasks (description, completed, item_order, scheduled_at, priority, tags, natural_language_input, task_category, scheduled_time_known, notes, completed_at, recurrence_rule)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(&task.description)
+            .bind(task.completed)
+            .bind(task.item_order.map(|order| order + order_offset))
+            .bind(task.scheduled_at)
+            .bind(task.priority)
+            .bind(&task.tags)
+            .bind(&task.natural_language_input)
+            .bind(&task.task_category)
+            .bind(task.scheduled_time_known)
+            .bind(&task.notes)
+            .bind(task.completed_at.filter(|_| task.completed))
+            .bind(&task.recurrence_rule)
+            .execute(&mut *tx)
+            .await?
+            .last_insert_rowid();
+            if let Some(id) = task.id {
+                task_ids.insert(id, new_id);
+            }
+            summary.tasks += 1;
+        }
+
+        for task in &backup.tasks {
+            if let (Some(id), Some(parent_id)) = (task.id, task.parent_id)
+                && let (Some(&child), Some(&parent)) = (task_ids.get(&id), task_ids.get(&parent_id))
+                && child != parent
+            {
+                sqlx::query("UPDATE tasks SET parent_id = ? WHERE id = ? AND parent_id IS NULL")
+                    .bind(parent)
+                    .bind(child)
+                    .execute(&mut *tx)
+                    .await?;
+            }
+        }
+
+        for (task_id, depends_on_id) in &backup.dependencies {
+            if let (Some(&task), Some(&depends_on)) =
+                (task_ids.get(task_id), task_ids.get(depends_on_id))
+                && task != depends_on
+            {
+                sqlx::query(
+                    "INSERT OR IGNORE INTO task_dependencies (task_id, depends_on_id) VALUES (?, ?)",
+                )
+                .bind(task)
+                .bind(depends_on)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        for (i, event) in backup.events.iter().enumerate() {
+            if event.title.trim().is_empty() {
+                return Err(format!("Event #{}: title is empty", i + 1).into());
+            }
+            if event.end_time < event.start_time {
+                return Err(format!("Event #{}: ends before it starts", i + 1).into());
+            }
+
+            let exists: i64 = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM events WHERE title = ? AND start_time = ?",
+            )
+            .bind(&event.title)
+            .bind(event.start_time)
+            .fetch_one(&mut *tx)
+            .await?;
+            if exists > 0 {
+                summary.skipped += 1;
+                continue;
+            }
+
+            sqlx::query(
+                "INSERT INTO events (title, description, start_time, end_time, location, calendar_id, event_type, recurrence_rule, exdates)
+                 VALUES (?, ?, ?, ?, ?, ?, COALESCE(?, 'event'), ?, ?)",
+            )
+            .bind(&event.title)
+            .bind(&event.description)
+            .bind(event.start_time)
+            .bind(event.end_time)
+            .bind(&event.location)
+            .bind(&event.calendar_id)
+            .bind(&event.event_type)
+            .bind(&event.recurrence_rule)
+            .bind(&event.exdates)
+            .execute(&mut *tx)
+            .await?;
+            summary.events += 1;
+        }
+
+        for (i, block) in backup.schedule_blocks.iter().enumerate() {
+            if !(0..=6).contains(&block.day_of_week) {
+                return Err(format!(
+                    "Schedule block #{}: invalid day_of_week {}",
+                    i + 1,
+                    block.day_of_week
+                )
+                .into());
+            }
+            Self::validate_time_format(&block.start_time)
+                .and_then(|_| Self::validate_time_format(&block.end_time))
+                .map_err(|e| format!("Schedule block #{}: {}", i + 1, e))?;
+
+            let exists: i64 = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM schedule_blocks
+                 WHERE day_of_week = ? AND start_time = ? AND end_time = ? AND title = ?",
+            )
+            .bind(block.day_of_week)
+            .bind(&block.start_time)
+            .bind(&block.end_time)
+            .bind(&block.title)
+            .fetch_one(&mut *tx)
+            .await?;
+            if exists > 0 {
+                summary.skipped += 1;
+                continue;
+            }
 
-        if days.is_empty() {
-            return Err(format!("Invalid day name: {}", name).into());
+            sqlx::query(
+                "INSERT INTO schedule_blocks (day_of_week, start_time, end_time, block_type, title, description, priority)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(block.day_of_week)
+            .bind(&block.start_time)
+            .bind(&block.end_time)
+            .bind(&block.block_type)
+            .bind(&block.title)
+            .bind(&block.description)
+            .bind(block.priority)
+            .execute(&mut *tx)
+            .await?;
+            summary.schedule_blocks += 1;
         }
 
-        days.sort();
-        Ok(days)
-    }
+        tx.commit().await?;
 
-    /// Convert day number to name (Monday-first: Mon=0, Sun=6)
-    fn day_number_to_name(num: i32) -> String {
-        match num {
-            0 => "monday",
-            1 => "tuesday",
-            2 => "wednesday",
-            3 => "thursday",
-            4 => "friday",
-            5 => "saturday",
-            6 => "sunday",
-            _ => "unknown",
-        }
-        .to_string()
+        self.load_tasks().await?;
+        self.refresh_calendar_data().await;
+        Ok(summary)
     }
 
-    /// Check if a new block would overlap with existing blocks
-    pub async fn has_block_conflict(
-        &self,
-        day_of_week: i32,
-        start_time: &str,
-        end_time: &str,
-    ) -> Result<bool, sqlx::Error> {
-        let new_start = Self::time_to_minutes(start_time).unwrap_or(0);
-        let new_end = Self::time_to_minutes(end_time).unwrap_or(0);
-
-        let existing = sqlx::query_as::<_, ScheduleBlock>(
-            "SELECT id, day_of_week, start_time, end_time, block_type, title, description, priority
-             FROM schedule_blocks WHERE day_of_week = ?",
-        )
-        .bind(day_of_week)
-        .fetch_all(&self.db_pool)
-        .await?;
-
-        for block in existing {
-            let block_start = Self::time_to_minutes(&block.start_time).unwrap_or(0);
-            let block_end = Self::time_to_minutes(&block.end_time).unwrap_or(0);
+    /// Insert events parsed from an iCalendar file. Events whose UID (or, without
+    /// a UID, title and start time) is already present are skipped.
+    pub async fn import_events(&mut self, events: &[Event]) -> Result<usize, sqlx::Error> {
+        let mut tx = self.db_pool.begin().await?;
+        let mut imported = 0;
 
-            // Check overlap: NOT (new_end <= block_start OR new_start >= block_end)
-            if !(new_end <= block_start || new_start >= block_end) {
-                return Ok(true);
+        for event in events {
+            if event.uid.is_none() {
+                let exists: i64 = sqlx::query_scalar(
+                    "SELECT COUNT(*) FROM events WHERE title = ? AND start_time = ?",
+                )
+                .bind(&event.title)
+                .bind(event.start_time)
+                .fetch_one(&mut *tx)
+                .await?;
+                if exists > 0 {
+                    continue;
+                }
             }
+
+            let result = sqlx::query(
+                "INSERT INTO events (uid, title, description, start_time, end_time, location, calendar_id, event_type, recurrence_rule, exdates)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                 ON CONFLICT(uid) DO NOTHING",
+            )
+            .bind(&event.uid)
+            .bind(&event.title)
+            .bind(&event.description)
+            .bind(event.start_time)
+            .bind(event.end_time)
+            .bind(&event.location)
+            .bind(&event.calendar_id)
+            .bind(&event.event_type)
+            .bind(&event.recurrence_rule)
+            .bind(&event.exdates)
+            .execute(&mut *tx)
+            .await?;
+            imported += result.rows_affected() as usize;
         }
 
-        Ok(false)
+        tx.commit().await?;
+        self.refresh_calendar_data().await;
+        Ok(imported)
     }
 
-    pub async fn import_schedule_from_toml(
-        &mut self,
-        path: &Path,
-    ) -> Result<usize, Box<dyn std::error::Error>> {
-        let content = std::fs::read_to_string(path)?;
-        let schedule: ScheduleToml = toml::from_str(&content)?;
+    /// Housekeeping pass: renumber `item_order` to 0..n, drop orphaned rows,
+    /// then VACUUM. Each step runs in its own transaction; a failing step is
+    /// recorded as a warning and the next one still runs.
+    pub async fn compact(&mut self) -> CompactSummary {
+        let mut summary = CompactSummary {
+            size_before: self.database_size().await.ok(),
+            ..Default::default()
+        };
 
-        let mut imported = 0;
+        match self.renumber_task_order().await {
+            Ok(changed) => summary.renumbered = changed,
+            Err(e) => summary
+                .warnings
+                .push(format!("renumbering task order failed: {}", e)),
+        }
 
-        for block in schedule.blocks {
-            // Parse day name(s) - supports compound days like "monday_wednesday"
-            let days = Self::parse_days(&block.day)?;
+        match self.remove_orphans().await {
+            Ok(removed) => summary.orphans_removed = removed,
+            Err(e) => summary
+                .warnings
+                .push(format!("orphan cleanup failed: {}", e)),
+        }
 
-            // Validate time format
-            Self::validate_time_format(&block.start)?;
-            Self::validate_time_format(&block.end)?;
+        // VACUUM can't run inside a transaction and fails while another
+        // connection (the TUI or daemon) holds one open
+        if let Err(e) = sqlx::query("VACUUM").execute(&self.db_pool).await {
+            summary.warnings.push(format!("VACUUM failed: {}", e));
+        }
+        summary.size_after = self.database_size().await.ok();
 
-            // Create a block for each day
-            for day_of_week in days {
-                // Check for conflicts
-                if self
-                    .has_block_conflict(day_of_week, &block.start, &block.end)
-                    .await?
-                {
-                    let day_name = Self::day_number_to_name(day_of_week);
-                    eprintln!(
-                        "Warning: Skipping overlapping block '{}' on {}",
-                        block.title, day_name
-                    );
-                    continue;
-                }
+        if let Err(e) = self.load_tasks().await {
+            summary
+                .warnings
+                .push(format!("reloading tasks failed: {}", e));
+        }
+        summary
+    }
 
-                sqlx::query(
-                    "INSERT INTO schedule_blocks (day_of_week, start_time, end_time, block_type, title, description, priority)
-                     VALUES (?, ?, ?, ?, ?, ?, ?)",
-                )
-                .bind(day_of_week)
-                .bind(&block.start)
-                .bind(&block.end)
-                .bind(&block.block_type)
-                .bind(&block.title)
-                .bind(&block.description)
-                .bind(block.priority)
-                .execute(&self.db_pool)
+    /// Rewrite `item_order` as a contiguous 0..n sequence in the current
+    /// display order. Returns how many tasks changed.
+    async fn renumber_task_order(&self) -> Result<u64, sqlx::Error> {
+        let mut tx = self.db_pool.begin_with("BEGIN IMMEDIATE").await?;
+        let rows: Vec<(i64, Option<i64>)> =
+            sqlx::query_as("SELECT id, item_order FROM tasks ORDER BY item_order ASC, id ASC")
+                .fetch_all(&mut *tx)
                 .await?;
 
-                imported += 1;
+        let mut changed = 0;
+        for (order, (id, current)) in rows.into_iter().enumerate() {
+            let order = order as i64;
+            if current == Some(order) {
+                continue;
             }
+            sqlx::query("UPDATE tasks SET item_order = ? WHERE id = ?")
+                .bind(order)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+            changed += 1;
         }
 
-        self.refresh_calendar_data().await;
-        Ok(imported)
+        tx.commit().await?;
+        Ok(changed)
     }
 
-    pub async fn export_schedule_to_toml(
-        &self,
-        path: &Path,
-    ) -> Result<usize, Box<dyn std::error::Error>> {
-        let blocks = sqlx::query_as::<_, ScheduleBlock>(
-            "SELECT id, day_of_week, start_time, end_time, block_type, title, description, priority
-             FROM schedule_blocks ORDER BY day_of_week, start_time",
+    /// Clear task links to deleted events, promote subtasks whose parent is
+    /// gone, and drop a saved selection pointing at a deleted task
+    async fn remove_orphans(&self) -> Result<u64, sqlx::Error> {
+        let mut tx = self.db_pool.begin().await?;
+        let unlinked = sqlx::query(
+            "UPDATE tasks SET scheduled_event_id = NULL
+             WHERE scheduled_event_id IS NOT NULL
+               AND scheduled_event_id NOT IN (SELECT id FROM events)",
         )
-        .fetch_all(&self.db_pool)
-        .await?;
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
 
-        let block_defs: Vec<BlockDefinition> = blocks
-            .iter()
-            .map(|b| BlockDefinition {
-                day: Self::day_number_to_name(b.day_of_week),
-                block_type: b.block_type.clone(),
-                start: b.start_time.clone(),
-                end: b.end_time.clone(),
-                title: b.title.clone(),
-                description: b.description.clone(),
-                priority: b.priority,
-            })
-            .collect();
+        let promoted = sqlx::query(
+            "UPDATE tasks SET parent_id = NULL
+             WHERE parent_id IS NOT NULL AND parent_id NOT IN (SELECT id FROM tasks)",
+        )
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
 
-        let schedule = ScheduleToml { blocks: block_defs };
-        let toml_string = toml::to_string_pretty(&schedule)?;
-        std::fs::write(path, toml_string)?;
+        let stale_state = sqlx::query(
+            "DELETE FROM app_state
+             WHERE key = ? AND CAST(value AS INTEGER) NOT IN (SELECT id FROM tasks)",
+        )
+        .bind(SELECTED_TASK_KEY)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
 
-        Ok(blocks.len())
+        tx.commit().await?;
+        Ok(unlinked + promoted + stale_state)
+    }
+
+    async fn database_size(&self) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar(
+            "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
+        )
+        .fetch_one(&self.db_pool)
+        .await
     }
 
     pub async fn clear_all_schedule_blocks(&mut self) -> Result<u64, sqlx::Error> {
@@ -1185,7 +4189,7 @@ impl App {
 
         if blocks.is_empty() {
             println!("No schedule blocks defined.");
-            println!("Import with: triptych schedule import <file.toml>");
+            println!("Import with: triptych schedule import [file.toml]");
             return Ok(());
         }
 
@@ -1215,13 +4219,13 @@ impl App {
         Ok(())
     }
 
-    pub async fn delete_block_at_selected_cell(&mut self) -> Result<(), sqlx::Error> {
+    /// The schedule block covering the selected cell's day and time
+    async fn block_at_selected_cell(&self) -> Result<Option<ScheduleBlock>, sqlx::Error> {
         let date = self.selected_cell_date();
         let day_of_week = date.weekday().num_days_from_monday() as i32;
         let time = self.selected_cell_time();
         let time_str = format!("{:02}:{:02}", time.hour(), time.minute());
 
-        // Find block that contains this time
         let blocks = sqlx::query_as::<_, ScheduleBlock>(
             "SELECT id, day_of_week, start_time, end_time, block_type, title, description, priority
              FROM schedule_blocks WHERE day_of_week = ?",
@@ -1231,30 +4235,618 @@ impl App {
         .await?;
 
         let time_minutes = Self::time_to_minutes(&time_str).unwrap_or(0);
-
-        for block in blocks {
+        Ok(blocks.into_iter().find(|block| {
             let start = Self::time_to_minutes(&block.start_time).unwrap_or(0);
             let end = Self::time_to_minutes(&block.end_time).unwrap_or(0);
+            time_minutes >= start && time_minutes < end
+        }))
+    }
 
-            if time_minutes >= start && time_minutes < end {
-                sqlx::query("DELETE FROM schedule_blocks WHERE id = ?")
-                    .bind(block.id)
-                    .execute(&self.db_pool)
-                    .await?;
+    pub async fn delete_block_at_selected_cell(&mut self) -> Result<(), sqlx::Error> {
+        let Some(block) = self.block_at_selected_cell().await? else {
+            self.status_message = Some((
+                "No block at this time".to_string(),
+                std::time::Instant::now(),
+            ));
+            return Ok(());
+        };
 
-                self.refresh_calendar_data().await;
-                self.status_message = Some((
-                    format!("Deleted block: {}", block.title),
-                    std::time::Instant::now(),
-                ));
-                return Ok(());
-            }
-        }
+        sqlx::query("DELETE FROM schedule_blocks WHERE id = ?")
+            .bind(block.id)
+            .execute(&self.db_pool)
+            .await?;
 
+        self.refresh_calendar_data().await;
         self.status_message = Some((
-            "No block at this time".to_string(),
+            format!("Deleted block: {}", block.title),
             std::time::Instant::now(),
         ));
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn test_app() -> App {
+        App::new(migrations::memory_pool().await).await
+    }
+
+    /// Insert an open task at the end of the list, returning its id
+    async fn insert_task(
+        app: &App,
+        description: &str,
+        scheduled_at: Option<NaiveDateTime>,
+        priority: i32,
+    ) -> i64 {
+        sqlx::query(
+            "INSERT INTO tasks (description, completed, item_order, scheduled_at, priority, scheduled_time_known)
+             VALUES (?, false, (SELECT COALESCE(MAX(item_order), -1) + 1 FROM tasks), ?, ?, true)",
+        )
+        .bind(description)
+        .bind(scheduled_at.map(|at| at.and_utc()))
+        .bind(priority)
+        .execute(&app.db_pool)
+        .await
+        .unwrap()
+        .last_insert_rowid()
+    }
+
+    fn descriptions(tasks: &[Task]) -> Vec<&str> {
+        tasks.iter().map(|t| t.description.as_str()).collect()
+    }
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, d).unwrap()
+    }
+
+    fn at(d: u32, h: u32, m: u32) -> NaiveDateTime {
+        day(d).and_hms_opt(h, m, 0).unwrap()
+    }
+
+    fn time(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    /// (date, start, end) of each segment
+    fn spans(segments: &[EventSegment]) -> Vec<(NaiveDate, NaiveTime, Option<NaiveTime>)> {
+        segments.iter().map(|s| (s.date, s.start, s.end)).collect()
+    }
+
+    #[test]
+    fn event_crossing_midnight_splits_into_two_days() {
+        let segments =
+            event_day_segments("Party", at(16, 22, 0), at(17, 2, 0), &[day(16), day(17)]);
+
+        assert_eq!(
+            spans(&segments),
+            [
+                (day(16), time(22, 0), None),
+                (day(17), NaiveTime::MIN, Some(time(2, 0))),
+            ]
+        );
+        assert!(segments.iter().all(|s| s.title == "Party"));
+    }
+
+    #[test]
+    fn event_ending_at_midnight_stays_on_its_day() {
+        let segments = event_day_segments(
+            "Late shift",
+            at(16, 20, 0),
+            at(17, 0, 0),
+            &[day(16), day(17)],
+        );
+
+        assert_eq!(spans(&segments), [(day(16), time(20, 0), None)]);
+    }
+
+    #[test]
+    fn multi_day_event_is_clipped_to_visible_days() {
+        let segments =
+            event_day_segments("Trip", at(14, 10, 0), at(19, 12, 0), &[day(16), day(17)]);
+
+        assert_eq!(
+            spans(&segments),
+            [
+                (day(16), NaiveTime::MIN, None),
+                (day(17), NaiveTime::MIN, None)
+            ]
+        );
+    }
+
+    #[test]
+    fn zero_length_event_lasts_an_hour() {
+        let segments =
+            event_day_segments("Call", at(16, 23, 30), at(16, 23, 30), &[day(16), day(17)]);
+
+        assert_eq!(
+            spans(&segments),
+            [
+                (day(16), time(23, 30), None),
+                (day(17), NaiveTime::MIN, Some(time(0, 30))),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn tasks_for_day_use_local_day_boundaries() {
+        let app = test_app().await;
+        insert_task(&app, "late", Some(at(16, 23, 30)), 0).await;
+        insert_task(&app, "midnight", Some(at(17, 0, 0)), 0).await;
+        insert_task(&app, "early", Some(at(16, 0, 0)), 0).await;
+        insert_task(&app, "undated", None, 0).await;
+
+        let today = app.get_tasks_for_day(day(16)).await.unwrap();
+        assert_eq!(descriptions(&today), ["early", "late"]);
+
+        // Midnight belongs to the day it starts, not the one it ends
+        let tomorrow = app.get_tasks_for_day(day(17)).await.unwrap();
+        assert_eq!(descriptions(&tomorrow), ["midnight"]);
+    }
+
+    #[tokio::test]
+    async fn parsed_late_evening_task_stays_on_its_local_day() {
+        let app = test_app().await;
+        let target = crate::timezone::now().date_naive() + Duration::days(1);
+
+        // West of Greenwich, 11:30pm local is already the next day in UTC;
+        // the task must still be listed on its local day
+        let item =
+            crate::nlp::rules::RuleParser::try_parse("submit essay tomorrow 11:30pm").unwrap();
+        let (title, scheduled_at, _, _, _) = item_fields(item);
+        insert_task(&app, &title, scheduled_at.map(|at| at.naive_utc()), 0).await;
+
+        let tasks = app.get_tasks_for_day(target).await.unwrap();
+        assert_eq!(descriptions(&tasks), ["submit essay"]);
+        assert!(
+            app.get_tasks_for_day(target + Duration::days(1))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn tasks_for_day_sort_by_time_then_priority() {
+        let app = test_app().await;
+        insert_task(&app, "low at nine", Some(at(16, 9, 0)), 0).await;
+        insert_task(&app, "urgent at nine", Some(at(16, 9, 0)), 3).await;
+        insert_task(&app, "at eight", Some(at(16, 8, 0)), 0).await;
+
+        let tasks = app.get_tasks_for_day(day(16)).await.unwrap();
+        assert_eq!(
+            descriptions(&tasks),
+            ["at eight", "urgent at nine", "low at nine"]
+        );
+    }
+
+    async fn scheduled_at(app: &App, id: i64) -> Option<NaiveDateTime> {
+        app.get_task_by_id(id)
+            .await
+            .unwrap()
+            .and_then(|t| t.scheduled_at)
+            .map(|at| at.naive_utc())
+    }
+
+    #[tokio::test]
+    async fn rollover_moves_only_todays_and_overdue_open_tasks() {
+        let mut app = test_app().await;
+        let today = app
+            .display
+            .logical_date(crate::timezone::now().naive_local());
+        let at_ten = |date: NaiveDate| Some(date.and_time(time(10, 0)));
+
+        let overdue = insert_task(&app, "overdue", at_ten(today - Duration::days(2)), 0).await;
+        let due_today = insert_task(&app, "today", at_ten(today), 0).await;
+        let future = insert_task(&app, "future", at_ten(today + Duration::days(3)), 0).await;
+        let done = insert_task(&app, "done today", at_ten(today), 0).await;
+        let undated = insert_task(&app, "undated", None, 0).await;
+        sqlx::query("UPDATE tasks SET completed = 1 WHERE id = ?")
+            .bind(done)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+
+        let candidates = app.rollover_candidates().await.unwrap();
+        assert_eq!(descriptions(&candidates), ["overdue", "today"]);
+
+        let moved = app.rollover_to_tomorrow(time(17, 0)).await.unwrap();
+        assert_eq!(moved, 2);
+
+        let tomorrow_at_five = Some((today + Duration::days(1)).and_time(time(17, 0)));
+        assert_eq!(scheduled_at(&app, overdue).await, tomorrow_at_five);
+        assert_eq!(scheduled_at(&app, due_today).await, tomorrow_at_five);
+        assert_eq!(
+            scheduled_at(&app, future).await,
+            at_ten(today + Duration::days(3))
+        );
+        assert_eq!(scheduled_at(&app, done).await, at_ten(today));
+        assert_eq!(scheduled_at(&app, undated).await, None);
+    }
+
+    async fn count(app: &App, table: &str) -> i64 {
+        sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {}", table))
+            .fetch_one(&app.db_pool)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn compact_closes_order_gaps_and_keeps_rows() {
+        let mut app = test_app().await;
+        for (description, order) in [("a", 3), ("b", 10), ("c", 11), ("d", 40)] {
+            let id = insert_task(&app, description, None, 0).await;
+            sqlx::query("UPDATE tasks SET item_order = ? WHERE id = ?")
+                .bind(order)
+                .bind(id)
+                .execute(&app.db_pool)
+                .await
+                .unwrap();
+        }
+        // A saved selection pointing at a deleted task is an orphan
+        sqlx::query("INSERT INTO app_state (key, value) VALUES (?, '999')")
+            .bind(SELECTED_TASK_KEY)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO schedule_blocks (day_of_week, start_time, end_time, block_type, title, priority)
+             VALUES (0, '09:00', '10:00', 'deepwork', 'Focus', 1)",
+        )
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+        let (tasks, blocks) = (
+            count(&app, "tasks").await,
+            count(&app, "schedule_blocks").await,
+        );
+
+        let summary = app.compact().await;
+        assert!(summary.warnings.is_empty(), "{:?}", summary.warnings);
+        assert_eq!(summary.renumbered, 4);
+        assert_eq!(summary.orphans_removed, 1);
+
+        let orders: Vec<(String, i64)> =
+            sqlx::query_as("SELECT description, item_order FROM tasks ORDER BY item_order")
+                .fetch_all(&app.db_pool)
+                .await
+                .unwrap();
+        assert_eq!(
+            orders,
+            [
+                ("a".to_string(), 0),
+                ("b".to_string(), 1),
+                ("c".to_string(), 2),
+                ("d".to_string(), 3)
+            ]
+        );
+        assert_eq!(count(&app, "tasks").await, tasks);
+        assert_eq!(count(&app, "schedule_blocks").await, blocks);
+
+        // A second pass has nothing left to do
+        let again = app.compact().await;
+        assert_eq!((again.renumbered, again.orphans_removed), (0, 0));
+    }
+
+    #[tokio::test]
+    async fn shift_task_days_moves_forward_and_back_keeping_time() {
+        let mut app = test_app().await;
+        let id = insert_task(&app, "essay", Some(at(16, 15, 0)), 0).await;
+
+        assert!(app.shift_task_days(id, 1).await.unwrap());
+        assert_eq!(scheduled_at(&app, id).await, Some(at(17, 15, 0)));
+
+        assert!(app.shift_task_days(id, -1).await.unwrap());
+        assert_eq!(scheduled_at(&app, id).await, Some(at(16, 15, 0)));
+    }
+
+    #[tokio::test]
+    async fn shift_task_days_skips_unscheduled_and_missing_tasks() {
+        let mut app = test_app().await;
+        let undated = insert_task(&app, "someday", None, 0).await;
+
+        assert!(!app.shift_task_days(undated, 1).await.unwrap());
+        assert_eq!(scheduled_at(&app, undated).await, None);
+        assert!(!app.shift_task_days(999, 1).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn calendar_navigation_clamps_at_max_week_offset() {
+        let mut app = test_app().await;
+        let today = app
+            .display
+            .logical_date(crate::timezone::now().naive_local());
+        let limit = Duration::weeks(MAX_WEEK_OFFSET);
+
+        app.shift_anchor_days(MAX_WEEK_OFFSET * 7);
+        assert_eq!(app.calendar_anchor, today + limit);
+        app.shift_anchor_days(7);
+        assert_eq!(app.calendar_anchor, today + limit);
+        app.shift_anchor_days(-7);
+        assert_eq!(app.calendar_anchor, today + limit - Duration::weeks(1));
+
+        app.shift_anchor_days(-2 * MAX_WEEK_OFFSET * 7);
+        assert_eq!(app.calendar_anchor, today - limit);
+        app.shift_anchor_days(-1);
+        assert_eq!(app.calendar_anchor, today - limit);
+    }
+
+    #[tokio::test]
+    async fn huge_calendar_jumps_clamp_instead_of_overflowing() {
+        let mut app = test_app().await;
+        let today = app
+            .display
+            .logical_date(crate::timezone::now().naive_local());
+        let limit = Duration::weeks(MAX_WEEK_OFFSET);
+
+        app.shift_anchor_days(100_000_000);
+        assert_eq!(app.calendar_anchor, today + limit);
+        app.shift_anchor_months(-1_000_000);
+        assert_eq!(app.calendar_anchor, today - limit);
+    }
+
+    #[tokio::test]
+    async fn each_sort_mode_orders_the_list() {
+        let app = test_app().await;
+        insert_task(&app, "walk dog", Some(at(18, 9, 0)), 1).await;
+        insert_task(&app, "Buy milk", None, 3).await;
+        insert_task(&app, "answer email", Some(at(16, 9, 0)), 1).await;
+        insert_task(&app, "call bank", Some(at(17, 9, 0)), 0).await;
+
+        let cases = [
+            (
+                SortMode::Manual,
+                ["walk dog", "Buy milk", "answer email", "call bank"],
+            ),
+            (
+                SortMode::Priority,
+                ["Buy milk", "walk dog", "answer email", "call bank"],
+            ),
+            (
+                SortMode::DueDate,
+                ["answer email", "call bank", "walk dog", "Buy milk"],
+            ),
+            (
+                SortMode::Alphabetical,
+                ["answer email", "Buy milk", "call bank", "walk dog"],
+            ),
+        ];
+        for (mode, expected) in cases {
+            let tasks = load_task_list(&app.db_pool, mode).await.unwrap();
+            assert_eq!(descriptions(&tasks), expected, "{:?}", mode);
+        }
+    }
+
+    fn schedule(toml: &str) -> ScheduleToml {
+        toml::from_str(toml).unwrap()
+    }
+
+    /// (day, start, end, title) of every block, in day and time order
+    async fn blocks(app: &App) -> Vec<(i32, String, String, String)> {
+        sqlx::query_as(
+            "SELECT day_of_week, start_time, end_time, title FROM schedule_blocks
+             ORDER BY day_of_week, start_time",
+        )
+        .fetch_all(&app.db_pool)
+        .await
+        .unwrap()
+    }
+
+    const MONDAY_FOCUS: &str = r#"
+        [[blocks]]
+        day = "monday"
+        type = "deepwork"
+        start = "09:00"
+        end = "10:00"
+        title = "Focus"
+    "#;
+
+    /// A duplicate of MONDAY_FOCUS, a block overlapping it, and a new one
+    const MIXED_IMPORT: &str = r#"
+        [[blocks]]
+        day = "monday"
+        type = "deepwork"
+        start = "09:00"
+        end = "10:00"
+        title = "Focus"
+
+        [[blocks]]
+        day = "monday"
+        type = "admin"
+        start = "09:30"
+        end = "11:00"
+        title = "Email"
+
+        [[blocks]]
+        day = "tuesday"
+        type = "deepwork"
+        start = "09:00"
+        end = "10:00"
+        title = "Focus"
+    "#;
+
+    #[tokio::test]
+    async fn appending_import_skips_duplicates_and_overlaps() {
+        let mut app = test_app().await;
+        app.import_schedule(schedule(MONDAY_FOCUS), false)
+            .await
+            .unwrap();
+
+        let summary = app
+            .import_schedule(schedule(MIXED_IMPORT), false)
+            .await
+            .unwrap();
+        assert_eq!((summary.imported, summary.duplicates), (1, 1));
+
+        let focus = |day| {
+            (
+                day,
+                "09:00".to_string(),
+                "10:00".to_string(),
+                "Focus".to_string(),
+            )
+        };
+        assert_eq!(blocks(&app).await, [focus(0), focus(1)]);
+    }
+
+    #[tokio::test]
+    async fn replacing_import_starts_from_an_empty_schedule() {
+        let mut app = test_app().await;
+        app.import_schedule(
+            schedule(
+                r#"
+                [[blocks]]
+                day = "friday"
+                type = "relax"
+                start = "18:00"
+                end = "20:00"
+                title = "Games"
+                "#,
+            ),
+            false,
+        )
+        .await
+        .unwrap();
+
+        let summary = app
+            .import_schedule(schedule(MIXED_IMPORT), true)
+            .await
+            .unwrap();
+        // Within the import, the Monday overlap is still skipped
+        assert_eq!((summary.imported, summary.duplicates), (2, 0));
+
+        let titles: Vec<(i32, String)> = blocks(&app)
+            .await
+            .into_iter()
+            .map(|(day, _, _, title)| (day, title))
+            .collect();
+        assert_eq!(titles, [(0, "Focus".to_string()), (1, "Focus".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn invalid_import_leaves_the_schedule_unchanged() {
+        let mut app = test_app().await;
+        app.import_schedule(schedule(MONDAY_FOCUS), false)
+            .await
+            .unwrap();
+
+        let backwards = r#"
+            [[blocks]]
+            day = "wednesday"
+            type = "deepwork"
+            start = "11:00"
+            end = "10:00"
+            title = "Backwards"
+        "#;
+        assert!(
+            app.import_schedule(schedule(backwards), true)
+                .await
+                .is_err()
+        );
+        assert_eq!(blocks(&app).await.len(), 1);
+    }
+
+    async fn insert_event(
+        app: &App,
+        title: &str,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+        event_type: &str,
+    ) {
+        sqlx::query(
+            "INSERT INTO events (title, start_time, end_time, event_type) VALUES (?, ?, ?, ?)",
+        )
+        .bind(title)
+        .bind(start.and_utc())
+        .bind(end.and_utc())
+        .bind(event_type)
+        .execute(&app.db_pool)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn calendar_data_covers_exactly_the_visible_week() {
+        let mut app = test_app().await;
+        app.shift_anchor_days(14);
+        let monday = app.week_start();
+        let on = |offset: i64, h: u32| (monday + Duration::days(offset)).and_time(time(h, 0));
+
+        insert_task(&app, "timed", Some(on(1, 10)), 2).await;
+        let date_only = insert_task(&app, "date only", Some(on(2, 9)), 0).await;
+        let done = insert_task(&app, "done", Some(on(3, 11)), 0).await;
+        insert_task(&app, "last week", Some(on(-1, 10)), 0).await;
+        insert_task(&app, "next week", Some(on(7, 10)), 0).await;
+        sqlx::query("UPDATE tasks SET scheduled_time_known = 0 WHERE id = ?")
+            .bind(date_only)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE tasks SET completed = 1 WHERE id = ?")
+            .bind(done)
+            .execute(&app.db_pool)
+            .await
+            .unwrap();
+        app.import_schedule(schedule(MONDAY_FOCUS), false)
+            .await
+            .unwrap();
+        insert_event(&app, "Overnight", on(-1, 22), on(0, 2), "event").await;
+        insert_event(&app, "Holiday", on(4, 0), on(5, 0), "all_day").await;
+
+        app.refresh_calendar_data().await;
+        let data = &app.calendar_data;
+
+        assert_eq!(
+            data.scheduled_tasks,
+            [(
+                monday + Duration::days(1),
+                time(10, 0),
+                "timed".to_string(),
+                2
+            )]
+        );
+        assert_eq!(
+            data.all_day,
+            [
+                (monday + Duration::days(2), "date only".to_string(), 0),
+                (monday + Duration::days(4), "Holiday".to_string(), 0)
+            ]
+        );
+        let blocks: Vec<(NaiveDate, &str)> = data
+            .schedule_blocks
+            .iter()
+            .map(|(date, block)| (*date, block.title.as_str()))
+            .collect();
+        assert_eq!(blocks, [(monday, "Focus")]);
+        assert_eq!(
+            spans(&data.events),
+            [(monday, NaiveTime::MIN, Some(time(2, 0)))]
+        );
+    }
+
+    #[tokio::test]
+    async fn block_types_come_from_the_schedule() {
+        let mut app = test_app().await;
+        assert!(app.list_block_types().await.unwrap().is_empty());
+
+        let mut toml = String::new();
+        for (day, block_type) in [
+            ("monday", "relax"),
+            ("tuesday", "deepwork"),
+            ("wednesday", "relax"),
+            ("thursday", "gardening"),
+        ] {
+            toml.push_str(&format!(
+                "[[blocks]]\nday = \"{}\"\ntype = \"{}\"\nstart = \"09:00\"\nend = \"10:00\"\ntitle = \"Block\"\n\n",
+                day, block_type
+            ));
+        }
+        app.import_schedule(schedule(&toml), false).await.unwrap();
+
+        assert_eq!(
+            app.list_block_types().await.unwrap(),
+            ["deepwork", "gardening", "relax"]
+        );
+    }
+}
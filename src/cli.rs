@@ -4,27 +4,128 @@ use std::path::PathBuf;
 #[derive(Parser)]
 #[command(name = "triptych")]
 #[command(about = "Terminal productivity suite", long_about = None)]
+#[command(disable_version_flag = true)]
 pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
+
+    /// Print the version, database schema version and whether Ollama is reachable
+    #[arg(long, short = 'V')]
+    pub version: bool,
+
+    /// Color palette: "dark" (default), "light", "high-contrast" or "colorblind"
+    /// (overrides TRIPTYCH_PALETTE and the theme file)
+    #[arg(long, global = true)]
+    pub palette: Option<String>,
+
+    /// Only print errors on stderr (overrides TRIPTYCH_LOG); implied by --json
+    #[arg(long, short, global = true)]
+    pub quiet: bool,
+
+    /// IANA timezone to plan in, e.g. "Europe/Berlin" (overrides TRIPTYCH_TZ
+    /// and TZ; defaults to the system zone)
+    #[arg(long, global = true)]
+    pub tz: Option<String>,
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
     /// Add a new task
-    Add { description: String },
+    Add {
+        description: String,
+        /// Add it as a subtask of the task with this ID
+        #[arg(long)]
+        parent: Option<i64>,
+    },
 
     /// List all tasks
-    List,
+    List {
+        /// Only show tasks with this tag (repeatable; tasks must have every tag)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// Only show tasks at this priority (low, medium, high, urgent)
+        #[arg(long)]
+        priority: Option<String>,
+        /// Print tasks as a JSON array instead of human-readable text
+        #[arg(long)]
+        json: bool,
+        /// Print each task as a block with its ID, schedule, priority, tags
+        /// and original input
+        #[arg(long, short, conflicts_with = "json")]
+        verbose: bool,
+        /// Order tasks by "manual", "priority", "due" or "alpha" (defaults to
+        /// the TUI's setting)
+        #[arg(long)]
+        sort: Option<String>,
+    },
+
+    /// Search tasks, events and emails by keyword, most recent first
+    Search {
+        query: String,
+        /// Print matches as a JSON array instead of human-readable text
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Replace a task's text, re-parsing dates, tags and priority
+    Edit { id: i64, description: String },
+
+    /// Show a task's notes, or replace them with TEXT ("" clears them)
+    Notes { id: i64, text: Option<String> },
+
+    /// Show a task's repeat rule, or set it to an RRULE such as
+    /// "FREQ=WEEKLY;BYDAY=MO" ("" stops it repeating)
+    Repeat { id: i64, rule: Option<String> },
+
+    /// Set a task's priority (low, medium, high, urgent)
+    Priority { id: i64, level: String },
 
     /// Mark a task as done
     Done { id: i64 },
 
-    /// Remove a task
+    /// Mark task ID as blocked until task ON is done
+    Depend { id: i64, on: i64 },
+
+    /// Remove a dependency added with `depend`
+    Undepend { id: i64, on: i64 },
+
+    /// Move a task and its subtasks to the trash
     Rm { id: i64 },
 
-    /// Clear completed tasks
-    Clear,
+    /// Move completed tasks to the trash (asks first when run interactively)
+    Clear {
+        /// Don't ask for confirmation; required when stdin isn't a terminal
+        #[arg(long, short)]
+        yes: bool,
+    },
+
+    /// Turn a scheduled task into a calendar event (the task moves to the trash)
+    Convert { id: i64 },
+
+    /// List tasks in the trash
+    Trash,
+
+    /// Bring a task back from the trash
+    Restore { id: i64 },
+
+    /// Permanently delete everything in the trash
+    EmptyTrash,
+
+    /// Move today's and overdue incomplete tasks to tomorrow
+    Rollover {
+        /// Time of day to schedule them at (HH:MM)
+        #[arg(long, default_value = "09:00")]
+        at: String,
+        /// Show what would move without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Show how many tasks were completed today and this week
+    Summary,
+
+    /// Print today's schedule blocks, events and open tasks in time order
+    Today,
 
     /// Start the background daemon
     Daemon,
@@ -35,18 +136,66 @@ pub enum Commands {
     /// Check daemon status
     Status,
 
+    /// Print the resolved database path
+    DbPath {
+        /// Print as JSON: { "db_path": "..." }
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Back up tasks, events and schedule blocks to a JSON file
+    Export {
+        /// Output file path
+        #[arg(default_value = "triptych-backup.json")]
+        path: PathBuf,
+    },
+
+    /// Restore a JSON backup, skipping rows that already exist
+    Import {
+        /// Path to a file written by `triptych export`
+        path: PathBuf,
+    },
+
+    /// Import events from an iCalendar (.ics) file
+    ImportIcs {
+        /// Path to the .ics file
+        path: PathBuf,
+        /// Print the parsed events without saving them
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Renumber task order, remove orphaned rows and VACUUM the database
+    Maintenance,
+
     /// Schedule management commands
     #[command(subcommand)]
     Schedule(ScheduleCommands),
+
+    /// List, rename and remove tags across all tasks
+    #[command(subcommand)]
+    Tag(TagCommands),
+}
+
+#[derive(Subcommand)]
+pub enum TagCommands {
+    /// Show every tag and how many tasks have it
+    List,
+
+    /// Rename a tag on every task, merging it into NEW where both are present
+    Rename { old: String, new: String },
+
+    /// Remove a tag from every task
+    Rm { tag: String },
 }
 
 #[derive(Subcommand)]
 pub enum ScheduleCommands {
-    /// Import schedule blocks from a TOML file
+    /// Import schedule blocks from a TOML or JSON file
     Import {
-        /// Path to TOML file
-        file: PathBuf,
-        /// Clear existing blocks before import
+        /// Path to a TOML file (or .json); omit it for the built-in template
+        file: Option<PathBuf>,
+        /// Replace existing blocks instead of merging the file into them
         #[arg(long)]
         clear: bool,
     },
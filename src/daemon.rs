@@ -1,23 +1,52 @@
-use crate::nlp::{NLPParser, types::ParseResult};
+use crate::app::{
+    EnhancedTaskInfo, InsertPosition, SortMode, clear_completed, complete_task, enhance_tasks,
+    item_fields, load_task_list, remove_task, reserve_item_order, update_task_from_input,
+};
+use crate::nlp::{
+    NLPParser,
+    types::{CacheEntry, ParseResult},
+};
+use crate::sync::warm_cache;
 use anyhow::{Context, Result};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use sqlx::SqlitePool;
+use std::fs::OpenOptions;
+use std::io::Write;
 use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use std::time::Instant;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use tokio::net::{UnixListener, UnixStream};
 use tokio::signal;
+use tokio::signal::unix::{SignalKind, signal as unix_signal};
 
 // Socket path (will be in /tmp on Unix systems)
 fn socket_path() -> PathBuf {
     std::env::temp_dir().join("triptych.sock")
 }
 
+// PID file marking the socket as owned by a live daemon
+fn lock_path() -> PathBuf {
+    std::env::temp_dir().join("triptych.pid")
+}
+
+// Cap on cache entries handed to a foreground process in one response
+const EXPORT_CACHE_LIMIT: usize = 500;
+
+// Largest message either side will accept in one frame
+const MAX_FRAME_BYTES: u32 = 4 * 1024 * 1024;
+
 // Messages sent between CLI and daemon
 #[derive(Serialize, Deserialize, Debug)]
 pub enum DaemonRequest {
     Parse { input: String },
     AddTask { description: String },
+    EditTask { id: i64, description: String },
+    ListTasks { sort: SortMode },
+    CompleteTask { id: i64 },
+    RemoveTask { id: i64 },
+    ClearCompleted,
+    ExportCache,
     Shutdown,
     Health,
 }
@@ -25,59 +54,69 @@ pub enum DaemonRequest {
 #[derive(Serialize, Deserialize, Debug)]
 pub enum DaemonResponse {
     ParseResult(ParseResult),
-    TaskAdded { id: i64 },
+    TaskAdded {
+        id: i64,
+    },
+    TaskUpdated {
+        id: i64,
+    },
+    Tasks(Vec<EnhancedTaskInfo>),
+    TaskCompleted {
+        id: i64,
+    },
+    TaskRemoved {
+        id: i64,
+    },
+    Cleared {
+        count: u64,
+    },
+    NotFound {
+        id: i64,
+    },
+    HealthInfo {
+        pid: u32,
+        uptime_secs: u64,
+        cache_entries: usize,
+    },
+    CacheEntries(Vec<CacheEntry>),
     Ok,
     Error(String),
 }
 
 /// Start the persistent background daemon
 pub async fn start_daemon(db: SqlitePool, nlp: Arc<NLPParser>) -> Result<()> {
+    let started = Instant::now();
     let socket = socket_path();
 
-    // Remove old socket if exists
+    // Only remove the socket once we own the lock, so a live daemon's
+    // socket is never clobbered
+    acquire_lock()?;
     let _ = std::fs::remove_file(&socket);
 
     let listener =
         UnixListener::bind(&socket).context(format!("Failed to bind to socket: {:?}", socket))?;
 
-    eprintln!("[Daemon] Started at {:?}", socket);
-    eprintln!("[Daemon] Pre-warming Ollama and loading cache...");
+    tracing::info!("[Daemon] Started at {:?}", socket);
+    tracing::info!("[Daemon] Pre-warming Ollama and loading cache...");
 
     // Pre-warm Ollama
     let warmup_start = std::time::Instant::now();
     let _ = nlp.parse("warmup query").await;
-    eprintln!(
+    tracing::info!(
         "[Daemon] Pre-warmed in {:.2}s",
         warmup_start.elapsed().as_secs_f64()
     );
 
     // Preload cache from database
     let cache_start = std::time::Instant::now();
-    let rows: Vec<(String, i64)> = sqlx::query_as(
-        r#"
-        SELECT natural_language_input, COUNT(*) as count
-        FROM tasks
-        WHERE natural_language_input IS NOT NULL
-        GROUP BY natural_language_input
-        ORDER BY count DESC
-        LIMIT 100
-        "#,
-    )
-    .fetch_all(&db)
-    .await?;
+    let loaded = warm_cache(&db, &nlp).await?;
 
-    let mut loaded = 0;
-    for (input, _) in rows {
-        let _ = nlp.parse(&input).await;
-        loaded += 1;
-    }
-
-    eprintln!(
+    tracing::info!(
         "[Daemon] Loaded {} cache entries in {:.2}s",
         loaded,
         cache_start.elapsed().as_secs_f64()
     );
-    eprintln!("[Daemon] Ready! Listening for commands...");
+    tracing::info!("[Daemon] Ready! Listening for commands...");
 
     // Setup graceful shutdown
     let (shutdown_tx, mut shutdown_rx) = tokio::sync::broadcast::channel::<()>(1);
@@ -86,10 +125,31 @@ pub async fn start_daemon(db: SqlitePool, nlp: Arc<NLPParser>) -> Result<()> {
     let shutdown_tx_clone = shutdown_tx.clone();
     tokio::spawn(async move {
         signal::ctrl_c().await.ok();
-        eprintln!("\n[Daemon] Shutting down...");
+        tracing::info!("\n[Daemon] Shutting down...");
         let _ = shutdown_tx_clone.send(());
     });
 
+    // Re-warm the cache on SIGHUP without touching the socket. Reloads run on
+    // their own task, so connections keep being served meanwhile.
+    let mut hangup =
+        unix_signal(SignalKind::hangup()).context("Failed to install SIGHUP handler")?;
+    let reload_db = db.clone();
+    let reload_nlp = nlp.clone();
+    tokio::spawn(async move {
+        while hangup.recv().await.is_some() {
+            tracing::info!("[Daemon] SIGHUP received, reloading cache...");
+            let reload_start = std::time::Instant::now();
+            match warm_cache(&reload_db, &reload_nlp).await {
+                Ok(loaded) => tracing::info!(
+                    "[Daemon] Reloaded {} cache entries in {:.2}s",
+                    loaded,
+                    reload_start.elapsed().as_secs_f64()
+                ),
+                Err(e) => tracing::error!("[Daemon] Cache reload failed: {}", e),
+            }
+        }
+    });
+
     // Accept connections until shutdown
     loop {
         tokio::select! {
@@ -102,152 +162,294 @@ pub async fn start_daemon(db: SqlitePool, nlp: Arc<NLPParser>) -> Result<()> {
 
                         tokio::spawn(async move {
                             tokio::select! {
-                                result = handle_client(stream, db, nlp) => {
+                                result = handle_client(stream, db, nlp, started) => {
                                     if let Err(e) = result {
-                                        eprintln!("[Daemon] Client error: {}", e);
+                                        tracing::error!("[Daemon] Client error: {}", e);
                                     }
                                 }
                                 _ = shutdown_rx.recv() => {
-                                    eprintln!("[Daemon] Client connection closed due to shutdown");
+                                    tracing::info!("[Daemon] Client connection closed due to shutdown");
                                 }
                             }
                         });
                     }
                     Err(e) => {
-                        eprintln!("[Daemon] Accept error: {}", e);
+                        tracing::error!("[Daemon] Accept error: {}", e);
                     }
                 }
             }
 
             _ = shutdown_rx.recv() => {
-                eprintln!("[Daemon] Shutdown signal received, exiting");
+                tracing::info!("[Daemon] Shutdown signal received, exiting");
                 break;
             }
         }
     }
 
-    // Cleanup socket
-    let _ = std::fs::remove_file(&socket);
+    cleanup();
     Ok(())
 }
 
-/// Handle a single client connection
-async fn handle_client(mut stream: UnixStream, db: SqlitePool, nlp: Arc<NLPParser>) -> Result<()> {
-    // Read request
-    let mut buffer = vec![0u8; 8192];
-    let n = stream
-        .read(&mut buffer)
+/// Create the lock file with our PID. A lock left by a process that is no
+/// longer running is removed; a live owner is an error.
+fn acquire_lock() -> Result<()> {
+    let path = lock_path();
+
+    for _ in 0..2 {
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(mut file) => {
+                write!(file, "{}", std::process::id())?;
+                return Ok(());
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                let owner = std::fs::read_to_string(&path)
+                    .ok()
+                    .and_then(|pid| pid.trim().parse::<u32>().ok());
+                if let Some(pid) = owner.filter(|pid| process_alive(*pid)) {
+                    anyhow::bail!("Daemon already running (pid {})", pid);
+                }
+                tracing::info!("[Daemon] Removing stale lock file {:?}", path);
+                let _ = std::fs::remove_file(&path);
+            }
+            Err(e) => {
+                return Err(e).context(format!("Failed to create lock file: {:?}", path));
+            }
+        }
+    }
+
+    anyhow::bail!("Failed to create lock file: {:?}", path)
+}
+
+/// Whether `pid` is a running process other than this one
+fn process_alive(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    if pid <= 0 || pid as u32 == std::process::id() {
+        return false;
+    }
+
+    // Signal 0 only checks that the process exists; EPERM means it exists
+    // but belongs to another user
+    let result = unsafe { libc::kill(pid, 0) };
+    result == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+/// Remove the socket and lock file on the way out
+fn cleanup() {
+    let _ = std::fs::remove_file(socket_path());
+    let _ = std::fs::remove_file(lock_path());
+}
+
+/// Handle a client connection: answer length-prefixed requests until the
+/// client closes its side
+async fn handle_client(
+    mut stream: UnixStream,
+    db: SqlitePool,
+    nlp: Arc<NLPParser>,
+    started: Instant,
+) -> Result<()> {
+    while let Some(request) = read_frame::<_, DaemonRequest>(&mut stream)
         .await
-        .context("Failed to read from socket")?;
+        .context("Failed to read request")?
+    {
+        if let DaemonRequest::Shutdown = request {
+            // Send OK then exit
+            write_frame(&mut stream, &DaemonResponse::Ok).await?;
+            cleanup();
+            std::process::exit(0);
+        }
 
-    if n == 0 {
-        return Ok(());
+        let response = process_request(request, &db, &nlp, started).await;
+        write_frame(&mut stream, &response)
+            .await
+            .context("Failed to write response")?;
     }
 
-    let request: DaemonRequest =
-        serde_json::from_slice(&buffer[..n]).context("Failed to parse request")?;
+    Ok(())
+}
 
-    // Process request
-    let response = match request {
+async fn process_request(
+    request: DaemonRequest,
+    db: &SqlitePool,
+    nlp: &Arc<NLPParser>,
+    started: Instant,
+) -> DaemonResponse {
+    match request {
         DaemonRequest::Parse { input } => match nlp.parse(&input).await {
             Ok(result) => DaemonResponse::ParseResult(result),
             Err(e) => DaemonResponse::Error(format!("Parse error: {}", e)),
         },
 
         DaemonRequest::AddTask { description } => {
-            match add_task_to_db(&db, &nlp, &description).await {
+            match add_task_to_db(db, nlp, &description).await {
                 Ok(id) => DaemonResponse::TaskAdded { id },
                 Err(e) => DaemonResponse::Error(format!("Database error: {}", e)),
             }
         }
 
-        DaemonRequest::Shutdown => {
-            // Send OK then exit
-            let response_bytes = serde_json::to_vec(&DaemonResponse::Ok)?;
-            stream.write_all(&response_bytes).await?;
-            std::process::exit(0);
+        DaemonRequest::EditTask { id, description } => {
+            match update_task_from_input(db, nlp, id, &description).await {
+                Ok(true) => DaemonResponse::TaskUpdated { id },
+                Ok(false) => DaemonResponse::NotFound { id },
+                Err(e) => DaemonResponse::Error(format!("Database error: {}", e)),
+            }
         }
 
-        DaemonRequest::Health => DaemonResponse::Ok,
-    };
-
-    // Send response
-    let response_bytes = serde_json::to_vec(&response)?;
-    stream
-        .write_all(&response_bytes)
-        .await
-        .context("Failed to write response")?;
+        DaemonRequest::ListTasks { sort } => match load_task_list(db, sort).await {
+            Ok(tasks) => DaemonResponse::Tasks(enhance_tasks(&tasks)),
+            Err(e) => DaemonResponse::Error(format!("Database error: {}", e)),
+        },
 
-    Ok(())
-}
+        DaemonRequest::CompleteTask { id } => match complete_task(db, id).await {
+            Ok(true) => DaemonResponse::TaskCompleted { id },
+            Ok(false) => DaemonResponse::NotFound { id },
+            Err(e) => DaemonResponse::Error(format!("Database error: {}", e)),
+        },
 
-/// Add a task to the database (daemon version)
-async fn add_task_to_db(db: &SqlitePool, nlp: &Arc<NLPParser>, description: &str) -> Result<i64> {
-    use crate::nlp::types::{ParsedItem, Priority};
+        DaemonRequest::RemoveTask { id } => match remove_task(db, id).await {
+            Ok(true) => DaemonResponse::TaskRemoved { id },
+            Ok(false) => DaemonResponse::NotFound { id },
+            Err(e) => DaemonResponse::Error(format!("Database error: {}", e)),
+        },
 
-    let parse_result = nlp.parse(description).await?;
+        DaemonRequest::ClearCompleted => match clear_completed(db).await {
+            Ok(count) => DaemonResponse::Cleared { count },
+            Err(e) => DaemonResponse::Error(format!("Database error: {}", e)),
+        },
 
-    let (task_title, scheduled_at, priority_value, tags_list) = match parse_result.item {
-        ParsedItem::Task(nlp_task) => {
-            let priority = match nlp_task.priority {
-                Priority::Urgent => 3,
-                Priority::High => 2,
-                Priority::Medium => 1,
-                Priority::Low => 0,
-            };
-            (nlp_task.title, nlp_task.due_date, priority, nlp_task.tags)
+        DaemonRequest::ExportCache => {
+            DaemonResponse::CacheEntries(nlp.export_cache(EXPORT_CACHE_LIMIT).await)
         }
-        ParsedItem::Event(event) => (event.title, Some(event.start_time), 1, event.tags),
-    };
 
-    let tags_json = if tags_list.is_empty() {
-        None
-    } else {
-        Some(serde_json::to_string(&tags_list).unwrap_or_default())
-    };
+        // Handled by the connection loop, which has to reply before exiting
+        DaemonRequest::Shutdown => DaemonResponse::Ok,
 
-    // Use runtime query instead of query! macro
-    let result = sqlx::query(
-        r#"
-        INSERT INTO tasks (description, completed, item_order, priority, natural_language_input, tags, scheduled_at)
-        VALUES (?, ?, (SELECT COALESCE(MAX(item_order), -1) + 1 FROM tasks), ?, ?, ?, ?)
-        "#
-    )
-    .bind(&task_title)
-    .bind(false)
-    .bind(priority_value)
-    .bind(description)
-    .bind(tags_json)
-    .bind(scheduled_at)
-    .execute(db)
-    .await?;
-
-    Ok(result.last_insert_rowid())
+        DaemonRequest::Health => DaemonResponse::HealthInfo {
+            pid: std::process::id(),
+            uptime_secs: started.elapsed().as_secs(),
+            cache_entries: nlp.cache_stats().await.0,
+        },
+    }
 }
 
-/// Send a request to the daemon
-pub async fn send_to_daemon(request: DaemonRequest) -> Result<DaemonResponse> {
-    let socket = socket_path();
+/// Write one message as a big-endian u32 length followed by its JSON
+async fn write_frame<W, T>(writer: &mut W, message: &T) -> Result<()>
+where
+    W: AsyncWrite + Unpin,
+    T: Serialize,
+{
+    let payload = serde_json::to_vec(message)?;
+    let len = u32::try_from(payload.len())
+        .ok()
+        .filter(|len| *len <= MAX_FRAME_BYTES)
+        .context("Message too large")?;
+
+    writer.write_u32(len).await?;
+    writer.write_all(&payload).await?;
+    writer.flush().await?;
+    Ok(())
+}
 
-    if !socket.exists() {
-        anyhow::bail!("Daemon not running (socket not found)");
+/// Read one length-prefixed message. Returns None when the peer closes the
+/// connection cleanly between messages.
+async fn read_frame<R, T>(reader: &mut R) -> Result<Option<T>>
+where
+    R: AsyncRead + Unpin,
+    T: DeserializeOwned,
+{
+    let len = match reader.read_u32().await {
+        Ok(len) => len,
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    if len > MAX_FRAME_BYTES {
+        anyhow::bail!(
+            "Message of {} bytes exceeds the {} byte limit",
+            len,
+            MAX_FRAME_BYTES
+        );
     }
 
-    let mut stream = UnixStream::connect(&socket)
+    let mut payload = vec![0u8; len as usize];
+    reader
+        .read_exact(&mut payload)
         .await
-        .context("Failed to connect to daemon")?;
+        .context("Connection closed mid-message")?;
+    Ok(Some(serde_json::from_slice(&payload)?))
+}
 
-    // Send request
-    let request_bytes = serde_json::to_vec(&request)?;
-    stream.write_all(&request_bytes).await?;
-    stream.shutdown().await?;
+/// Insert every item parsed from `description` at the end of the list,
+/// returning the first new task's id
+async fn add_task_to_db(db: &SqlitePool, nlp: &Arc<NLPParser>, description: &str) -> Result<i64> {
+    let parse_result = nlp.parse(description).await?;
+
+    // Use runtime query instead of query! macro
+    let mut tx = db.begin_with("BEGIN IMMEDIATE").await?;
+    let mut first_id = None;
+    for item in parse_result.into_items() {
+        let (task_title, scheduled_at, time_known, priority_value, tags_list) = item_fields(item);
+
+        let tags_json = if tags_list.is_empty() {
+            None
+        } else {
+            Some(serde_json::to_string(&tags_list).unwrap_or_default())
+        };
+
+        let item_order = reserve_item_order(&mut tx, InsertPosition::End).await?;
+        let result = sqlx::query(
+            r#"
+            INSERT INTO tasks (description, completed, item_order, priority, natural_language_input, tags, scheduled_at, scheduled_time_known)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#
+        )
+        .bind(&task_title)
+        .bind(false)
+        .bind(item_order)
+        .bind(priority_value)
+        .bind(description)
+        .bind(tags_json)
+        .bind(scheduled_at)
+        .bind(time_known)
+        .execute(&mut *tx)
+        .await?;
+        first_id.get_or_insert(result.last_insert_rowid());
+    }
+    tx.commit().await?;
 
-    // Read response
-    let mut buffer = vec![0u8; 8192];
-    let n = stream.read(&mut buffer).await?;
+    first_id.context("Parser returned no items")
+}
 
-    let response: DaemonResponse = serde_json::from_slice(&buffer[..n])?;
-    Ok(response)
+/// A connection to the daemon that can carry several requests in turn
+pub struct DaemonClient {
+    stream: UnixStream,
+}
+
+impl DaemonClient {
+    pub async fn connect() -> Result<Self> {
+        let socket = socket_path();
+
+        if !socket.exists() {
+            anyhow::bail!("Daemon not running (socket not found)");
+        }
+
+        let stream = UnixStream::connect(&socket)
+            .await
+            .context("Failed to connect to daemon")?;
+        Ok(Self { stream })
+    }
+
+    pub async fn request(&mut self, request: &DaemonRequest) -> Result<DaemonResponse> {
+        write_frame(&mut self.stream, request).await?;
+        read_frame(&mut self.stream)
+            .await?
+            .context("Daemon closed the connection without responding")
+    }
+}
+
+/// Send a single request to the daemon on a fresh connection
+pub async fn send_to_daemon(request: DaemonRequest) -> Result<DaemonResponse> {
+    DaemonClient::connect().await?.request(&request).await
 }
 
 /// Check if daemon is running
@@ -260,7 +462,7 @@ pub async fn is_daemon_running() -> bool {
     // Try to connect and send health check
     matches!(
         send_to_daemon(DaemonRequest::Health).await,
-        Ok(DaemonResponse::Ok)
+        Ok(DaemonResponse::HealthInfo { .. })
     )
 }
 
@@ -277,3 +479,59 @@ pub async fn stop_daemon() -> Result<()> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::App;
+    use crate::migrations;
+
+    async fn item_orders(db: &SqlitePool) -> Vec<(String, i64)> {
+        sqlx::query_as("SELECT description, item_order FROM tasks ORDER BY item_order, id")
+            .fetch_all(db)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn tui_and_daemon_inserts_get_unique_orders() {
+        let db = migrations::memory_pool().await;
+        let mut app = App::new(db.clone()).await;
+        let nlp = app.nlp_parser_ref();
+
+        app.add_task("buy milk").await.unwrap();
+        add_task_to_db(&db, &nlp, "water plants").await.unwrap();
+        app.load_tasks().await.unwrap();
+
+        // TUI inserts at the top and after the selection shift later rows down
+        app.selected = 0;
+        app.add_task("read mail").await.unwrap();
+        app.selected = 1;
+        app.add_task("fold laundry").await.unwrap();
+
+        let (a, b) = tokio::join!(
+            add_task_to_db(&db, &nlp, "pay rent"),
+            add_task_to_db(&db, &nlp, "call the bank"),
+        );
+        a.unwrap();
+        b.unwrap();
+
+        let orders = item_orders(&db).await;
+        let values: Vec<i64> = orders.iter().map(|(_, order)| *order).collect();
+        assert!(
+            values.windows(2).all(|pair| pair[0] < pair[1]),
+            "duplicate or unordered item_order: {:?}",
+            orders
+        );
+
+        let descriptions: Vec<&str> = orders.iter().map(|(d, _)| d.as_str()).collect();
+        assert_eq!(
+            &descriptions[..4],
+            ["read mail", "buy milk", "fold laundry", "water plants"]
+        );
+        // Daemon inserts always land at the end
+        let mut tail = descriptions[4..].to_vec();
+        tail.sort();
+        assert_eq!(tail, ["call the bank", "pay rent"]);
+    }
+}
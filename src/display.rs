@@ -0,0 +1,332 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
+use std::ops::Range;
+
+use crate::theme::Theme;
+
+const DEFAULT_DATE_FORMAT: &str = "%m/%d";
+const DEFAULT_TIME_FORMAT: &str = "%l:%M%P";
+const DEFAULT_HOUR_FORMAT: &str = "%I%p";
+/// Hours the calendar grid shows by default: 7am up to 11pm
+const DEFAULT_CALENDAR_HOURS: Range<u32> = 7..23;
+/// Slot lengths the week view can be divided into
+const CALENDAR_SLOT_MINUTES: [u32; 3] = [60, 30, 15];
+
+/// Which day a scheduled task falls on, for the list badges
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DayLabel {
+    Today,
+    Tomorrow,
+    /// Any other day, by calendar date
+    Date(NaiveDate),
+}
+
+/// Date/time display formats shared by the CLI and TUI
+#[derive(Debug, Clone)]
+pub struct DisplayConfig {
+    /// strftime format for dates, e.g. "%m/%d" or "%d/%m"
+    pub date_format: String,
+    /// strftime format for times, e.g. "%l:%M%P" or "%H:%M"
+    pub time_format: String,
+    /// strftime format for calendar hour labels, e.g. "%I%p" or "%H:00"
+    pub hour_format: String,
+    /// Hour at which a new day begins for "today" purposes (0 = midnight)
+    pub day_start_hour: u32,
+    /// Hour rows shown in the week and day views; the end is exclusive, so
+    /// 6..24 runs from 6am through the 11pm row
+    pub calendar_hours: Range<u32>,
+    /// Length of a week-view row and of a j/k step: 60, 30 or 15 minutes
+    pub calendar_slot_minutes: u32,
+    /// Colors for priorities, block types and feedback
+    pub theme: Theme,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            date_format: DEFAULT_DATE_FORMAT.to_string(),
+            time_format: DEFAULT_TIME_FORMAT.to_string(),
+            hour_format: DEFAULT_HOUR_FORMAT.to_string(),
+            day_start_hour: 0,
+            calendar_hours: DEFAULT_CALENDAR_HOURS,
+            calendar_slot_minutes: 60,
+            theme: Theme::default(),
+        }
+    }
+}
+
+impl DisplayConfig {
+    /// Read formats from TRIPTYCH_DATE_FORMAT, TRIPTYCH_TIME_FORMAT and
+    /// TRIPTYCH_HOUR_FORMAT, falling back to the US-style defaults, the day
+    /// rollover hour from TRIPTYCH_DAY_START_HOUR, the calendar's hours from
+    /// TRIPTYCH_CALENDAR_START_HOUR and TRIPTYCH_CALENDAR_END_HOUR, its slot
+    /// length from TRIPTYCH_CALENDAR_SLOT_MINUTES and the palette from
+    /// TRIPTYCH_PALETTE
+    pub fn from_env() -> Self {
+        Self {
+            date_format: format_from_env("TRIPTYCH_DATE_FORMAT", DEFAULT_DATE_FORMAT),
+            time_format: format_from_env("TRIPTYCH_TIME_FORMAT", DEFAULT_TIME_FORMAT),
+            hour_format: format_from_env("TRIPTYCH_HOUR_FORMAT", DEFAULT_HOUR_FORMAT),
+            day_start_hour: day_start_hour_from_env(),
+            calendar_hours: calendar_hours_from_env(),
+            calendar_slot_minutes: calendar_slot_minutes_from_env(),
+            theme: Theme::from_env(),
+        }
+    }
+
+    /// The day a moment belongs to once the rollover hour is applied, so with
+    /// a 4am day start, 1am still counts as the previous day
+    pub fn logical_date(&self, dt: NaiveDateTime) -> NaiveDate {
+        (dt - Duration::hours(self.day_start_hour as i64)).date()
+    }
+
+    /// Badge for a task scheduled at `scheduled`, seen at `now`. Both are
+    /// local wall-clock times (what `scheduled_at` stores and
+    /// `timezone::now().naive_local()` returns), so a 9pm task is "today" at 8pm
+    /// wherever the clock's UTC date has already moved on.
+    pub fn day_label(&self, scheduled: NaiveDateTime, now: NaiveDateTime) -> DayLabel {
+        let today = self.logical_date(now);
+        match self.logical_date(scheduled) {
+            day if day == today => DayLabel::Today,
+            day if day == today + Duration::days(1) => DayLabel::Tomorrow,
+            _ => DayLabel::Date(scheduled.date()),
+        }
+    }
+
+    /// How many slots the calendar's hours divide into
+    pub fn calendar_slot_count(&self) -> usize {
+        self.calendar_hours.len() * 60 / self.calendar_slot_minutes as usize
+    }
+
+    /// Start time of the calendar slot at `index`
+    pub fn calendar_slot_time(&self, index: usize) -> NaiveTime {
+        let minutes = self.calendar_hours.start * 60 + index as u32 * self.calendar_slot_minutes;
+        NaiveTime::from_hms_opt(minutes / 60, minutes % 60, 0).unwrap()
+    }
+
+    /// Short date, e.g. "03/14"
+    pub fn format_date(&self, date: NaiveDate) -> String {
+        date.format(&self.date_format).to_string()
+    }
+
+    /// Short date with weekday, e.g. "Fri 03/14"
+    pub fn format_day(&self, date: NaiveDate) -> String {
+        format!("{} {}", date.format("%a"), self.format_date(date))
+    }
+
+    /// Time of day, e.g. "3:00pm"
+    pub fn format_time(&self, time: NaiveTime) -> String {
+        time.format(&self.time_format)
+            .to_string()
+            .trim()
+            .to_lowercase()
+    }
+
+    /// Calendar row label, e.g. "03pm"
+    pub fn format_hour(&self, time: NaiveTime) -> String {
+        time.format(&self.hour_format)
+            .to_string()
+            .trim()
+            .to_lowercase()
+    }
+}
+
+fn format_from_env(var: &str, default: &str) -> String {
+    match std::env::var(var) {
+        Ok(fmt) if is_valid_format(&fmt) => fmt,
+        Ok(fmt) => {
+            tracing::warn!("⚠️  Ignoring invalid {}: {:?}", var, fmt);
+            default.to_string()
+        }
+        Err(_) => default.to_string(),
+    }
+}
+
+fn day_start_hour_from_env() -> u32 {
+    match std::env::var("TRIPTYCH_DAY_START_HOUR") {
+        Ok(value) => match value.parse::<u32>() {
+            Ok(hour) if hour < 24 => hour,
+            _ => {
+                tracing::warn!("⚠️  Ignoring invalid TRIPTYCH_DAY_START_HOUR: {:?}", value);
+                0
+            }
+        },
+        Err(_) => 0,
+    }
+}
+
+fn calendar_hours_from_env() -> Range<u32> {
+    let hour = |var: &str, default: u32| match std::env::var(var) {
+        Ok(value) => match value.parse::<u32>() {
+            Ok(hour) if hour <= 24 => Some(hour),
+            _ => {
+                tracing::warn!("⚠️  Ignoring invalid {}: {:?}", var, value);
+                None
+            }
+        },
+        Err(_) => Some(default),
+    };
+
+    let start = hour("TRIPTYCH_CALENDAR_START_HOUR", DEFAULT_CALENDAR_HOURS.start);
+    let end = hour("TRIPTYCH_CALENDAR_END_HOUR", DEFAULT_CALENDAR_HOURS.end);
+    match (start, end) {
+        (Some(start), Some(end)) if start < end => start..end,
+        (Some(start), Some(end)) => {
+            tracing::warn!(
+                "⚠️  Ignoring calendar hours {}-{}: the start must come before the end",
+                start,
+                end
+            );
+            DEFAULT_CALENDAR_HOURS
+        }
+        _ => DEFAULT_CALENDAR_HOURS,
+    }
+}
+
+fn calendar_slot_minutes_from_env() -> u32 {
+    match std::env::var("TRIPTYCH_CALENDAR_SLOT_MINUTES") {
+        Ok(value) => match value.parse::<u32>() {
+            Ok(minutes) if CALENDAR_SLOT_MINUTES.contains(&minutes) => minutes,
+            _ => {
+                tracing::warn!(
+                    "⚠️  Ignoring invalid TRIPTYCH_CALENDAR_SLOT_MINUTES: {:?} (use 60, 30 or 15)",
+                    value
+                );
+                60
+            }
+        },
+        Err(_) => 60,
+    }
+}
+
+/// chrono panics when displaying a malformed format string, so reject those up front
+fn is_valid_format(fmt: &str) -> bool {
+    !fmt.is_empty() && !StrftimeItems::new(fmt).any(|item| matches!(item, Item::Error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(y: i32, m: u32, d: u32, h: u32, min: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn day_label_flips_at_midnight() {
+        let display = DisplayConfig::default();
+        let task = at(2026, 10, 17, 0, 1);
+
+        assert_eq!(
+            display.day_label(task, at(2026, 10, 16, 23, 59)),
+            DayLabel::Tomorrow
+        );
+        assert_eq!(
+            display.day_label(task, at(2026, 10, 17, 0, 1)),
+            DayLabel::Today
+        );
+        assert_eq!(
+            display.day_label(at(2026, 10, 16, 23, 59), at(2026, 10, 17, 0, 1)),
+            DayLabel::Date(NaiveDate::from_ymd_opt(2026, 10, 16).unwrap())
+        );
+    }
+
+    #[test]
+    fn day_label_follows_day_start_hour() {
+        let display = DisplayConfig {
+            day_start_hour: 4,
+            ..DisplayConfig::default()
+        };
+        let now = at(2026, 10, 17, 1, 0);
+
+        // At 1am the logical day is still the 16th
+        assert_eq!(
+            display.day_label(at(2026, 10, 16, 22, 0), now),
+            DayLabel::Today
+        );
+        assert_eq!(
+            display.day_label(at(2026, 10, 17, 3, 59), now),
+            DayLabel::Today
+        );
+        assert_eq!(
+            display.day_label(at(2026, 10, 17, 4, 0), now),
+            DayLabel::Tomorrow
+        );
+    }
+
+    #[test]
+    fn day_label_uses_dates_beyond_tomorrow() {
+        let display = DisplayConfig::default();
+        let now = at(2026, 10, 16, 12, 0);
+
+        assert_eq!(
+            display.day_label(at(2026, 10, 16, 9, 0), now),
+            DayLabel::Today
+        );
+        assert_eq!(
+            display.day_label(at(2026, 10, 17, 9, 0), now),
+            DayLabel::Tomorrow
+        );
+        assert_eq!(
+            display.day_label(at(2026, 10, 18, 9, 0), now),
+            DayLabel::Date(NaiveDate::from_ymd_opt(2026, 10, 18).unwrap())
+        );
+    }
+
+    #[test]
+    fn formats_follow_the_configured_strings() {
+        let moment = at(2026, 3, 14, 15, 5);
+        let us = DisplayConfig::default();
+        let european = DisplayConfig {
+            date_format: "%d/%m".to_string(),
+            time_format: "%H:%M".to_string(),
+            hour_format: "%H:00".to_string(),
+            ..DisplayConfig::default()
+        };
+
+        assert_eq!(us.format_date(moment.date()), "03/14");
+        assert_eq!(us.format_day(moment.date()), "Sat 03/14");
+        assert_eq!(us.format_time(moment.time()), "3:05pm");
+        assert_eq!(us.format_hour(moment.time()), "03pm");
+
+        assert_eq!(european.format_date(moment.date()), "14/03");
+        assert_eq!(european.format_day(moment.date()), "Sat 14/03");
+        assert_eq!(european.format_time(moment.time()), "15:05");
+        assert_eq!(european.format_hour(moment.time()), "15:00");
+    }
+
+    #[test]
+    fn malformed_formats_are_rejected() {
+        assert!(is_valid_format("%d/%m"));
+        assert!(is_valid_format("%l:%M%P"));
+        assert!(!is_valid_format(""));
+        assert!(!is_valid_format("%Q"));
+        assert!(!is_valid_format("%"));
+    }
+
+    #[test]
+    fn logical_date_rolls_over_at_the_day_start_hour() {
+        let display = DisplayConfig {
+            day_start_hour: 4,
+            ..DisplayConfig::default()
+        };
+        let day = |d| NaiveDate::from_ymd_opt(2026, 10, d).unwrap();
+
+        assert_eq!(display.logical_date(at(2026, 10, 17, 0, 0)), day(16));
+        assert_eq!(display.logical_date(at(2026, 10, 17, 3, 59)), day(16));
+        assert_eq!(display.logical_date(at(2026, 10, 17, 4, 0)), day(17));
+        assert_eq!(display.logical_date(at(2026, 10, 17, 23, 59)), day(17));
+    }
+
+    #[test]
+    fn logical_date_is_the_calendar_date_with_midnight_start() {
+        let display = DisplayConfig::default();
+        let day = |d| NaiveDate::from_ymd_opt(2026, 10, d).unwrap();
+
+        assert_eq!(display.logical_date(at(2026, 10, 16, 23, 59)), day(16));
+        assert_eq!(display.logical_date(at(2026, 10, 17, 0, 0)), day(17));
+    }
+}
@@ -0,0 +1,42 @@
+//! Status and progress chatter on stderr ("✓ NLP parsing ready", daemon
+//! lifecycle, parser cache hits) goes through `tracing` so it can be turned
+//! down without touching command output. Command results and the errors that
+//! end a command still use `println!`/`eprintln!` directly.
+
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+/// Install the stderr logger. `--quiet` keeps only errors; otherwise the level
+/// comes from `TRIPTYCH_LOG` (off, error, warn, info, debug or trace) and
+/// defaults to info, which hides per-parse debug lines.
+pub fn init(quiet: bool) {
+    let from_env = std::env::var("TRIPTYCH_LOG").ok();
+    let level = match (quiet, from_env.as_deref().map(str::parse::<LevelFilter>)) {
+        (true, _) => LevelFilter::ERROR,
+        (false, Some(Ok(level))) => level,
+        (false, _) => LevelFilter::INFO,
+    };
+
+    // Messages already carry their own ✓/⚠️ markers, so print them bare.
+    // Dependencies (sqlx, reqwest) log every query and connection at debug,
+    // so only our own events get through.
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stderr)
+                .without_time()
+                .with_level(false)
+                .with_target(false)
+                .with_ansi(false),
+        )
+        .with(Targets::new().with_target(env!("CARGO_CRATE_NAME"), level))
+        .init();
+
+    if let (false, Some(value)) = (quiet, from_env)
+        && value.parse::<LevelFilter>().is_err()
+    {
+        tracing::warn!("⚠️  Ignoring invalid TRIPTYCH_LOG: {:?}", value);
+    }
+}
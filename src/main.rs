@@ -1,40 +1,78 @@
 mod app;
 mod cli;
 mod daemon;
+mod display;
+mod logging;
 mod nlp;
+mod paths;
+mod recurrence;
 mod sync;
+mod theme;
+mod timezone;
 mod ui;
 
-use crate::app::{BlockFormState, CalendarInputMode, InputMode, ViewMode};
-use crate::ui::ui;
+use crate::app::{
+    AgendaKind, BlockFormState, CalendarInputMode, DependencyChange, EnhancedTaskInfo, InputMode,
+    MoveDirection, ScheduleToml, SortMode, ViewMode, blocked_task_ids, filter_task_list,
+    subtask_depths,
+};
+use crate::display::{DayLabel, DisplayConfig};
+use crate::recurrence::RecurrenceRule;
+use crate::ui::{ScreenLayout, ui};
 mod migrations;
 use app::App;
 use clap::Parser;
-use cli::{Cli, Commands, ScheduleCommands};
+use cli::{Cli, Commands, ScheduleCommands, TagCommands};
 use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture, Event, EventStream, KeyCode},
+    event::{
+        DisableMouseCapture, EnableMouseCapture, Event, EventStream, KeyCode, KeyModifiers,
+        MouseButton, MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
 use daemon::{DaemonRequest, DaemonResponse};
 use futures::StreamExt;
-use migrations::run_calendar_migration;
+use migrations::SchemaVersion;
 use ratatui::{
     Terminal,
     backend::{Backend, CrosstermBackend},
 };
-use std::io;
-use sync::{SyncConfig, SyncDaemon};
+use std::io::{self, IsTerminal, Write};
+use sync::{AppEvent, SyncConfig, SyncDaemon};
 use tokio::signal;
+use tokio::sync::broadcast;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli_args = Cli::parse();
 
+    // JSON output is for scripts, which want nothing else on either stream
+    let json_output = matches!(
+        cli_args.command,
+        Some(
+            Commands::List { json: true, .. }
+                | Commands::Search { json: true, .. }
+                | Commands::DbPath { json: true }
+        )
+    );
+    logging::init(cli_args.quiet || json_output);
+
+    if !timezone::init(cli_args.tz.as_deref()) {
+        eprintln!(
+            "✗ Unknown timezone \"{}\" (expected an IANA name like Europe/Berlin, or UTC)",
+            cli_args.tz.unwrap_or_default()
+        );
+        std::process::exit(1);
+    }
+
     // Handle daemon commands first
     if let Some(Commands::Daemon) = &cli_args.command {
         let app = App::build().await?;
-        daemon::start_daemon(app.db_pool.clone(), app.nlp_parser_ref()).await?;
+        if let Err(e) = daemon::start_daemon(app.db_pool.clone(), app.nlp_parser_ref()).await {
+            eprintln!("✗ {:#}", e);
+            std::process::exit(1);
+        }
         return Ok(());
     }
 
@@ -44,11 +82,36 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     if let Some(Commands::Status) = &cli_args.command {
-        if daemon::is_daemon_running().await {
-            println!("✓ Daemon is running");
+        match daemon::send_to_daemon(DaemonRequest::Health).await {
+            Ok(DaemonResponse::HealthInfo {
+                pid,
+                uptime_secs,
+                cache_entries,
+            }) => {
+                println!("✓ Daemon is running");
+                println!("  PID:    {}", pid);
+                println!("  Uptime: {}", format_uptime(uptime_secs));
+                println!("  Cache:  {} entries", cache_entries);
+            }
+            _ => {
+                println!("✗ Daemon is not running");
+                println!("  Start with: triptych daemon");
+            }
+        }
+        return Ok(());
+    }
+
+    if cli_args.version {
+        print_version().await;
+        return Ok(());
+    }
+
+    if let Some(Commands::DbPath { json }) = &cli_args.command {
+        let path = paths::database_path();
+        if *json {
+            println!("{}", serde_json::json!({ "db_path": path }));
         } else {
-            println!("✗ Daemon is not running");
-            println!("  Start with: triptych daemon");
+            println!("{}", path.display());
         }
         return Ok(());
     }
@@ -56,22 +119,43 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Build app for other commands
     let mut app = App::build().await?;
 
-    if let Err(e) = run_calendar_migration(&app.db_pool).await {
-        eprintln!("⚠️  Calendar migration failed: {}", e);
-        eprintln!("   Calendar features will be disabled");
+    if let Some(name) = &cli_args.palette {
+        match theme::Palette::from_name(name) {
+            Some(palette) => app.display.theme.palette = palette,
+            None => {
+                eprintln!(
+                    "✗ Invalid palette \"{}\" (expected dark, light, high-contrast or colorblind)",
+                    name
+                );
+                std::process::exit(1);
+            }
+        }
     }
 
+    let _ = app.restore_sort_mode().await;
+
     // Check if a subcommand was provided
     if let Some(command) = cli_args.command {
         let result = handle_cli_command(&mut app, command).await;
         return result;
     }
 
+    // Seed the parser cache from a running daemon so the TUI starts warm
+    if daemon::is_daemon_running().await
+        && let Ok(DaemonResponse::CacheEntries(entries)) =
+            daemon::send_to_daemon(DaemonRequest::ExportCache).await
+    {
+        let seeded = app.nlp_parser_ref().import_cache(entries).await;
+        tracing::info!("✓ Seeded {} cache entries from daemon", seeded);
+    }
+
     // No subcommand - start the TUI (with sync daemon)
     // Start sync daemon BEFORE entering alternate screen so warmup messages print cleanly
     let sync_config = SyncConfig::from_env();
     let daemon = SyncDaemon::start(app.db_pool.clone(), app.nlp_parser_ref(), sync_config).await?;
+    app.sync_status = Some(daemon.status());
 
+    let _ = app.restore_selection().await;
     app.load_tasks().await?;
 
     // Now enter TUI mode
@@ -80,7 +164,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
-    let tui_result = run_app(&mut terminal, app).await;
+    let tui_result = run_app(&mut terminal, &mut app, daemon.subscribe()).await;
 
     disable_raw_mode()?;
     execute!(
@@ -90,6 +174,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     )?;
     terminal.show_cursor()?;
 
+    let _ = app.save_selection().await;
     daemon.shutdown().await?;
     tui_result?;
     Ok(())
@@ -100,7 +185,30 @@ async fn handle_cli_command(
     command: Commands,
 ) -> Result<(), Box<dyn std::error::Error>> {
     match command {
-        Commands::Add { description } => {
+        Commands::Add {
+            description,
+            parent: Some(parent_id),
+        } => match app.add_subtask(parent_id, &description).await {
+            Ok(true) => {
+                println!("✓ Added subtask of task {}: \"{}\"", parent_id, description);
+                if let Some(task) = app.tasks.get(app.selected) {
+                    print_conflicts(app, task.id).await;
+                }
+            }
+            Ok(false) => {
+                eprintln!("✗ Task {} not found", parent_id);
+                std::process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("✗ Error adding subtask: {}", e);
+                std::process::exit(1);
+            }
+        },
+
+        Commands::Add {
+            description,
+            parent: None,
+        } => {
             // Try daemon first for instant response
             if daemon::is_daemon_running().await {
                 match daemon::send_to_daemon(DaemonRequest::AddTask {
@@ -110,6 +218,7 @@ async fn handle_cli_command(
                 {
                     Ok(DaemonResponse::TaskAdded { id }) => {
                         println!("✓ Added task: \"{}\" (ID: {}, via daemon)", description, id);
+                        print_conflicts(app, id).await;
                         return Ok(());
                     }
                     Ok(DaemonResponse::Error(e)) => {
@@ -129,7 +238,12 @@ async fn handle_cli_command(
 
             // Fallback: direct execution
             match app.add_task(&description).await {
-                Ok(_) => println!("✓ Added task: \"{}\"", description),
+                Ok(_) => {
+                    println!("✓ Added task: \"{}\"", description);
+                    if let Some(task) = app.tasks.get(app.selected) {
+                        print_conflicts(app, task.id).await;
+                    }
+                }
                 Err(e) => {
                     eprintln!("✗ Error adding task: {}", e);
                     std::process::exit(1);
@@ -137,19 +251,157 @@ async fn handle_cli_command(
             }
         }
 
-        Commands::List => {
-            // List command logic (unchanged)
-            match app.get_enhanced_task_list().await {
+        Commands::Edit { id, description } => {
+            // Try daemon first for instant response
+            if daemon::is_daemon_running().await {
+                match daemon::send_to_daemon(DaemonRequest::EditTask {
+                    id,
+                    description: description.clone(),
+                })
+                .await
+                {
+                    Ok(DaemonResponse::TaskUpdated { id }) => {
+                        println!("✓ Updated task {}: \"{}\" (via daemon)", id, description);
+                        return Ok(());
+                    }
+                    Ok(DaemonResponse::NotFound { id }) => {
+                        eprintln!("✗ Task with ID {} not found", id);
+                        std::process::exit(1);
+                    }
+                    Ok(DaemonResponse::Error(e)) => {
+                        eprintln!("⚠️  Daemon error: {}", e);
+                        eprintln!("   Falling back to direct mode...");
+                    }
+                    Err(e) => {
+                        eprintln!("⚠️  Daemon communication error: {}", e);
+                        eprintln!("   Falling back to direct mode...");
+                    }
+                    _ => {
+                        eprintln!("⚠️  Unexpected daemon response");
+                        eprintln!("   Falling back to direct mode...");
+                    }
+                }
+            }
+
+            // Fallback: direct execution
+            match app.update_task_text(id, &description).await {
+                Ok(true) => println!("✓ Updated task {}: \"{}\"", id, description),
+                Ok(false) => {
+                    eprintln!("✗ Task with ID {} not found", id);
+                    std::process::exit(1);
+                }
+                Err(e) => {
+                    eprintln!("✗ Error updating task: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        Commands::List {
+            tags,
+            priority,
+            json,
+            verbose,
+            sort,
+        } => {
+            if let Some(name) = sort {
+                match SortMode::from_name(&name) {
+                    Some(mode) => app.sort_mode = mode,
+                    None => {
+                        eprintln!(
+                            "✗ Invalid sort \"{}\" (expected manual, priority, due or alpha)",
+                            name
+                        );
+                        std::process::exit(1);
+                    }
+                }
+            }
+
+            let priority = match priority {
+                Some(level) => match crate::nlp::Priority::from_name(&level) {
+                    Some(p) => Some(p.to_level()),
+                    None => {
+                        eprintln!(
+                            "✗ Invalid priority \"{}\" (expected low, medium, high or urgent)",
+                            level
+                        );
+                        std::process::exit(1);
+                    }
+                },
+                None => None,
+            };
+            let filtered = !tags.is_empty() || priority.is_some();
+
+            // Try daemon first for instant response
+            let mut from_daemon = None;
+            if daemon::is_daemon_running().await {
+                match daemon::send_to_daemon(DaemonRequest::ListTasks {
+                    sort: app.sort_mode,
+                })
+                .await
+                {
+                    Ok(DaemonResponse::Tasks(list)) => from_daemon = Some(list),
+                    Ok(DaemonResponse::Error(e)) => {
+                        eprintln!("⚠️  Daemon error: {}", e);
+                        eprintln!("   Falling back to direct mode...");
+                    }
+                    Err(e) => {
+                        eprintln!("⚠️  Daemon communication error: {}", e);
+                        eprintln!("   Falling back to direct mode...");
+                    }
+                    _ => {
+                        eprintln!("⚠️  Unexpected daemon response");
+                        eprintln!("   Falling back to direct mode...");
+                    }
+                }
+            }
+
+            // Fallback: direct execution
+            let task_list = match from_daemon {
+                Some(list) => Ok(filter_task_list(list, &tags, priority)),
+                None => app.get_filtered_task_list(&tags, priority).await,
+            };
+
+            let blocked = blocked_task_ids(&app.db_pool).await.unwrap_or_default();
+
+            match task_list {
+                Ok(enhanced_tasks) if json => {
+                    println!("{}", serde_json::to_string_pretty(&enhanced_tasks)?);
+                }
                 Ok(enhanced_tasks) => {
-                    if enhanced_tasks.is_empty() {
+                    if enhanced_tasks.is_empty() && filtered {
+                        println!("🔍 No tasks match the given filters");
+                    } else if enhanced_tasks.is_empty() {
                         println!("📝 No tasks yet! Add one with: triptych add \"Your task\"");
                     } else {
                         println!("📋 Current Tasks:");
+                        let depths = subtask_depths(enhanced_tasks.iter().map(|e| &e.task));
                         for enhanced in &enhanced_tasks {
+                            let indent =
+                                "  ".repeat(depths.get(&enhanced.task.id).copied().unwrap_or(0));
+                            if verbose {
+                                let is_blocked =
+                                    !enhanced.task.completed && blocked.contains(&enhanced.task.id);
+                                print!(
+                                    "{}",
+                                    format_task_verbose(
+                                        &app.display,
+                                        enhanced,
+                                        &indent,
+                                        is_blocked
+                                    )
+                                );
+                                continue;
+                            }
+
                             let task = &enhanced.task;
                             let status = if task.completed { "✓" } else { "○" };
                             let mut indicators = Vec::new();
 
+                            if !task.completed && blocked.contains(&task.id) {
+                                indicators.push("🔒".to_string());
+                            }
+
                             match task.priority {
                                 3 => indicators.push("[HIGH]".to_string()),
                                 2 => indicators.push("[MED]".to_string()),
@@ -158,19 +410,16 @@ async fn handle_cli_command(
                             }
 
                             if let Some(scheduled) = task.scheduled_at {
-                                let now = chrono::Utc::now();
-                                let scheduled_date = scheduled.date_naive();
-                                let today = now.date_naive();
-                                let tomorrow = today + chrono::Duration::days(1);
-
-                                let date_text = if scheduled_date == today {
-                                    "[TODAY]"
-                                } else if scheduled_date == tomorrow {
-                                    "[TOMORROW]"
-                                } else {
-                                    &format!("[{}]", scheduled.format("%m/%d"))
-                                };
-                                indicators.push(date_text.to_string());
+                                let now = timezone::now().naive_local();
+                                let date_text =
+                                    match app.display.day_label(scheduled.naive_utc(), now) {
+                                        DayLabel::Today => "[TODAY]".to_string(),
+                                        DayLabel::Tomorrow => "[TOMORROW]".to_string(),
+                                        DayLabel::Date(date) => {
+                                            format!("[{}]", app.display.format_date(date))
+                                        }
+                                    };
+                                indicators.push(date_text);
                             }
 
                             let indicators_str = if indicators.is_empty() {
@@ -179,11 +428,17 @@ async fn handle_cli_command(
                                 format!("{} ", indicators.join(" "))
                             };
 
-                            let tags_display = if !enhanced.tags.is_empty() {
+                            let mut suffix = if !enhanced.tags.is_empty() {
                                 format!(" #{}", enhanced.tags.join(" #"))
                             } else {
                                 String::new()
                             };
+                            if task.notes.is_some() {
+                                suffix.push_str(" 📝");
+                            }
+                            if task.recurrence_rule.is_some() {
+                                suffix.push_str(" 🔁");
+                            }
 
                             let description = if task.completed {
                                 format!("\x1b[9m{}\x1b[0m", task.description)
@@ -191,76 +446,680 @@ async fn handle_cli_command(
                                 task.description.clone()
                             };
 
-                            println!(
-                                "  {} {}{} (ID: {}){}",
-                                status, indicators_str, description, task.id, tags_display
-                            );
+                            println!(
+                                "  {}{} {}{} (ID: {}){}",
+                                indent, status, indicators_str, description, task.id, suffix
+                            );
+                        }
+                    }
+                }
+                Err(e) => {
+                    eprintln!("✗ Error loading tasks: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        Commands::Search { query, json } => match app.search(&query).await {
+            Ok(results) if json => {
+                println!("{}", serde_json::to_string_pretty(&results)?);
+            }
+            Ok(results) if results.is_empty() => {
+                println!("🔍 Nothing matches \"{}\"", query);
+            }
+            Ok(results) => {
+                println!("🔍 Matches for \"{}\":", query);
+                for result in &results {
+                    let status = if result.completed { "✓" } else { "○" };
+                    let date = result
+                        .at
+                        .map(|at| format!("[{}] ", app.display.format_date(at.date_naive())))
+                        .unwrap_or_default();
+                    println!(
+                        "  {} {:<5} {}{} (ID: {})",
+                        status,
+                        result.kind.name(),
+                        date,
+                        result.title,
+                        result.id
+                    );
+                }
+            }
+            Err(e) => {
+                eprintln!("✗ Error searching: {}", e);
+                std::process::exit(1);
+            }
+        },
+
+        Commands::Done { id } => {
+            // Read before completing, while the blockers are still open
+            let blockers = app.open_blockers(id).await.unwrap_or_default();
+
+            // Try daemon first for instant response
+            if daemon::is_daemon_running().await {
+                match daemon::send_to_daemon(DaemonRequest::CompleteTask { id }).await {
+                    Ok(DaemonResponse::TaskCompleted { id }) => {
+                        print_completed(app, id, &blockers).await;
+                        return Ok(());
+                    }
+                    Ok(DaemonResponse::NotFound { id }) => {
+                        eprintln!("✗ Task with ID {} not found", id);
+                        std::process::exit(1);
+                    }
+                    Ok(DaemonResponse::Error(e)) => {
+                        eprintln!("⚠️  Daemon error: {}", e);
+                        eprintln!("   Falling back to direct mode...");
+                    }
+                    Err(e) => {
+                        eprintln!("⚠️  Daemon communication error: {}", e);
+                        eprintln!("   Falling back to direct mode...");
+                    }
+                    _ => {
+                        eprintln!("⚠️  Unexpected daemon response");
+                        eprintln!("   Falling back to direct mode...");
+                    }
+                }
+            }
+
+            // Fallback: direct execution
+            match app.complete_task_by_id(id).await {
+                Ok(true) => print_completed(app, id, &blockers).await,
+                Ok(false) => {
+                    eprintln!("✗ Task with ID {} not found", id);
+                    std::process::exit(1);
+                }
+                Err(e) => {
+                    eprintln!("✗ Error completing task: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        Commands::Notes {
+            id,
+            text: Some(text),
+        } => match app.set_notes(id, &text).await {
+            Ok(true) if text.trim().is_empty() => println!("✓ Cleared notes on task {}", id),
+            Ok(true) => println!("✓ Updated notes on task {}", id),
+            Ok(false) => {
+                eprintln!("✗ Task with ID {} not found", id);
+                std::process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("✗ Error saving notes: {}", e);
+                std::process::exit(1);
+            }
+        },
+
+        Commands::Notes { id, text: None } => match app.get_task_by_id(id).await {
+            Ok(Some(task)) => match task.notes {
+                Some(notes) => println!("{}", notes),
+                None => println!("📝 No notes on \"{}\"", task.description),
+            },
+            Ok(None) => {
+                eprintln!("✗ Task with ID {} not found", id);
+                std::process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("✗ Error loading task: {}", e);
+                std::process::exit(1);
+            }
+        },
+
+        Commands::Repeat {
+            id,
+            rule: Some(rule),
+        } if rule.trim().is_empty() => match app.set_recurrence(id, None).await {
+            Ok(true) => println!("✓ Task {} no longer repeats", id),
+            Ok(false) => {
+                eprintln!("✗ Task with ID {} not found", id);
+                std::process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("✗ Error updating task: {}", e);
+                std::process::exit(1);
+            }
+        },
+
+        Commands::Repeat {
+            id,
+            rule: Some(rule),
+        } => {
+            let rule = rule.trim();
+            match app.get_task_by_id(id).await {
+                Ok(Some(task)) if task.scheduled_at.is_none() => {
+                    eprintln!(
+                        "✗ \"{}\" has no date to repeat from; reschedule it first",
+                        task.description
+                    );
+                    std::process::exit(1);
+                }
+                Ok(Some(_)) => {}
+                Ok(None) => {
+                    eprintln!("✗ Task with ID {} not found", id);
+                    std::process::exit(1);
+                }
+                Err(e) => {
+                    eprintln!("✗ Error loading task: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            match RecurrenceRule::parse(rule) {
+                None => {
+                    eprintln!(
+                        "✗ Invalid repeat rule \"{}\" (e.g. \"FREQ=WEEKLY;BYDAY=MO,WE\")",
+                        rule
+                    );
+                    std::process::exit(1);
+                }
+                Some(parsed) if parsed.has_count() => {
+                    eprintln!("✗ COUNT isn't supported for tasks; end the series with UNTIL");
+                    std::process::exit(1);
+                }
+                Some(_) => {}
+            }
+            match app.set_recurrence(id, Some(rule)).await {
+                Ok(_) => println!("🔁 Task {} repeats: {}", id, rule),
+                Err(e) => {
+                    eprintln!("✗ Error updating task: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        Commands::Repeat { id, rule: None } => match app.get_task_by_id(id).await {
+            Ok(Some(task)) => match task.recurrence_rule {
+                Some(rule) => println!("🔁 {}", rule),
+                None => println!("🔁 \"{}\" doesn't repeat", task.description),
+            },
+            Ok(None) => {
+                eprintln!("✗ Task with ID {} not found", id);
+                std::process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("✗ Error loading task: {}", e);
+                std::process::exit(1);
+            }
+        },
+
+        Commands::Depend { id, on } => match app.add_dependency(id, on).await {
+            Ok(DependencyChange::Added) => {
+                println!("🔒 Task {} is now blocked until task {} is done", id, on)
+            }
+            Ok(DependencyChange::Unchanged) => {
+                println!("Task {} already depends on task {}", id, on)
+            }
+            Ok(DependencyChange::NotFound(missing)) => {
+                eprintln!("✗ Task with ID {} not found", missing);
+                std::process::exit(1);
+            }
+            Ok(DependencyChange::Cycle(path)) if path.len() == 1 => {
+                eprintln!("✗ A task can't depend on itself");
+                std::process::exit(1);
+            }
+            Ok(DependencyChange::Cycle(path)) => {
+                let chain: Vec<String> = path.iter().map(|id| id.to_string()).collect();
+                eprintln!(
+                    "✗ That would create a cycle: {} already depends on {} ({})",
+                    on,
+                    id,
+                    chain.join(" → ")
+                );
+                std::process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("✗ Error adding dependency: {}", e);
+                std::process::exit(1);
+            }
+        },
+
+        Commands::Undepend { id, on } => match app.remove_dependency(id, on).await {
+            Ok(true) => println!("✓ Task {} no longer depends on task {}", id, on),
+            Ok(false) => {
+                eprintln!("✗ Task {} doesn't depend on task {}", id, on);
+                std::process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("✗ Error removing dependency: {}", e);
+                std::process::exit(1);
+            }
+        },
+
+        Commands::Priority { id, level } => {
+            let Some(priority) = crate::nlp::Priority::from_name(&level) else {
+                eprintln!(
+                    "✗ Invalid priority \"{}\" (expected low, medium, high or urgent)",
+                    level
+                );
+                std::process::exit(1);
+            };
+
+            match app.set_priority(id, priority.to_level()).await {
+                Ok(true) => println!("✓ Set task {} priority to {}", id, level.to_lowercase()),
+                Ok(false) => {
+                    eprintln!("✗ Task with ID {} not found", id);
+                    std::process::exit(1);
+                }
+                Err(e) => {
+                    eprintln!("✗ Error setting priority: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        Commands::Rm { id } => {
+            // Try daemon first for instant response
+            if daemon::is_daemon_running().await {
+                match daemon::send_to_daemon(DaemonRequest::RemoveTask { id }).await {
+                    Ok(DaemonResponse::TaskRemoved { id }) => {
+                        println!("✓ Moved task {} to the trash (via daemon)", id);
+                        return Ok(());
+                    }
+                    Ok(DaemonResponse::NotFound { id }) => {
+                        eprintln!("✗ Task with ID {} not found", id);
+                        std::process::exit(1);
+                    }
+                    Ok(DaemonResponse::Error(e)) => {
+                        eprintln!("⚠️  Daemon error: {}", e);
+                        eprintln!("   Falling back to direct mode...");
+                    }
+                    Err(e) => {
+                        eprintln!("⚠️  Daemon communication error: {}", e);
+                        eprintln!("   Falling back to direct mode...");
+                    }
+                    _ => {
+                        eprintln!("⚠️  Unexpected daemon response");
+                        eprintln!("   Falling back to direct mode...");
+                    }
+                }
+            }
+
+            // Fallback: direct execution
+            match app.remove_task_by_id(id).await {
+                Ok(true) => println!("✓ Moved task {} to the trash", id),
+                Ok(false) => {
+                    eprintln!("✗ Task with ID {} not found", id);
+                    std::process::exit(1);
+                }
+                Err(e) => {
+                    eprintln!("✗ Error removing task: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        Commands::Clear { yes } => {
+            if !yes {
+                let count = match app.completed_task_count().await {
+                    Ok(count) => count,
+                    Err(e) => {
+                        eprintln!("✗ Error counting completed tasks: {}", e);
+                        std::process::exit(1);
+                    }
+                };
+                if count == 0 {
+                    print_cleared(0);
+                    return Ok(());
+                }
+                if !std::io::stdin().is_terminal() {
+                    eprintln!(
+                        "✗ Refusing to clear {} completed task{} without confirmation (pass --yes)",
+                        count,
+                        if count == 1 { "" } else { "s" }
+                    );
+                    std::process::exit(1);
+                }
+                if !confirm(&format!(
+                    "Move {} completed task{} to the trash?",
+                    count,
+                    if count == 1 { "" } else { "s" }
+                ))? {
+                    println!("Cancelled");
+                    return Ok(());
+                }
+            }
+
+            // Try daemon first for instant response
+            if daemon::is_daemon_running().await {
+                match daemon::send_to_daemon(DaemonRequest::ClearCompleted).await {
+                    Ok(DaemonResponse::Cleared { count }) => {
+                        print_cleared(count);
+                        return Ok(());
+                    }
+                    Ok(DaemonResponse::Error(e)) => {
+                        eprintln!("⚠️  Daemon error: {}", e);
+                        eprintln!("   Falling back to direct mode...");
+                    }
+                    Err(e) => {
+                        eprintln!("⚠️  Daemon communication error: {}", e);
+                        eprintln!("   Falling back to direct mode...");
+                    }
+                    _ => {
+                        eprintln!("⚠️  Unexpected daemon response");
+                        eprintln!("   Falling back to direct mode...");
+                    }
+                }
+            }
+
+            // Fallback: direct execution
+            match app.clear_completed_tasks().await {
+                Ok(count) => print_cleared(count),
+                Err(e) => {
+                    eprintln!("✗ Error clearing completed tasks: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        Commands::Trash => match app.trashed_tasks().await {
+            Ok(tasks) if tasks.is_empty() => println!("🗑  The trash is empty"),
+            Ok(tasks) => {
+                println!("🗑  Trash:");
+                for task in &tasks {
+                    let deleted = task
+                        .deleted_at
+                        .map(|at| {
+                            format!(
+                                "{} {}",
+                                app.display.format_date(at.date_naive()),
+                                app.display.format_time(at.time())
+                            )
+                        })
+                        .unwrap_or_default();
+                    println!(
+                        "  {} {} (ID: {}) deleted {}",
+                        if task.completed { "✓" } else { "○" },
+                        task.description,
+                        task.id,
+                        deleted
+                    );
+                }
+            }
+            Err(e) => {
+                eprintln!("✗ Error loading the trash: {}", e);
+                std::process::exit(1);
+            }
+        },
+
+        Commands::Restore { id } => match app.restore_task_by_id(id).await {
+            Ok(true) => println!("✓ Restored task {}", id),
+            Ok(false) => {
+                eprintln!("✗ Task with ID {} is not in the trash", id);
+                std::process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("✗ Error restoring task: {}", e);
+                std::process::exit(1);
+            }
+        },
+
+        Commands::Convert { id } => {
+            match app.get_task_by_id(id).await {
+                Ok(Some(task)) if task.scheduled_at.is_none() => {
+                    eprintln!(
+                        "✗ \"{}\" has no date to make an event from; reschedule it first",
+                        task.description
+                    );
+                    std::process::exit(1);
+                }
+                Ok(Some(_)) => {}
+                Ok(None) => {
+                    eprintln!("✗ Task with ID {} not found", id);
+                    std::process::exit(1);
+                }
+                Err(e) => {
+                    eprintln!("✗ Error loading task: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            match app.convert_task_to_event(id).await {
+                Ok(Some(event_id)) => {
+                    println!(
+                        "◆ Converted task {} to event {} (the task is in the trash)",
+                        id, event_id
+                    )
+                }
+                Ok(None) => {
+                    eprintln!("✗ Task with ID {} not found", id);
+                    std::process::exit(1);
+                }
+                Err(e) => {
+                    eprintln!("✗ Error converting task: {}", e);
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        Commands::EmptyTrash => match app.empty_trash().await {
+            Ok(0) => println!("🗑  The trash is already empty"),
+            Ok(count) => println!(
+                "🗑  Permanently deleted {} task{}",
+                count,
+                if count == 1 { "" } else { "s" }
+            ),
+            Err(e) => {
+                eprintln!("✗ Error emptying the trash: {}", e);
+                std::process::exit(1);
+            }
+        },
+
+        Commands::Rollover { at, dry_run } => {
+            let Ok(target_time) = chrono::NaiveTime::parse_from_str(&at, "%H:%M") else {
+                eprintln!("✗ Invalid time \"{}\" (expected HH:MM)", at);
+                std::process::exit(1);
+            };
+
+            if dry_run {
+                match app.rollover_candidates().await {
+                    Ok(tasks) if tasks.is_empty() => println!("🌙 Nothing to roll over"),
+                    Ok(tasks) => {
+                        println!("Would move to tomorrow at {}:", at);
+                        for task in &tasks {
+                            println!("  ○ {} (ID: {})", task.description, task.id);
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("✗ Error loading tasks: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            } else {
+                match app.rollover_to_tomorrow(target_time).await {
+                    Ok(0) => println!("🌙 Nothing to roll over"),
+                    Ok(count) => println!(
+                        "🌙 Moved {} task{} to tomorrow at {}",
+                        count,
+                        if count == 1 { "" } else { "s" },
+                        at
+                    ),
+                    Err(e) => {
+                        eprintln!("✗ Error rolling over tasks: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            }
+        }
+
+        Commands::Today => {
+            let today = app.display.logical_date(timezone::now().naive_local());
+            match app.agenda(today).await {
+                Ok(agenda) => {
+                    println!("📅 {}", today.format("%A, %b %-d"));
+                    if agenda.timed.is_empty() && agenda.anytime.is_empty() {
+                        println!("  Nothing planned");
+                    }
+                    for entry in &agenda.timed {
+                        let title = match entry.kind {
+                            AgendaKind::Block => entry.title.clone(),
+                            AgendaKind::Event => format!("◆ {}", entry.title),
+                            AgendaKind::Task => format!("● {}", entry.title),
+                        };
+                        match entry.end {
+                            Some(end) => println!(
+                                "  {:>7}  {} (until {})",
+                                app.display.format_time(entry.start),
+                                title,
+                                app.display.format_time(end)
+                            ),
+                            None => {
+                                println!("  {:>7}  {}", app.display.format_time(entry.start), title)
+                            }
+                        }
+                    }
+                    if !agenda.anytime.is_empty() {
+                        println!("  Anytime today:");
+                        for title in &agenda.anytime {
+                            println!("    ◇ {}", title);
                         }
                     }
                 }
                 Err(e) => {
-                    eprintln!("✗ Error loading tasks: {}", e);
+                    eprintln!("✗ Error loading today's agenda: {}", e);
                     std::process::exit(1);
                 }
             }
         }
 
-        Commands::Done { id } => match app.complete_task_by_id(id).await {
-            Ok(true) => {
-                if let Ok(Some(task)) = app.get_task_by_id(id).await {
-                    println!("✓ Marked task as done: \"{}\"", task.description);
-                } else {
-                    println!("✓ Marked task {} as done", id);
+        Commands::Summary => match app.completion_summary().await {
+            Ok((done_today, done_this_week)) => {
+                println!("📊 Completed today: {}", done_today.len());
+                for task in &done_today {
+                    let at = task
+                        .completed_at
+                        .map(|at| app.display.format_time(at.time()))
+                        .unwrap_or_default();
+                    println!("  ✓ {} {} (ID: {})", at, task.description, task.id);
                 }
-            }
-            Ok(false) => {
-                eprintln!("✗ Task with ID {} not found", id);
-                std::process::exit(1);
+                println!("   This week: {}", done_this_week);
             }
             Err(e) => {
-                eprintln!("✗ Error completing task: {}", e);
+                eprintln!("✗ Error loading completed tasks: {}", e);
                 std::process::exit(1);
             }
         },
 
-        Commands::Rm { id } => match app.remove_task_by_id(id).await {
-            Ok(true) => println!("✓ Removed task with ID {}", id),
-            Ok(false) => {
-                eprintln!("✗ Task with ID {} not found", id);
-                std::process::exit(1);
-            }
+        Commands::Export { path } => match app.export_database(&path).await {
+            Ok(backup) => println!(
+                "✓ Exported {} tasks, {} events and {} schedule blocks to {:?}",
+                backup.tasks.len(),
+                backup.events.len(),
+                backup.schedule_blocks.len(),
+                path
+            ),
             Err(e) => {
-                eprintln!("✗ Error removing task: {}", e);
+                eprintln!("✗ Export failed: {}", e);
                 std::process::exit(1);
             }
         },
 
-        Commands::Clear => match app.clear_completed_tasks().await {
-            Ok(count) => {
-                if count == 0 {
-                    println!("🧹 No completed tasks to clear");
-                } else {
-                    println!(
-                        "🧹 Cleared {} completed task{}",
-                        count,
-                        if count == 1 { "" } else { "s" }
-                    );
+        Commands::Maintenance => {
+            let summary = app.compact().await;
+            for warning in &summary.warnings {
+                eprintln!("⚠ {}", warning);
+            }
+            println!("✓ Renumbered {} tasks", summary.renumbered);
+            println!("✓ Removed {} orphaned rows", summary.orphans_removed);
+            if let (Some(before), Some(after)) = (summary.size_before, summary.size_after) {
+                println!(
+                    "✓ Database size {:.1} KB → {:.1} KB",
+                    before as f64 / 1024.0,
+                    after as f64 / 1024.0
+                );
+            }
+        }
+
+        Commands::Import { path } => match app.import_database(&path).await {
+            Ok(summary) => {
+                println!(
+                    "✓ Imported {} tasks, {} events and {} schedule blocks from {:?}",
+                    summary.tasks, summary.events, summary.schedule_blocks, path
+                );
+                if summary.skipped > 0 {
+                    println!("  Skipped {} rows that already exist", summary.skipped);
                 }
             }
             Err(e) => {
-                eprintln!("✗ Error clearing completed tasks: {}", e);
+                eprintln!("✗ Import failed (nothing was changed): {}", e);
                 std::process::exit(1);
             }
         },
 
+        Commands::ImportIcs { path, dry_run } => {
+            let content = match std::fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(e) => {
+                    eprintln!("✗ Could not read {:?}: {}", path, e);
+                    std::process::exit(1);
+                }
+            };
+            let events = sync::ical::parse_events(&content, None);
+
+            if events.is_empty() {
+                println!("📅 No events found in {:?}", path);
+            } else if dry_run {
+                println!("Would import {} events:", events.len());
+                for event in &events {
+                    let start = event.start_time.naive_utc();
+                    let when = if event.event_type.as_deref() == Some("all_day") {
+                        format!("{} (all day)", app.display.format_day(start.date()))
+                    } else {
+                        format!(
+                            "{} {}",
+                            app.display.format_day(start.date()),
+                            app.display.format_time(start.time())
+                        )
+                    };
+                    print!("  ◆ {} — {}", event.title, when);
+                    if let Some(location) = &event.location {
+                        print!(" @ {}", location);
+                    }
+                    if let Some(rule) = &event.recurrence_rule {
+                        print!(" [repeats: {}]", rule);
+                    }
+                    println!();
+                }
+            } else {
+                match app.import_events(&events).await {
+                    Ok(count) => {
+                        println!("✓ Imported {} events from {:?}", count, path);
+                        let skipped = events.len() - count;
+                        if skipped > 0 {
+                            println!("  Skipped {} events that already exist", skipped);
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("✗ Import failed: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            }
+        }
+
         Commands::Schedule(schedule_cmd) => match schedule_cmd {
             ScheduleCommands::Import { file, clear } => {
-                if clear {
-                    app.clear_all_schedule_blocks().await?;
-                    println!("Cleared existing blocks");
-                }
-                match app.import_schedule_from_toml(&file).await {
-                    Ok(count) => println!("✓ Imported {} schedule blocks from {:?}", count, file),
+                let (schedule, source) = match &file {
+                    Some(path) => match ScheduleToml::from_file(path) {
+                        Ok(schedule) => (schedule, format!("{:?}", path)),
+                        Err(e) => {
+                            eprintln!("✗ Import failed: {}", e);
+                            std::process::exit(1);
+                        }
+                    },
+                    None => (ScheduleToml::builtin(), "the built-in template".to_string()),
+                };
+                match app.import_schedule(schedule, clear).await {
+                    Ok(summary) => {
+                        if clear {
+                            println!("Replaced existing blocks");
+                        }
+                        println!(
+                            "✓ Imported {} schedule blocks from {}",
+                            summary.imported, source
+                        );
+                        if summary.duplicates > 0 {
+                            println!("  Skipped {} blocks that already exist", summary.duplicates);
+                        }
+                    }
                     Err(e) => {
                         eprintln!("✗ Import failed: {}", e);
                         std::process::exit(1);
@@ -283,13 +1142,232 @@ async fn handle_cli_command(
             }
         },
 
-        _ => unreachable!("Daemon commands handled earlier"),
+        Commands::Tag(tag_cmd) => match tag_cmd {
+            TagCommands::List => match app.tag_counts().await {
+                Ok(counts) if counts.is_empty() => println!("🏷  No tags yet"),
+                Ok(counts) => {
+                    println!("🏷  Tags:");
+                    for (tag, count) in counts {
+                        println!(
+                            "  #{} ({} task{})",
+                            tag,
+                            count,
+                            if count == 1 { "" } else { "s" }
+                        );
+                    }
+                }
+                Err(e) => {
+                    eprintln!("✗ Error listing tags: {}", e);
+                    std::process::exit(1);
+                }
+            },
+            TagCommands::Rename { old, new } => {
+                if new.trim().trim_start_matches('#').is_empty() {
+                    eprintln!("✗ The new tag name can't be empty");
+                    std::process::exit(1);
+                }
+                match app.rename_tag(&old, &new).await {
+                    Ok(0) => println!("🏷  No tasks are tagged #{}", old.trim_start_matches('#')),
+                    Ok(count) => println!(
+                        "✓ Renamed #{} to #{} on {} task{}",
+                        old.trim_start_matches('#'),
+                        new.trim_start_matches('#'),
+                        count,
+                        if count == 1 { "" } else { "s" }
+                    ),
+                    Err(e) => {
+                        eprintln!("✗ Error renaming tag: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            }
+            TagCommands::Rm { tag } => match app.delete_tag(&tag).await {
+                Ok(0) => println!("🏷  No tasks are tagged #{}", tag.trim_start_matches('#')),
+                Ok(count) => println!(
+                    "✓ Removed #{} from {} task{}",
+                    tag.trim_start_matches('#'),
+                    count,
+                    if count == 1 { "" } else { "s" }
+                ),
+                Err(e) => {
+                    eprintln!("✗ Error removing tag: {}", e);
+                    std::process::exit(1);
+                }
+            },
+        },
+
+        _ => unreachable!("Daemon and db-path commands handled earlier"),
     }
 
     Ok(())
 }
 
-async fn run_app<B: Backend>(terminal: &mut Terminal<B>, mut app: App) -> io::Result<()>
+/// "45s", "12m 05s", "3h 20m" or "2d 4h"
+fn format_uptime(secs: u64) -> String {
+    let (days, hours, minutes, seconds) =
+        (secs / 86_400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {:02}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m {:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Multi-line `list --verbose` entry, starting with a blank line
+fn format_task_verbose(
+    display: &DisplayConfig,
+    enhanced: &EnhancedTaskInfo,
+    indent: &str,
+    blocked: bool,
+) -> String {
+    let task = &enhanced.task;
+    let status = match (task.completed, blocked) {
+        (true, _) => "✓",
+        (false, true) => "🔒",
+        (false, false) => "○",
+    };
+
+    let when = match task.scheduled_at {
+        Some(scheduled) if task.scheduled_time_known => format!(
+            "{} {}",
+            display.format_day(scheduled.date_naive()),
+            display.format_time(scheduled.time())
+        ),
+        Some(scheduled) => format!("{} (all day)", display.format_day(scheduled.date_naive())),
+        None => "unscheduled".to_string(),
+    };
+    let tags = if enhanced.tags.is_empty() {
+        "none".to_string()
+    } else {
+        format!("#{}", enhanced.tags.join(" #"))
+    };
+
+    let mut lines = vec![format!(
+        "  {}{} {} (ID: {})",
+        indent, status, task.description, task.id
+    )];
+    if let Some(parent_id) = task.parent_id {
+        lines.push(format!("  {}    Parent:   {}", indent, parent_id));
+    }
+    lines.push(format!("  {}    When:     {}", indent, when));
+    if let Some(rule) = &task.recurrence_rule {
+        lines.push(format!("  {}    Repeats:  {}", indent, rule));
+    }
+    lines.push(format!(
+        "  {}    Priority: {}",
+        indent,
+        crate::nlp::Priority::from_level(task.priority).name()
+    ));
+    lines.push(format!("  {}    Tags:     {}", indent, tags));
+    lines.push(format!(
+        "  {}    Input:    {}",
+        indent,
+        task.natural_language_input.as_deref().unwrap_or("none")
+    ));
+    if let Some(notes) = &task.notes {
+        for (i, line) in notes.lines().enumerate() {
+            let label = if i == 0 { "Notes:" } else { "" };
+            lines.push(format!("  {}    {:<9} {}", indent, label, line));
+        }
+    }
+    format!("\n{}\n", lines.join("\n"))
+}
+
+async fn print_completed(app: &App, id: i64, blockers: &[(i64, String)]) {
+    if let Ok(Some(task)) = app.get_task_by_id(id).await {
+        println!("✓ Marked task as done: \"{}\"", task.description);
+    } else {
+        println!("✓ Marked task {} as done", id);
+    }
+    for (blocker_id, description) in blockers {
+        println!(
+            "⚠️  It was still blocked by task {} \"{}\"",
+            blocker_id, description
+        );
+    }
+}
+
+/// Version details for bug reports. Reads the database without creating or
+/// migrating it, so it's safe to run before the first launch.
+async fn print_version() {
+    println!("triptych {}", env!("CARGO_PKG_VERSION"));
+
+    let path = paths::database_path();
+    let latest = migrations::latest_version();
+    let schema = match migrations::applied_version(&path).await {
+        Ok(SchemaVersion::Uninitialized) => "uninitialized".to_string(),
+        Ok(SchemaVersion::Unversioned) => {
+            format!("unversioned (this build migrates it to {})", latest)
+        }
+        Ok(SchemaVersion::Version(version)) if version < latest => {
+            format!("{} (this build migrates it to {})", version, latest)
+        }
+        Ok(SchemaVersion::Version(version)) if version > latest => {
+            format!("{} (newer than this build's {})", version, latest)
+        }
+        Ok(SchemaVersion::Version(version)) => version.to_string(),
+        Err(e) => format!("unreadable ({})", e),
+    };
+    println!("schema:   {}", schema);
+    println!("database: {}", path.display());
+
+    let ollama = nlp::ollama_client::OllamaClient::new(None);
+    let (host, model) = ollama.endpoint();
+    let reachable = tokio::time::timeout(std::time::Duration::from_secs(2), ollama.health_check())
+        .await
+        .unwrap_or(false);
+    println!(
+        "ollama:   {} at {} ({})",
+        model,
+        host,
+        if reachable {
+            "reachable"
+        } else {
+            "not reachable"
+        }
+    );
+}
+
+/// Ask a yes/no question on the terminal; anything but "y" or "yes" is no
+fn confirm(question: &str) -> io::Result<bool> {
+    print!("{} [y/N] ", question);
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+fn print_cleared(count: u64) {
+    if count == 0 {
+        println!("🧹 No completed tasks to clear");
+    } else {
+        println!(
+            "🧹 Moved {} completed task{} to the trash",
+            count,
+            if count == 1 { "" } else { "s" }
+        );
+    }
+}
+
+/// Warn about schedule blocks and tasks overlapping a newly added task
+async fn print_conflicts(app: &App, id: i64) {
+    let Ok(Some(task)) = app.get_task_by_id(id).await else {
+        return;
+    };
+    for conflict in app.find_conflicts(&task).await.unwrap_or_default() {
+        println!("⚠️  Conflicts with {}", conflict);
+    }
+}
+
+async fn run_app<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    mut events: broadcast::Receiver<AppEvent>,
+) -> io::Result<()>
 where
     std::io::Error: std::convert::From<<B as ratatui::backend::Backend>::Error>,
 {
@@ -305,8 +1383,9 @@ where
     // Create async event stream (crossterm's async API)
     let mut reader = EventStream::new();
 
+    let mut layout = ScreenLayout::default();
     loop {
-        terminal.draw(|f| ui(f, &app))?;
+        terminal.draw(|f| layout = ui(f, app, &layout))?;
 
         // Wait for either keyboard event or shutdown signal
         tokio::select! {
@@ -320,20 +1399,64 @@ where
                                     ViewMode::TodoList => match key.code {
                                         KeyCode::Char('q') => return Ok(()),
                                         KeyCode::Char('c') => { app.toggle_to_calendar().await; }
-                                        KeyCode::Char('a') => {
-                                            app.input_mode = InputMode::Editing;
+                                        KeyCode::Char('a') => app.start_adding_task().await,
+                                        KeyCode::Char('A') if !app.tasks.is_empty() => {
+                                            app.input_mode = InputMode::AddingSubtask;
                                             app.input_buffer.clear();
                                         }
+                                        KeyCode::Char('/') => app.start_search(),
+                                        KeyCode::Char('n') => app.start_editing_notes(),
                                         KeyCode::Char('x') => {
                                             if let Err(e) = app.delete_task().await {
                                                 app.status_message = Some((format!("Error: {}", e), std::time::Instant::now()));
                                             }
                                         }
+                                        KeyCode::Char('X') => app.start_clearing_completed(),
+                                        KeyCode::Char('u') | KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                            let page = layout.task_page_size();
+                                            app.selected = if key.code == KeyCode::Char('u') {
+                                                app.selected.saturating_sub(page)
+                                            } else {
+                                                (app.selected + page).min(app.tasks.len().saturating_sub(1))
+                                            };
+                                        }
+                                        KeyCode::PageUp => {
+                                            app.selected = app.selected.saturating_sub(layout.task_page_size());
+                                        }
+                                        KeyCode::PageDown => {
+                                            app.selected = (app.selected + layout.task_page_size()).min(app.tasks.len().saturating_sub(1));
+                                        }
+                                        KeyCode::Char('u') => {
+                                            if let Err(e) = app.undo().await {
+                                                app.status_message = Some((format!("Error: {}", e), std::time::Instant::now()));
+                                            }
+                                        }
+                                        KeyCode::Char('v') => {
+                                            if let Err(e) = app.convert_selected_task().await {
+                                                app.status_message = Some((format!("Error: {}", e), std::time::Instant::now()));
+                                            }
+                                        }
                                         KeyCode::Char('s') => {
                                             if let Err(e) = app.auto_schedule_task().await {
                                                 app.status_message = Some((format!("Error: {}", e), std::time::Instant::now()));
                                             }
                                         }
+                                        KeyCode::Char('r') if !app.tasks.is_empty() => {
+                                            app.input_mode = InputMode::Rescheduling;
+                                            app.input_buffer.clear();
+                                            app.input_error = None;
+                                        }
+                                        KeyCode::Char('p') => {
+                                            if let Err(e) = app.cycle_priority().await {
+                                                app.status_message = Some((format!("Error: {}", e), std::time::Instant::now()));
+                                            }
+                                        }
+                                        KeyCode::Char('b') => { app.start_planning().await; }
+                                        KeyCode::Char('o') => {
+                                            if let Err(e) = app.cycle_sort_mode().await {
+                                                app.status_message = Some((format!("Error: {}", e), std::time::Instant::now()));
+                                            }
+                                        }
                                         KeyCode::Enter => {
                                             if let Err(e) = app.toggle_completed().await {
                                                 app.status_message = Some((format!("Error: {}", e), std::time::Instant::now()));
@@ -342,28 +1465,65 @@ where
                                         KeyCode::Char('k') => {
                                             app.selected = app.selected.saturating_sub(1);
                                         }
-                                        KeyCode::Char('j') => {
-                                            if !app.tasks.is_empty() {
-                                                let max = app.tasks.len() - 1;
-                                                if app.selected < max {
-                                                    app.selected += 1;
-                                                }
+                                        KeyCode::Char('K') => {
+                                            if let Err(e) = app.move_task(MoveDirection::Up).await {
+                                                app.status_message = Some((format!("Error: {}", e), std::time::Instant::now()));
+                                            }
+                                        }
+                                        KeyCode::Char('J') => {
+                                            if let Err(e) = app.move_task(MoveDirection::Down).await {
+                                                app.status_message = Some((format!("Error: {}", e), std::time::Instant::now()));
+                                            }
+                                        }
+                                        KeyCode::Char('j') if !app.tasks.is_empty() => {
+                                            let max = app.tasks.len() - 1;
+                                            if app.selected < max {
+                                                app.selected += 1;
                                             }
                                         }
                                         _ => {}
                                     },
-                                    ViewMode::Calendar => match app.calendar_input_mode {
+                                    ViewMode::Calendar | ViewMode::Day | ViewMode::Month => match app.calendar_input_mode {
+                                        // The month grid has no time slots, so only navigation applies
+                                        CalendarInputMode::Navigate if app.view_mode == ViewMode::Month => match key.code {
+                                            KeyCode::Char('q') => return Ok(()),
+                                            KeyCode::Char('t') | KeyCode::Esc => { app.toggle_to_todo().await; }
+                                            KeyCode::Char('v') => { app.cycle_calendar_view().await; }
+                                            KeyCode::Char('h') | KeyCode::Left => { app.prev_period().await; }
+                                            KeyCode::Char('l') | KeyCode::Right => { app.next_period().await; }
+                                            KeyCode::Char('k') | KeyCode::Up | KeyCode::Char('H') => { app.prev_week().await; }
+                                            KeyCode::Char('j') | KeyCode::Down | KeyCode::Char('L') => { app.next_week().await; }
+                                            KeyCode::Enter => { app.open_day_view().await; }
+                                            _ => {}
+                                        },
                                         CalendarInputMode::Navigate => match key.code {
                                             KeyCode::Char('q') => return Ok(()),
                                             KeyCode::Char('t') | KeyCode::Esc => { app.toggle_to_todo().await; }
+                                            KeyCode::Char('v') => { app.cycle_calendar_view().await; }
                                             KeyCode::Char('j') | KeyCode::Down => app.calendar_move_down(),
                                             KeyCode::Char('k') | KeyCode::Up => app.calendar_move_up(),
+                                            KeyCode::Char('h') | KeyCode::Left if app.view_mode == ViewMode::Day => { app.prev_period().await; }
+                                            KeyCode::Char('l') | KeyCode::Right if app.view_mode == ViewMode::Day => { app.next_period().await; }
                                             KeyCode::Char('h') | KeyCode::Left => app.calendar_move_left(),
                                             KeyCode::Char('l') | KeyCode::Right => app.calendar_move_right(),
                                             KeyCode::Char('H') => { app.prev_week().await; }
                                             KeyCode::Char('L') => { app.next_week().await; }
+                                            KeyCode::Char('<') => {
+                                                if let Err(e) = app.shift_selected_task_days(-1).await {
+                                                    app.status_message = Some((format!("Error: {}", e), std::time::Instant::now()));
+                                                }
+                                            }
+                                            KeyCode::Char('>') => {
+                                                if let Err(e) = app.shift_selected_task_days(1).await {
+                                                    app.status_message = Some((format!("Error: {}", e), std::time::Instant::now()));
+                                                }
+                                            }
                                             KeyCode::Char('n') => {
-                                                app.block_form = BlockFormState::new_at(app.selected_time_slot);
+                                                app.block_form = BlockFormState::new_at(
+                                                    app.selected_cell_time(),
+                                                    app.display.calendar_slot_minutes,
+                                                )
+                                                    .with_block_types(&app.cached_block_types);
                                                 app.calendar_input_mode = CalendarInputMode::BlockForm;
                                             }
                                             KeyCode::Char('s') => {
@@ -379,6 +1539,11 @@ where
                                                     app.status_message = Some((format!("Error: {}", e), std::time::Instant::now()));
                                                 }
                                             }
+                                            KeyCode::Enter => {
+                                                if let Err(e) = app.edit_block_at_selected_cell().await {
+                                                    app.status_message = Some((format!("Error: {}", e), std::time::Instant::now()));
+                                                }
+                                            }
                                             _ => {}
                                         },
                                         CalendarInputMode::BlockForm => match key.code {
@@ -389,7 +1554,7 @@ where
                                             KeyCode::BackTab => app.block_form.prev_field(),
                                             KeyCode::Enter => {
                                                 if !app.block_form.title.is_empty()
-                                                    && let Err(e) = app.create_schedule_block().await {
+                                                    && let Err(e) = app.save_schedule_block().await {
                                                         app.status_message = Some((format!("Error: {}", e), std::time::Instant::now()));
                                                     }
                                             }
@@ -489,6 +1654,138 @@ where
                                         }
                                     app.input_mode = InputMode::Normal;
                                 }
+                                KeyCode::Tab => {
+                                    app.complete_tag();
+                                }
+                                KeyCode::Char(c) => {
+                                    app.input_buffer.push(c);
+                                }
+                                KeyCode::Backspace => {
+                                    app.input_buffer.pop();
+                                }
+                                KeyCode::Esc => {
+                                    app.input_mode = InputMode::Normal;
+                                }
+                                _ => {}
+                            },
+
+                            InputMode::AddingSubtask => match key.code {
+                                KeyCode::Enter => {
+                                    let description = app.input_buffer.trim().to_string();
+                                    if !description.is_empty()
+                                        && let Some(parent) = app.tasks.get(app.selected)
+                                        && let Err(e) = app.add_subtask(parent.id, &description).await {
+                                            app.status_message = Some((format!("Error: {}", e), std::time::Instant::now()));
+                                        }
+                                    app.input_mode = InputMode::Normal;
+                                }
+                                KeyCode::Char(c) => {
+                                    app.input_buffer.push(c);
+                                }
+                                KeyCode::Backspace => {
+                                    app.input_buffer.pop();
+                                }
+                                KeyCode::Esc => {
+                                    app.input_mode = InputMode::Normal;
+                                }
+                                _ => {}
+                            },
+
+                            InputMode::EditingNotes => match key.code {
+                                KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                                    if let Err(e) = app.save_notes_from_input().await {
+                                        app.status_message = Some((format!("Error: {}", e), std::time::Instant::now()));
+                                    }
+                                    app.input_mode = InputMode::Normal;
+                                }
+                                KeyCode::Enter => {
+                                    app.input_buffer.push('\n');
+                                }
+                                KeyCode::Char(c) => {
+                                    app.input_buffer.push(c);
+                                }
+                                KeyCode::Backspace => {
+                                    app.input_buffer.pop();
+                                }
+                                KeyCode::Esc => {
+                                    app.input_buffer.clear();
+                                    app.input_mode = InputMode::Normal;
+                                }
+                                _ => {}
+                            },
+
+                            InputMode::Planning => match key.code {
+                                KeyCode::Enter => match app.plan_selected_from_input().await {
+                                    Ok(true) => app.input_mode = InputMode::Normal,
+                                    Ok(false) => {} // Keep the prompt open to show the error
+                                    Err(e) => {
+                                        app.status_message = Some((format!("Error: {}", e), std::time::Instant::now()));
+                                        app.input_mode = InputMode::Normal;
+                                    }
+                                },
+                                KeyCode::Char(c) => {
+                                    app.input_buffer.push(c);
+                                }
+                                KeyCode::Backspace => {
+                                    app.input_buffer.pop();
+                                }
+                                KeyCode::Esc => {
+                                    app.input_error = None;
+                                    app.input_mode = InputMode::Normal;
+                                }
+                                _ => {}
+                            },
+
+                            InputMode::ConfirmingClear => match key.code {
+                                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                                    if let Err(e) = app.confirm_clear_completed().await {
+                                        app.status_message = Some((format!("Error: {}", e), std::time::Instant::now()));
+                                    }
+                                }
+                                // Anything else, including Esc, keeps the tasks
+                                _ => app.input_mode = InputMode::Normal,
+                            },
+
+                            InputMode::Searching => match key.code {
+                                KeyCode::Enter => {
+                                    app.open_search_result().await;
+                                    app.input_mode = InputMode::Normal;
+                                }
+                                KeyCode::Down => app.next_search_result(),
+                                KeyCode::Up => app.previous_search_result(),
+                                KeyCode::Char(c) => {
+                                    app.input_buffer.push(c);
+                                    if let Err(e) = app.refresh_search().await {
+                                        app.status_message = Some((format!("Error: {}", e), std::time::Instant::now()));
+                                    }
+                                }
+                                KeyCode::Backspace => {
+                                    app.input_buffer.pop();
+                                    if let Err(e) = app.refresh_search().await {
+                                        app.status_message = Some((format!("Error: {}", e), std::time::Instant::now()));
+                                    }
+                                }
+                                KeyCode::Esc => {
+                                    app.input_mode = InputMode::Normal;
+                                }
+                                _ => {}
+                            },
+
+                            InputMode::Rescheduling => match key.code {
+                                KeyCode::Enter => {
+                                    if app.input_buffer.trim().is_empty() {
+                                        app.input_mode = InputMode::Normal;
+                                    } else {
+                                        match app.reschedule_selected_from_input().await {
+                                            Ok(true) => app.input_mode = InputMode::Normal,
+                                            Ok(false) => {} // Keep the prompt open to show the error
+                                            Err(e) => {
+                                                app.status_message = Some((format!("Error: {}", e), std::time::Instant::now()));
+                                                app.input_mode = InputMode::Normal;
+                                            }
+                                        }
+                                    }
+                                }
                                 KeyCode::Char(c) => {
                                     app.input_buffer.push(c);
                                 }
@@ -496,13 +1793,19 @@ where
                                     app.input_buffer.pop();
                                 }
                                 KeyCode::Esc => {
+                                    app.input_error = None;
                                     app.input_mode = InputMode::Normal;
                                 }
                                 _ => {}
                             },
                         }
                     }
-                    Some(Ok(_)) => {} // Other events (mouse, resize, etc.)
+                    Some(Ok(Event::Mouse(mouse))) if matches!(app.input_mode, InputMode::Normal) => {
+                        handle_mouse(app, &layout, mouse).await;
+                    }
+                    // The next draw picks up the new size and lays the views out again
+                    Some(Ok(Event::Resize(_, _))) => {}
+                    Some(Ok(_)) => {} // Other events (focus, paste)
                     Some(Err(e)) => {
                         app.status_message = Some((format!("Input error: {}", e), std::time::Instant::now()));
                     }
@@ -510,6 +1813,13 @@ where
                 }
             }
 
+            // A worker or another process changed something on screen
+            Some(event) = next_app_event(&mut events) => {
+                if let Err(e) = app.reload_after_change(event).await {
+                    app.status_message = Some((format!("Error reloading: {}", e), std::time::Instant::now()));
+                }
+            }
+
             // Shutdown signal
             _ = shutdown_rx.recv() => {
                 return Ok(());
@@ -519,3 +1829,123 @@ where
 
     Ok(())
 }
+
+/// The next event from the sync workers, or None once they've all stopped.
+/// Events missed because the TUI fell behind are caught up by one reload.
+async fn next_app_event(events: &mut broadcast::Receiver<AppEvent>) -> Option<AppEvent> {
+    match events.recv().await {
+        Ok(event) => Some(event),
+        Err(broadcast::error::RecvError::Lagged(_)) => Some(AppEvent::DatabaseChanged),
+        Err(broadcast::error::RecvError::Closed) => None,
+    }
+}
+
+/// Clicks select a task (toggling it on its checkbox) or a week grid cell;
+/// the wheel moves the selection like j/k
+async fn handle_mouse(app: &mut App, layout: &ScreenLayout, mouse: MouseEvent) {
+    let navigating = app.calendar_input_mode == CalendarInputMode::Navigate;
+    match (mouse.kind, &app.view_mode) {
+        (MouseEventKind::Down(MouseButton::Left), ViewMode::TodoList) => {
+            if let Some((index, on_checkbox)) = layout.task_at(app, mouse.column, mouse.row) {
+                app.selected = index;
+                if on_checkbox && let Err(e) = app.toggle_completed().await {
+                    app.status_message = Some((format!("Error: {}", e), std::time::Instant::now()));
+                }
+            }
+        }
+        (MouseEventKind::Down(MouseButton::Left), ViewMode::Calendar) if navigating => {
+            if let Some((day, slot)) = layout.week_cell_at(mouse.column, mouse.row) {
+                app.select_calendar_cell(day, slot);
+            }
+        }
+        (MouseEventKind::ScrollUp, ViewMode::TodoList) => {
+            app.selected = app.selected.saturating_sub(1);
+        }
+        (MouseEventKind::ScrollDown, ViewMode::TodoList) if app.selected + 1 < app.tasks.len() => {
+            app.selected += 1;
+        }
+        (MouseEventKind::ScrollUp, ViewMode::Calendar | ViewMode::Day) if navigating => {
+            app.calendar_move_up()
+        }
+        (MouseEventKind::ScrollDown, ViewMode::Calendar | ViewMode::Day) if navigating => {
+            app.calendar_move_down()
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn known_task() -> EnhancedTaskInfo {
+        let scheduled = NaiveDate::from_ymd_opt(2026, 3, 13)
+            .unwrap()
+            .and_hms_opt(15, 0, 0)
+            .unwrap()
+            .and_utc();
+        EnhancedTaskInfo {
+            task: app::Task {
+                id: 7,
+                description: "Submit report".to_string(),
+                completed: false,
+                item_order: Some(0),
+                scheduled_at: Some(scheduled),
+                priority: 2,
+                tags: Some(r#"["work","q1"]"#.to_string()),
+                natural_language_input: Some("submit report friday 3pm #work #q1".to_string()),
+                task_category: None,
+                scheduled_time_known: true,
+                parent_id: Some(3),
+                notes: Some("Attach the figures\nCC the team".to_string()),
+                completed_at: None,
+                deleted_at: None,
+                recurrence_rule: Some("FREQ=WEEKLY;BYDAY=FR".to_string()),
+            },
+            tags: vec!["work".to_string(), "q1".to_string()],
+            is_scheduled: true,
+        }
+    }
+
+    #[test]
+    fn verbose_entry_snapshot() {
+        let output = format_task_verbose(&DisplayConfig::default(), &known_task(), "  ", false);
+        assert_eq!(
+            output,
+            "
+    ○ Submit report (ID: 7)
+        Parent:   3
+        When:     Fri 03/13 3:00pm
+        Repeats:  FREQ=WEEKLY;BYDAY=FR
+        Priority: high
+        Tags:     #work #q1
+        Input:    submit report friday 3pm #work #q1
+        Notes:    Attach the figures
+                  CC the team
+"
+        );
+    }
+
+    #[test]
+    fn verbose_entry_for_a_blocked_all_day_task() {
+        let mut enhanced = known_task();
+        enhanced.task.scheduled_time_known = false;
+        enhanced.task.parent_id = None;
+        enhanced.task.recurrence_rule = None;
+        enhanced.task.notes = None;
+        enhanced.tags.clear();
+
+        let output = format_task_verbose(&DisplayConfig::default(), &enhanced, "", true);
+        assert_eq!(
+            output,
+            "
+  🔒 Submit report (ID: 7)
+      When:     Fri 03/13 (all day)
+      Priority: high
+      Tags:     none
+      Input:    submit report friday 3pm #work #q1
+"
+        );
+    }
+}
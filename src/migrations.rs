@@ -1,74 +1,174 @@
-use anyhow::Result;
-use sqlx::SqlitePool;
-
-pub async fn run_calendar_migration(pool: &SqlitePool) -> Result<()> {
-    eprintln!("[Migration] Checking calendar schema...");
-
-    // Check and add tasks columns safely
-    if !column_exists(pool, "tasks", "scheduled_event_id").await? {
-        sqlx::query(
-            "ALTER TABLE tasks ADD COLUMN scheduled_event_id INTEGER REFERENCES events(id)",
-        )
-        .execute(pool)
-        .await?;
-        eprintln!("  ✓ Added scheduled_event_id to tasks");
-    }
+use sqlx::migrate::Migrator;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::{ConnectOptions, SqlitePool};
+use std::path::Path;
 
-    if !column_exists(pool, "tasks", "task_category").await? {
-        sqlx::query("ALTER TABLE tasks ADD COLUMN task_category TEXT DEFAULT 'general'")
-            .execute(pool)
-            .await?;
-        eprintln!("  ✓ Added task_category to tasks");
+/// Versioned schema migrations from ./migrations, run by `App::build`
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+/// The migration adding the calendar columns to `tasks` and `events`
+const CALENDAR_COLUMNS_VERSION: i64 = 20261016120000;
+
+/// (table, column, definition) for every column that migration adds
+const CALENDAR_COLUMNS: &[(&str, &str, &str)] = &[
+    (
+        "tasks",
+        "scheduled_event_id",
+        "INTEGER REFERENCES events(id)",
+    ),
+    ("tasks", "task_category", "TEXT DEFAULT 'general'"),
+    (
+        "tasks",
+        "scheduled_time_known",
+        "BOOLEAN NOT NULL DEFAULT 1",
+    ),
+    ("events", "event_type", "TEXT DEFAULT 'event'"),
+    ("events", "recurrence_rule", "TEXT"),
+    ("events", "exdates", "TEXT"),
+    ("events", "uid", "TEXT"),
+];
+
+/// Bring a database upgraded before the calendar schema was versioned in line
+/// with `MIGRATOR`. Such databases already have some or all of the calendar
+/// columns, which the calendar migration's ALTER TABLEs would fail on, so add
+/// whatever is still missing and record that migration as applied. Fresh and
+/// already-versioned databases are left alone. Call before `MIGRATOR.run`.
+pub async fn adopt_unversioned_schema(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    if !table_exists(pool, "_sqlx_migrations").await? {
+        return Ok(());
     }
 
-    // Check and add events columns
-    if !column_exists(pool, "events", "event_type").await? {
-        sqlx::query("ALTER TABLE events ADD COLUMN event_type TEXT DEFAULT 'event'")
-            .execute(pool)
+    let applied: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM _sqlx_migrations WHERE version = ?")
+            .bind(CALENDAR_COLUMNS_VERSION)
+            .fetch_one(pool)
             .await?;
-        eprintln!("  ✓ Added event_type to events");
+    if applied > 0 {
+        return Ok(());
     }
 
-    if !column_exists(pool, "events", "recurrence_rule").await? {
-        sqlx::query("ALTER TABLE events ADD COLUMN recurrence_rule TEXT")
-            .execute(pool)
-            .await?;
-        eprintln!("  ✓ Added recurrence_rule to events");
+    let mut missing = Vec::new();
+    for &(table, column, definition) in CALENDAR_COLUMNS {
+        if !column_exists(pool, table, column).await? {
+            missing.push((table, column, definition));
+        }
     }
+    if missing.len() == CALENDAR_COLUMNS.len() {
+        // Never touched by the old migration; MIGRATOR adds everything
+        return Ok(());
+    }
+
+    let Some(migration) = MIGRATOR
+        .iter()
+        .find(|m| m.version == CALENDAR_COLUMNS_VERSION)
+    else {
+        return Ok(());
+    };
 
-    // Create schedule_blocks table
+    let mut tx = pool.begin().await?;
+    for (table, column, definition) in missing {
+        sqlx::query(&format!(
+            "ALTER TABLE {} ADD COLUMN {} {}",
+            table, column, definition
+        ))
+        .execute(&mut *tx)
+        .await?;
+        tracing::info!("  ✓ Added {} to {}", column, table);
+    }
     sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS schedule_blocks (
-            id INTEGER PRIMARY KEY AUTOINCREMENT,
-            day_of_week INTEGER NOT NULL CHECK(day_of_week >= 0 AND day_of_week <= 6),
-            start_time TEXT NOT NULL,
-            end_time TEXT NOT NULL,
-            block_type TEXT NOT NULL,
-            title TEXT NOT NULL,
-            description TEXT,
-            priority INTEGER DEFAULT 1,
-            created_at TEXT DEFAULT CURRENT_TIMESTAMP
-        )
-    "#,
+        "INSERT INTO _sqlx_migrations (version, description, success, checksum, execution_time)
+         VALUES (?, ?, TRUE, ?, 0)",
     )
-    .execute(pool)
+    .bind(migration.version)
+    .bind(migration.description.as_ref())
+    .bind(migration.checksum.as_ref())
+    .execute(&mut *tx)
     .await?;
-    eprintln!("  ✓ Schedule blocks table ready");
+    tx.commit().await?;
 
-    // Create indexes
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_schedule_blocks_day ON schedule_blocks(day_of_week, start_time)")
-        .execute(pool)
-        .await?;
-    sqlx::query("CREATE INDEX IF NOT EXISTS idx_tasks_category ON tasks(task_category)")
-        .execute(pool)
+    tracing::info!("[Migration] Adopted existing calendar schema ✓");
+    Ok(())
+}
+
+/// Version of the newest migration this build knows about
+pub fn latest_version() -> i64 {
+    MIGRATOR.iter().map(|m| m.version).max().unwrap_or(0)
+}
+
+/// What `applied_version` found in a database file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaVersion {
+    /// No file, or a file without any tables
+    Uninitialized,
+    /// Tables from before migrations were versioned, but no `_sqlx_migrations`
+    Unversioned,
+    /// Newest migration applied
+    Version(i64),
+}
+
+/// Schema state of the database at `path`, opened read-only
+pub async fn applied_version(path: &Path) -> Result<SchemaVersion, sqlx::Error> {
+    if !path.is_file() {
+        return Ok(SchemaVersion::Uninitialized);
+    }
+    let mut conn = SqliteConnectOptions::new()
+        .filename(path)
+        .read_only(true)
+        .connect()
         .await?;
 
-    eprintln!("[Migration] Calendar schema ready ✓");
-    Ok(())
+    let has_table: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations'",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+    if has_table > 0 {
+        let version: Option<i64> =
+            sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations WHERE success = 1")
+                .fetch_one(&mut conn)
+                .await?;
+        if let Some(version) = version {
+            return Ok(SchemaVersion::Version(version));
+        }
+    }
+
+    let tables: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM sqlite_master
+         WHERE type = 'table' AND name != '_sqlx_migrations' AND name NOT LIKE 'sqlite_%'",
+    )
+    .fetch_one(&mut conn)
+    .await?;
+    Ok(if tables > 0 {
+        SchemaVersion::Unversioned
+    } else {
+        SchemaVersion::Uninitialized
+    })
+}
+
+/// A fresh, fully migrated in-memory database. One connection, since each
+/// connection to `sqlite::memory:` would open a database of its own.
+#[cfg(test)]
+pub async fn memory_pool() -> SqlitePool {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    MIGRATOR.run(&pool).await.unwrap();
+    pool
+}
+
+async fn table_exists(pool: &SqlitePool, table: &str) -> Result<bool, sqlx::Error> {
+    let count: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?")
+            .bind(table)
+            .fetch_one(pool)
+            .await?;
+
+    Ok(count > 0)
 }
 
-async fn column_exists(pool: &SqlitePool, table: &str, column: &str) -> Result<bool> {
+async fn column_exists(pool: &SqlitePool, table: &str, column: &str) -> Result<bool, sqlx::Error> {
     let count: i64 = sqlx::query_scalar(&format!(
         "SELECT COUNT(*) FROM pragma_table_info('{}') WHERE name = ?",
         table
@@ -1,11 +1,21 @@
-use crate::nlp::types::{Event, ParsedItem, Priority, Task};
-use chrono::Duration;
+use crate::nlp::types::{EmailAction, Event, ParsedItem, Priority, Task};
+use crate::timezone;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use reqwest::{Client, Error as ReqwestError};
 use serde::{Deserialize, Serialize};
+use std::time::Duration as StdDuration;
 use tokio::time::timeout;
 
+// Defaults, overridable with OLLAMA_HOST, OLLAMA_MODEL, OLLAMA_TIMEOUT_MS
+// and OLLAMA_IDLE_TIMEOUT_MS
 const OLLAMA_BASE_URL: &str = "http://localhost:11434";
+const OLLAMA_MODEL: &str = "qwen2.5:7b";
 const OLLAMA_TIMEOUT_MS: u64 = 15000;
+/// How far in the past a returned datetime may be before it's treated as a
+/// hallucination (e.g. a wrong year) rather than a late "today" task
+const MAX_PAST_DAYS: i64 = 3;
+/// Longest gap between streamed tokens before the model is considered hung
+const OLLAMA_IDLE_TIMEOUT_MS: u64 = 5000;
 
 #[derive(Serialize)]
 struct OllamaRequest {
@@ -20,45 +30,112 @@ struct OllamaResponse {
     response: String,
 }
 
+/// One line of a streamed `/api/generate` response
+#[derive(Deserialize)]
+struct OllamaStreamChunk {
+    #[serde(default)]
+    response: String,
+    #[serde(default)]
+    done: bool,
+}
+
+/// The model is asked for `{"items": [...]}`, but a bare array or a single
+/// item object is accepted too
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StructuredResponse {
+    Wrapped { items: Vec<StructuredOutput> },
+    Many(Vec<StructuredOutput>),
+    One(StructuredOutput),
+}
+
 #[derive(Deserialize)]
 struct StructuredOutput {
     #[serde(rename = "type")]
     item_type: String,
+    #[serde(default)]
     title: String,
     datetime: Option<String>,
     tags: Option<Vec<String>>,
     priority: Option<String>,
+    // Email items only
+    action: Option<String>,
+    recipient: Option<String>,
+    subject: Option<String>,
 }
 
 pub struct OllamaClient {
     client: Client,
+    base_url: String,
     model: String,
+    timeout_ms: u64,
+    /// Stream tokens (OLLAMA_STREAM=1) and give up after `idle_timeout_ms`
+    /// without one, instead of waiting out the whole `timeout_ms`
+    stream: bool,
+    idle_timeout_ms: u64,
 }
 
 impl OllamaClient {
+    /// `model` takes precedence over OLLAMA_MODEL
     pub fn new(model: Option<String>) -> Self {
+        let env = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+
+        let base_url = env("OLLAMA_HOST")
+            .map(|host| normalize_host(&host))
+            .unwrap_or_else(|| OLLAMA_BASE_URL.to_string());
+        let millis = |name: &str, default: u64| {
+            env(name)
+                .and_then(|ms| {
+                    let parsed = ms.trim().parse().ok().filter(|ms| *ms > 0);
+                    if parsed.is_none() {
+                        tracing::warn!("⚠️  Ignoring invalid {} \"{}\"", name, ms);
+                    }
+                    parsed
+                })
+                .unwrap_or(default)
+        };
+        let stream = env("OLLAMA_STREAM")
+            .is_some_and(|v| matches!(v.trim().to_lowercase().as_str(), "1" | "true" | "yes"));
+
         Self {
             client: Client::new(),
-            model: model.unwrap_or_else(|| "qwen2.5:7b".to_string()),
+            base_url,
+            model: model
+                .or_else(|| env("OLLAMA_MODEL"))
+                .unwrap_or_else(|| OLLAMA_MODEL.to_string()),
+            timeout_ms: millis("OLLAMA_TIMEOUT_MS", OLLAMA_TIMEOUT_MS),
+            stream,
+            idle_timeout_ms: millis("OLLAMA_IDLE_TIMEOUT_MS", OLLAMA_IDLE_TIMEOUT_MS),
         }
     }
 
-    pub async fn parse(&self, input: &str) -> Result<ParsedItem, OllamaError> {
+    /// Parse `input` into one or more items (never an empty list)
+    pub async fn parse(&self, input: &str) -> Result<Vec<ParsedItem>, OllamaError> {
         let prompt = self.build_prompt(input);
 
         let request = OllamaRequest {
             model: self.model.clone(),
             prompt,
-            stream: false,
+            stream: self.stream,
             format: "json".to_string(),
         };
 
+        let response = if self.stream {
+            self.generate_streaming(&request).await?
+        } else {
+            self.generate(&request).await?
+        };
+
+        self.parse_response(&response)
+    }
+
+    async fn generate(&self, request: &OllamaRequest) -> Result<String, OllamaError> {
         // Apply timeout to prevent hanging (use std::time::Duration for tokio)
         let response = timeout(
-            std::time::Duration::from_millis(OLLAMA_TIMEOUT_MS),
+            std::time::Duration::from_millis(self.timeout_ms),
             self.client
-                .post(format!("{}/api/generate", OLLAMA_BASE_URL))
-                .json(&request)
+                .post(format!("{}/api/generate", self.base_url))
+                .json(request)
                 .send(),
         )
         .await
@@ -67,13 +144,86 @@ impl OllamaClient {
 
         let ollama_response: OllamaResponse =
             response.json().await.map_err(OllamaError::Request)?;
+        Ok(ollama_response.response)
+    }
+
+    /// Accumulate a streamed response, one JSON object per line. Fails with
+    /// `Stalled` when no data arrives for `idle_timeout_ms`, and with `Timeout`
+    /// once the whole exchange exceeds `timeout_ms`.
+    async fn generate_streaming(&self, request: &OllamaRequest) -> Result<String, OllamaError> {
+        let deadline = tokio::time::Instant::now() + StdDuration::from_millis(self.timeout_ms);
+        let idle = StdDuration::from_millis(self.idle_timeout_ms);
+        // Wait for the next piece of the exchange, whichever limit comes first
+        let wait = || {
+            let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+            (idle.min(remaining), remaining <= idle)
+        };
+
+        let (limit, overall) = wait();
+        let mut response = timeout(
+            limit,
+            self.client
+                .post(format!("{}/api/generate", self.base_url))
+                .json(request)
+                .send(),
+        )
+        .await
+        .map_err(|_| {
+            if overall {
+                OllamaError::Timeout
+            } else {
+                OllamaError::Stalled
+            }
+        })?
+        .map_err(OllamaError::Request)?
+        .error_for_status()
+        .map_err(OllamaError::Request)?;
 
-        self.parse_response(&ollama_response.response)
+        let mut pending = Vec::new();
+        let mut text = String::new();
+        loop {
+            let (limit, overall) = wait();
+            let chunk = timeout(limit, response.chunk())
+                .await
+                .map_err(|_| {
+                    if overall {
+                        OllamaError::Timeout
+                    } else {
+                        OllamaError::Stalled
+                    }
+                })?
+                .map_err(OllamaError::Request)?;
+            let Some(chunk) = chunk else {
+                break;
+            };
+
+            pending.extend_from_slice(&chunk);
+            while let Some(newline) = pending.iter().position(|b| *b == b'\n') {
+                let line: Vec<u8> = pending.drain(..=newline).collect();
+                if line.trim_ascii().is_empty() {
+                    continue;
+                }
+                let part: OllamaStreamChunk = serde_json::from_slice(&line)
+                    .map_err(|e| OllamaError::ParseError(e.to_string()))?;
+                text.push_str(&part.response);
+                if part.done {
+                    return Ok(text);
+                }
+            }
+        }
+
+        // The stream may end without a trailing newline after the last object
+        if !pending.trim_ascii().is_empty() {
+            let part: OllamaStreamChunk = serde_json::from_slice(&pending)
+                .map_err(|e| OllamaError::ParseError(e.to_string()))?;
+            text.push_str(&part.response);
+        }
+        Ok(text)
     }
 
     fn build_prompt(&self, input: &str) -> String {
         // Get current date for context
-        let now = chrono::Local::now();
+        let now = timezone::now();
         let today = now.format("%Y-%m-%d").to_string();
         let tomorrow = (now + Duration::days(1)).format("%Y-%m-%d").to_string();
 
@@ -85,42 +235,85 @@ CRITICAL TIME PARSING RULES:
 - "4:12 AM" or "4:12 am" → use 04:12:00 (morning)  
 - "12:00 PM" → use 12:00:00 (noon)
 - "12:00 AM" → use 00:00:00 (midnight)
-- Always output datetime in ISO 8601 format with timezone: YYYY-MM-DDTHH:MM:SS+00:00
+- Always output datetime as the local clock time the user said, in ISO 8601 format: YYYY-MM-DDTHH:MM:SS+00:00 (do not convert to another timezone)
+- If the input names a day but no clock time, output only the date: YYYY-MM-DD
 
-Extract: type (task/event), title, datetime (ISO 8601 with UTC timezone), tags (array), priority (low/medium/high/urgent).
+Output an object with an "items" array. Each item has: type (task/event/email), title, datetime (ISO 8601, local clock time), tags (array), priority (low/medium/high/urgent).
+Items about writing an email also have type "email", action (email/reply/follow up), recipient and subject.
+If the input joins separate actions ("X and Y", "X, then Y"), output one item per action; otherwise output exactly one item.
 
 Examples:
 Input: "Submit report tomorrow at 3pm #work"
-Output: {{"type": "task", "title": "Submit report", "datetime": "{}T15:00:00+00:00", "tags": ["work"], "priority": "medium"}}
+Output: {{"items": [{{"type": "task", "title": "Submit report", "datetime": "{}T15:00:00+00:00", "tags": ["work"], "priority": "medium"}}]}}
 
 Input: "Meeting at 4:12 PM #important"
-Output: {{"type": "task", "title": "Meeting", "datetime": "{}T16:12:00+00:00", "tags": ["important"], "priority": "medium"}}
+Output: {{"items": [{{"type": "task", "title": "Meeting", "datetime": "{}T16:12:00+00:00", "tags": ["important"], "priority": "medium"}}]}}
 
 Input: "Call John at 9:30 AM tomorrow"
-Output: {{"type": "task", "title": "Call John", "datetime": "{}T09:30:00+00:00", "tags": [], "priority": "medium"}}
+Output: {{"items": [{{"type": "task", "title": "Call John", "datetime": "{}T09:30:00+00:00", "tags": [], "priority": "medium"}}]}}
+
+Input: "Essay due tomorrow #school"
+Output: {{"items": [{{"type": "task", "title": "Essay due", "datetime": "{}", "tags": ["school"], "priority": "medium"}}]}}
+
+Input: "email John about the report tomorrow"
+Output: {{"items": [{{"type": "email", "title": "Email John", "action": "email", "recipient": "John", "subject": "the report", "datetime": "{}", "tags": [], "priority": "medium"}}]}}
+
+Input: "email Sam and schedule the review for 3pm tomorrow"
+Output: {{"items": [{{"type": "email", "title": "Email Sam", "action": "email", "recipient": "Sam", "subject": null, "datetime": null, "tags": [], "priority": "medium"}}, {{"type": "task", "title": "Schedule the review", "datetime": "{}T15:00:00+00:00", "tags": [], "priority": "medium"}}]}}
 
 Now parse: "{}"
 Output (ONLY valid JSON, no explanations):"#,
-            today, tomorrow, today, tomorrow, input
+            today, tomorrow, today, tomorrow, tomorrow, tomorrow, tomorrow, input
         )
     }
 
-    fn parse_response(&self, response: &str) -> Result<ParsedItem, OllamaError> {
-        let structured: StructuredOutput =
+    /// Items that fail to convert are dropped; the response is only an error
+    /// when nothing usable remains
+    fn parse_response(&self, response: &str) -> Result<Vec<ParsedItem>, OllamaError> {
+        let structured: StructuredResponse =
             serde_json::from_str(response).map_err(|e| OllamaError::ParseError(e.to_string()))?;
+        let outputs = match structured {
+            StructuredResponse::Wrapped { items } | StructuredResponse::Many(items) => items,
+            StructuredResponse::One(item) => vec![item],
+        };
 
-        let datetime = structured
-            .datetime
-            .and_then(|dt| chrono::DateTime::parse_from_rfc3339(&dt).ok())
-            .map(|dt| dt.with_timezone(&chrono::Utc));
+        let mut items = Vec::new();
+        let mut first_error = None;
+        for output in outputs {
+            match self.parse_item(output) {
+                Ok(item) => items.push(item),
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
 
-        let priority = match structured.priority.as_deref() {
-            Some("urgent") => Priority::Urgent,
-            Some("high") => Priority::High,
-            Some("low") => Priority::Low,
-            _ => Priority::Medium,
+        if items.is_empty() {
+            return Err(first_error
+                .unwrap_or_else(|| OllamaError::ParseError("No items in response".to_string())));
+        }
+        Ok(items)
+    }
+
+    fn parse_item(&self, structured: StructuredOutput) -> Result<ParsedItem, OllamaError> {
+        let (datetime, has_time) = match structured.datetime.as_deref().and_then(parse_datetime) {
+            Some((dt, _)) if dt < Utc::now() - Duration::days(MAX_PAST_DAYS) => {
+                tracing::warn!(
+                    "⚠️  Ignoring implausible date {} from Ollama",
+                    dt.with_timezone(&timezone::zone()).format("%Y-%m-%d %H:%M")
+                );
+                (None, false)
+            }
+            Some((dt, has_time)) => (Some(dt), has_time),
+            None => (None, false),
         };
 
+        let priority = structured
+            .priority
+            .as_deref()
+            .and_then(Priority::from_name)
+            .unwrap_or(Priority::Medium);
+
         let tags = structured.tags.unwrap_or_default();
 
         match structured.item_type.as_str() {
@@ -130,6 +323,7 @@ Output (ONLY valid JSON, no explanations):"#,
                 tags,
                 priority,
                 is_scheduled: datetime.is_some(),
+                has_time,
             })),
             "event" => Ok(ParsedItem::Event(Event {
                 title: structured.title,
@@ -139,7 +333,28 @@ Output (ONLY valid JSON, no explanations):"#,
                 end_time: None,
                 location: None,
                 tags,
+                has_time,
             })),
+            "email" => {
+                let recipient = structured
+                    .recipient
+                    .filter(|r| !r.trim().is_empty())
+                    .ok_or_else(|| {
+                        OllamaError::ParseError("Emails require a recipient".to_string())
+                    })?;
+                Ok(ParsedItem::Email(EmailAction {
+                    action: structured
+                        .action
+                        .filter(|a| !a.trim().is_empty())
+                        .unwrap_or_else(|| "email".to_string()),
+                    recipient,
+                    subject: structured.subject.filter(|s| !s.trim().is_empty()),
+                    scheduled_time: datetime,
+                    has_time,
+                    tags,
+                    priority,
+                }))
+            }
             _ => Err(OllamaError::ParseError(format!(
                 "Unknown type: {}",
                 structured.item_type
@@ -147,19 +362,58 @@ Output (ONLY valid JSON, no explanations):"#,
         }
     }
 
+    /// Server URL and model name, for `triptych --version`
+    pub fn endpoint(&self) -> (&str, &str) {
+        (&self.base_url, &self.model)
+    }
+
     pub async fn health_check(&self) -> bool {
         self.client
-            .get(format!("{}/api/tags", OLLAMA_BASE_URL))
+            .get(format!("{}/api/tags", self.base_url))
             .send()
             .await
             .is_ok()
     }
 }
 
+/// OLLAMA_HOST may be a bare "host:port" (as the Ollama CLI accepts) or a URL
+fn normalize_host(host: &str) -> String {
+    let host = host.trim().trim_end_matches('/');
+    if host.contains("://") {
+        host.to_string()
+    } else {
+        format!("http://{}", host)
+    }
+}
+
+/// Accept a full RFC 3339 timestamp, or a bare date (no clock time given),
+/// which defaults to 9am in the planning zone like the rule parser's date-only phrases.
+///
+/// The prompt has the model write the user's clock time ("3pm" becomes
+/// `T15:00:00+00:00`), so the offset is ignored and the time is read in the planning zone
+fn parse_datetime(value: &str) -> Option<(DateTime<Utc>, bool)> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        let local = dt
+            .naive_local()
+            .and_local_timezone(timezone::zone())
+            .earliest()?;
+        return Some((local.with_timezone(&Utc), true));
+    }
+
+    let date = NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d").ok()?;
+    let local = date
+        .and_hms_opt(9, 0, 0)?
+        .and_local_timezone(timezone::zone())
+        .single()?;
+    Some((local.with_timezone(&Utc), false))
+}
+
 #[derive(Debug)]
 #[allow(dead_code)]
 pub enum OllamaError {
     Timeout,
+    /// Streaming stopped producing tokens
+    Stalled,
     Request(ReqwestError),
     ParseError(String),
     ServiceUnavailable,
@@ -169,6 +423,7 @@ impl std::fmt::Display for OllamaError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             OllamaError::Timeout => write!(f, "Ollama request timed out"),
+            OllamaError::Stalled => write!(f, "Ollama stopped sending tokens"),
             OllamaError::Request(e) => write!(f, "Request error: {}", e),
             OllamaError::ParseError(e) => write!(f, "Parse error: {}", e),
             OllamaError::ServiceUnavailable => write!(f, "Ollama service unavailable"),
@@ -177,3 +432,141 @@ impl std::fmt::Display for OllamaError {
 }
 
 impl std::error::Error for OllamaError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveTime;
+
+    #[test]
+    fn rfc3339_datetime_keeps_the_clock_time_as_local() {
+        let (dt, has_time) = parse_datetime("2026-10-16T15:00:00+00:00").unwrap();
+        let local = dt.with_timezone(&timezone::zone());
+        assert!(has_time);
+        assert_eq!(
+            local.date_naive(),
+            NaiveDate::from_ymd_opt(2026, 10, 16).unwrap()
+        );
+        assert_eq!(local.time(), NaiveTime::from_hms_opt(15, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn date_only_datetime_defaults_to_nine_local() {
+        let (dt, has_time) = parse_datetime("2026-10-16").unwrap();
+        assert!(!has_time);
+        assert_eq!(
+            dt.with_timezone(&timezone::zone()).time(),
+            NaiveTime::from_hms_opt(9, 0, 0).unwrap()
+        );
+    }
+
+    fn client() -> OllamaClient {
+        OllamaClient::new(None)
+    }
+
+    #[test]
+    fn malformed_json_is_a_parse_error() {
+        let result = client().parse_response(r#"{"items": [{"type": "task", "title": "#);
+        assert!(matches!(result, Err(OllamaError::ParseError(_))));
+    }
+
+    #[test]
+    fn unknown_types_are_dropped() {
+        let only_unknown =
+            client().parse_response(r#"{"items": [{"type": "poem", "title": "Ode"}]}"#);
+        assert!(matches!(only_unknown, Err(OllamaError::ParseError(e)) if e.contains("poem")));
+
+        let mixed = client()
+            .parse_response(
+                r#"{"items": [{"type": "poem", "title": "Ode"}, {"type": "task", "title": "Buy milk"}]}"#,
+            )
+            .unwrap();
+        assert_eq!(mixed.len(), 1);
+        assert!(matches!(&mixed[0], ParsedItem::Task(task) if task.title == "Buy milk"));
+    }
+
+    #[test]
+    fn empty_items_is_an_error() {
+        let result = client().parse_response(r#"{"items": []}"#);
+        assert!(matches!(result, Err(OllamaError::ParseError(e)) if e == "No items in response"));
+    }
+
+    #[test]
+    fn implausible_past_dates_are_dropped() {
+        let items = client()
+            .parse_response(
+                r#"{"items": [{"type": "task", "title": "Submit report", "datetime": "2019-03-01T15:00:00+00:00"}]}"#,
+            )
+            .unwrap();
+        let ParsedItem::Task(task) = &items[0] else {
+            panic!("expected a task");
+        };
+        assert!(task.due_date.is_none());
+        assert!(!task.has_time);
+        assert!(!task.is_scheduled);
+    }
+
+    #[test]
+    fn emails_require_a_recipient() {
+        for recipient in [r#""#, r#", "recipient": null"#, r#", "recipient": "  ""#] {
+            let response = format!(
+                r#"{{"items": [{{"type": "email", "title": "Email someone"{}}}]}}"#,
+                recipient
+            );
+            let result = client().parse_response(&response);
+            assert!(
+                matches!(result, Err(OllamaError::ParseError(e)) if e.contains("recipient")),
+                "accepted {}",
+                response
+            );
+        }
+    }
+
+    #[test]
+    fn email_action_defaults_to_email() {
+        let items = client()
+            .parse_response(
+                r#"{"items": [{"type": "email", "title": "Email Sam", "recipient": "Sam", "action": " ", "subject": ""}]}"#,
+            )
+            .unwrap();
+        let ParsedItem::Email(email) = &items[0] else {
+            panic!("expected an email");
+        };
+        assert_eq!(email.action, "email");
+        assert_eq!(email.recipient, "Sam");
+        assert!(email.subject.is_none());
+        assert!(email.scheduled_time.is_none());
+    }
+
+    #[test]
+    fn email_with_date_only_is_untimed() {
+        let date = (timezone::now() + Duration::days(1)).date_naive();
+        let response = format!(
+            r#"{{"items": [{{"type": "email", "title": "Email John", "recipient": "John", "datetime": "{}"}}]}}"#,
+            date.format("%Y-%m-%d")
+        );
+        let items = client().parse_response(&response).unwrap();
+        let ParsedItem::Email(email) = &items[0] else {
+            panic!("expected an email");
+        };
+        let scheduled = email
+            .scheduled_time
+            .unwrap()
+            .with_timezone(&timezone::zone());
+        assert_eq!(scheduled.date_naive(), date);
+        assert!(!email.has_time);
+    }
+
+    #[test]
+    fn ollama_items_carry_has_time() {
+        let date = (timezone::now() + Duration::days(1)).date_naive();
+        let response = format!(
+            r#"{{"items": [{{"type": "task", "title": "Essay", "datetime": "{0}"}}, {{"type": "event", "title": "Seminar", "datetime": "{0}T15:00:00+00:00"}}]}}"#,
+            date.format("%Y-%m-%d")
+        );
+        let items = client().parse_response(&response).unwrap();
+
+        assert!(matches!(&items[0], ParsedItem::Task(task) if !task.has_time));
+        assert!(matches!(&items[1], ParsedItem::Event(event) if event.has_time));
+    }
+}
@@ -1,7 +1,10 @@
 use crate::nlp::ollama_client::OllamaClient;
 use crate::nlp::rules::RuleParser;
-use crate::nlp::types::{ParseResult, ParseStrategy, ParsedItem};
+use crate::nlp::types::{CacheEntry, ParseResult, ParseStrategy, ParsedItem};
+use crate::timezone;
+use chrono::NaiveDateTime;
 use lru::LruCache;
+use std::collections::{HashMap, HashSet};
 use std::num::NonZeroUsize;
 use std::time::Instant;
 use strsim::jaro_winkler;
@@ -10,15 +13,148 @@ use tokio::sync::Mutex;
 pub struct NLPParser {
     ollama_client: OllamaClient,
     ollama_available: bool,
-    cache: Mutex<LruCache<String, CachedParse>>,
+    cache: Mutex<ParseCache>,
+}
+
+/// Most fuzzy comparisons made per lookup, so a miss stays cheap on a full cache
+const MAX_FUZZY_COMPARISONS: usize = 32;
+
+/// LRU of parses plus an index of cached inputs by their first word. Fuzzy
+/// lookups only compare against inputs sharing the query's first word instead
+/// of scanning the whole cache. Keys are normalized with `cache_key`.
+struct ParseCache {
+    entries: LruCache<String, CachedParse>,
+    by_first_token: HashMap<String, HashSet<String>>,
+}
+
+impl ParseCache {
+    fn new(capacity: NonZeroUsize) -> Self {
+        Self {
+            entries: LruCache::new(capacity),
+            by_first_token: HashMap::new(),
+        }
+    }
+
+    fn get(&mut self, input: &str) -> Option<&CachedParse> {
+        self.entries.get(input)
+    }
+
+    fn put(&mut self, input: String, parse: CachedParse) {
+        if let Some((evicted, _)) = self.entries.push(input.clone(), parse)
+            && evicted != input
+        {
+            self.unindex(&evicted);
+        }
+        self.by_first_token
+            .entry(first_token(&input))
+            .or_default()
+            .insert(input);
+    }
+
+    fn pop(&mut self, input: &str) {
+        if self.entries.pop(input).is_some() {
+            self.unindex(input);
+        }
+    }
+
+    fn unindex(&mut self, input: &str) {
+        let token = first_token(input);
+        if let Some(inputs) = self.by_first_token.get_mut(&token) {
+            inputs.remove(input);
+            if inputs.is_empty() {
+                self.by_first_token.remove(&token);
+            }
+        }
+    }
+
+    /// Cached inputs worth a fuzzy comparison against `input`, at most
+    /// `MAX_FUZZY_COMPARISONS` of them
+    fn fuzzy_candidates<'a>(
+        &'a self,
+        input: &str,
+    ) -> impl Iterator<Item = (&'a String, &'a CachedParse)> {
+        self.by_first_token
+            .get(&first_token(input))
+            .into_iter()
+            .flatten()
+            .filter_map(|key| self.entries.peek(key).map(|parse| (key, parse)))
+            .take(MAX_FUZZY_COMPARISONS)
+    }
+}
+
+fn first_token(key: &str) -> String {
+    key.split(' ').next().unwrap_or_default().to_string()
+}
+
+/// Cache key for an input: lowercased, trimmed, internal whitespace collapsed,
+/// so "Buy  milk" and "buy milk" share an entry. A hit re-cases the cached
+/// titles to match the new input (see `recase`).
+fn cache_key(input: &str) -> String {
+    input
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// `text` taken from `cached_input`, with the casing the same words have in
+/// `input`. The two share a cache key, so only case and spacing differ. Text
+/// that isn't a run of the cached input's words (Ollama may reword a title)
+/// is left as it is.
+fn recase(text: &mut String, cached_input: &str, input: &str) {
+    let cached: Vec<&str> = cached_input.split_whitespace().collect();
+    let current: Vec<&str> = input.split_whitespace().collect();
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.is_empty() || cached.len() != current.len() {
+        return;
+    }
+    if let Some(start) = cached.windows(words.len()).position(|run| run == words) {
+        *text = current[start..start + words.len()].join(" ");
+    }
+}
+
+fn recase_item(item: &mut ParsedItem, cached_input: &str, input: &str) {
+    match item {
+        ParsedItem::Task(task) => recase(&mut task.title, cached_input, input),
+        ParsedItem::Event(event) => recase(&mut event.title, cached_input, input),
+        ParsedItem::Email(email) => {
+            recase(&mut email.recipient, cached_input, input);
+            if let Some(subject) = &mut email.subject {
+                recase(subject, cached_input, input);
+            }
+        }
+    }
 }
 
 #[derive(Clone)]
 struct CachedParse {
+    /// The input as typed, which may differ from its key in case and spacing
+    input: String,
     item: ParsedItem,
+    extra_items: Vec<ParsedItem>,
     strategy: ParseStrategy,
     confidence: f32,
-    cached_at: Instant,
+    /// Local wall-clock time the entry was parsed
+    cached_at: NaiveDateTime,
+}
+
+impl CachedParse {
+    fn has_date(&self) -> bool {
+        std::iter::once(&self.item)
+            .chain(&self.extra_items)
+            .any(|item| match item {
+                ParsedItem::Task(task) => task.due_date.is_some(),
+                ParsedItem::Event(_) => true,
+                ParsedItem::Email(email) => email.scheduled_time.is_some(),
+            })
+    }
+
+    /// Relative dates ("tomorrow at 3pm") were resolved against the day the
+    /// entry was cached, so entries carrying a date expire at midnight.
+    /// Entries without one only leave through LRU eviction.
+    fn is_expired(&self, now: NaiveDateTime) -> bool {
+        self.has_date() && self.cached_at.date() != now.date()
+    }
 }
 
 impl NLPParser {
@@ -27,30 +163,47 @@ impl NLPParser {
         let ollama_available = ollama_client.health_check().await;
 
         if !ollama_available {
-            eprintln!("Warning: Ollama service not available. Falling back to regex-only parsing.");
+            tracing::warn!(
+                "Warning: Ollama service not available. Falling back to regex-only parsing."
+            );
         }
 
         Self {
             ollama_client,
             ollama_available,
-            cache: Mutex::new(LruCache::new(NonZeroUsize::new(1000).unwrap())),
+            cache: Mutex::new(ParseCache::new(NonZeroUsize::new(1000).unwrap())),
         }
     }
 
     pub async fn parse(&self, input: &str) -> Result<ParseResult, ParseError> {
         let start = Instant::now();
 
+        let now = timezone::now().naive_local();
+        let key = cache_key(input);
+
         // Layer 0: Check exact cache match first (hold lock briefly)
         let cache_hit = {
             let mut cache = self.cache.lock().await;
-            cache.get(input).cloned() // Clone while lock is held
+            match cache.get(&key) {
+                Some(cached) if cached.is_expired(now) => {
+                    cache.pop(&key);
+                    None
+                }
+                cached => cached.cloned(), // Clone while lock is held
+            }
         };
 
-        if let Some(cached) = cache_hit {
+        if let Some(mut cached) = cache_hit {
             let elapsed = start.elapsed().as_millis() as u64;
-            eprintln!("⚡ Exact cache hit!"); // Changed to eprintln!
+            tracing::debug!("⚡ Exact cache hit!");
+            if cached.input != input {
+                for item in std::iter::once(&mut cached.item).chain(&mut cached.extra_items) {
+                    recase_item(item, &cached.input, input);
+                }
+            }
             return Ok(ParseResult {
                 item: cached.item,
+                extra_items: cached.extra_items,
                 strategy: ParseStrategy::Cached,
                 confidence: cached.confidence,
                 parse_time_ms: elapsed,
@@ -62,24 +215,31 @@ impl NLPParser {
         let fuzzy_match = {
             let cache = self.cache.lock().await;
 
-            // Early exit optimization: don't check if input is very short
-            if input.len() < 3 {
+            // Early exit optimization: don't check if input is very short.
+            // Dates are never approximated: "meeting tomorrow" must not reuse
+            // "meeting today", and "buy milk" must not pick up a cached date.
+            if key.len() < 3 || RuleParser::has_temporal(input) {
                 None
             } else {
-                cache.iter().find_map(|(cached_input, cached_parse)| {
-                    let similarity = jaro_winkler(input, cached_input);
-                    if similarity > similarity_threshold {
-                        Some((cached_input.clone(), cached_parse.clone(), similarity))
-                    } else {
-                        None
-                    }
-                })
+                cache
+                    .fuzzy_candidates(&key)
+                    .find_map(|(cached_input, cached_parse)| {
+                        if cached_parse.has_date() {
+                            return None;
+                        }
+                        let similarity = jaro_winkler(&key, cached_input);
+                        if similarity > similarity_threshold {
+                            Some((cached_input.clone(), cached_parse.clone(), similarity))
+                        } else {
+                            None
+                        }
+                    })
             }
         };
 
         if let Some((matched_input, cached_parse, similarity)) = fuzzy_match {
             let elapsed = start.elapsed().as_millis() as u64;
-            eprintln!(
+            tracing::debug!(
                 "🔍 Similar pattern found ({:.0}% match): \"{}\"",
                 similarity * 100.0,
                 matched_input
@@ -88,6 +248,7 @@ impl NLPParser {
             let adjusted_confidence = cached_parse.confidence * similarity as f32;
             return Ok(ParseResult {
                 item: cached_parse.item,
+                extra_items: cached_parse.extra_items,
                 strategy: ParseStrategy::Cached,
                 confidence: adjusted_confidence,
                 parse_time_ms: elapsed,
@@ -100,6 +261,7 @@ impl NLPParser {
 
             let result = ParseResult {
                 item: item.clone(),
+                extra_items: Vec::new(),
                 strategy: ParseStrategy::Regex,
                 confidence: 0.95,
                 parse_time_ms: elapsed,
@@ -109,12 +271,14 @@ impl NLPParser {
             {
                 let mut cache = self.cache.lock().await;
                 cache.put(
-                    input.to_string(),
+                    key,
                     CachedParse {
+                        input: input.to_string(),
                         item,
+                        extra_items: Vec::new(),
                         strategy: ParseStrategy::Regex,
                         confidence: 0.95,
-                        cached_at: Instant::now(),
+                        cached_at: timezone::now().naive_local(),
                     },
                 );
             }
@@ -125,11 +289,14 @@ impl NLPParser {
         // Layer 2: Try Ollama for complex parsing
         if self.ollama_available {
             match self.ollama_client.parse(input).await {
-                Ok(item) => {
+                Ok(mut items) => {
                     let elapsed = start.elapsed().as_millis() as u64;
+                    // The client never returns an empty list
+                    let item = items.remove(0);
 
                     let result = ParseResult {
                         item: item.clone(),
+                        extra_items: items.clone(),
                         strategy: ParseStrategy::Ollama,
                         confidence: 0.85,
                         parse_time_ms: elapsed,
@@ -139,12 +306,14 @@ impl NLPParser {
                     {
                         let mut cache = self.cache.lock().await;
                         cache.put(
-                            input.to_string(),
+                            key,
                             CachedParse {
+                                input: input.to_string(),
                                 item,
+                                extra_items: items,
                                 strategy: ParseStrategy::Ollama,
                                 confidence: 0.85,
-                                cached_at: Instant::now(),
+                                cached_at: timezone::now().naive_local(),
                             },
                         );
                     }
@@ -152,7 +321,7 @@ impl NLPParser {
                     return Ok(result);
                 }
                 Err(e) => {
-                    eprintln!("Ollama parsing failed: {}. Falling back.", e);
+                    tracing::warn!("Ollama parsing failed: {}. Falling back.", e);
                 }
             }
         }
@@ -166,10 +335,12 @@ impl NLPParser {
             tags: vec![],
             priority: crate::nlp::types::Priority::Medium,
             is_scheduled: false,
+            has_time: false,
         });
 
         let result = ParseResult {
             item: item.clone(),
+            extra_items: Vec::new(),
             strategy: ParseStrategy::Fallback,
             confidence: 0.50,
             parse_time_ms: elapsed,
@@ -179,12 +350,14 @@ impl NLPParser {
         {
             let mut cache = self.cache.lock().await;
             cache.put(
-                input.to_string(),
+                key,
                 CachedParse {
+                    input: input.to_string(),
                     item,
+                    extra_items: Vec::new(),
                     strategy: ParseStrategy::Fallback,
                     confidence: 0.50,
-                    cached_at: Instant::now(),
+                    cached_at: timezone::now().naive_local(),
                 },
             );
         }
@@ -196,9 +369,54 @@ impl NLPParser {
         self.ollama_available
     }
 
+    /// Snapshot up to `limit` unexpired cache entries, most recently used first
+    pub async fn export_cache(&self, limit: usize) -> Vec<CacheEntry> {
+        let now = timezone::now().naive_local();
+        let cache = self.cache.lock().await;
+        cache
+            .entries
+            .iter()
+            .filter(|(_, cached)| !cached.is_expired(now))
+            .take(limit)
+            .map(|(_, cached)| CacheEntry {
+                input: cached.input.clone(),
+                item: cached.item.clone(),
+                extra_items: cached.extra_items.clone(),
+                strategy: cached.strategy.clone(),
+                confidence: cached.confidence,
+                cached_at: Some(cached.cached_at),
+            })
+            .collect()
+    }
+
+    /// Seed the cache from an exported snapshot, preserving its recency order.
+    /// Entries that have expired since they were parsed are skipped.
+    pub async fn import_cache(&self, entries: Vec<CacheEntry>) -> usize {
+        let now = timezone::now().naive_local();
+        let mut cache = self.cache.lock().await;
+        let mut count = 0;
+        for entry in entries.into_iter().rev() {
+            let key = cache_key(&entry.input);
+            let cached = CachedParse {
+                input: entry.input,
+                item: entry.item,
+                extra_items: entry.extra_items,
+                strategy: entry.strategy,
+                confidence: entry.confidence,
+                cached_at: entry.cached_at.unwrap_or(now),
+            };
+            if cached.is_expired(now) {
+                continue;
+            }
+            cache.put(key, cached);
+            count += 1;
+        }
+        count
+    }
+
     pub async fn cache_stats(&self) -> (usize, usize) {
         let cache = self.cache.lock().await;
-        (cache.len(), cache.cap().get())
+        (cache.entries.len(), cache.entries.cap().get())
     }
 }
 
@@ -217,3 +435,315 @@ impl std::fmt::Display for ParseError {
 }
 
 impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    /// A parser that never talks to Ollama, whatever is running locally
+    fn offline_parser() -> NLPParser {
+        NLPParser {
+            ollama_client: OllamaClient::new(None),
+            ollama_available: false,
+            cache: Mutex::new(ParseCache::new(NonZeroUsize::new(1000).unwrap())),
+        }
+    }
+
+    fn cached_task(title: &str, dated: bool, cached_at: NaiveDateTime) -> CachedParse {
+        CachedParse {
+            input: title.to_string(),
+            item: ParsedItem::Task(crate::nlp::types::Task {
+                title: title.to_string(),
+                due_date: dated.then(|| cached_at.and_utc()),
+                tags: vec![],
+                priority: crate::nlp::types::Priority::Medium,
+                is_scheduled: dated,
+                has_time: dated,
+            }),
+            extra_items: Vec::new(),
+            strategy: ParseStrategy::Regex,
+            confidence: 0.95,
+            cached_at,
+        }
+    }
+
+    fn at(h: u32, m: u32) -> NaiveDateTime {
+        chrono::NaiveDate::from_ymd_opt(2026, 10, 16)
+            .unwrap()
+            .and_hms_opt(h, m, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn dated_entries_expire_at_midnight() {
+        let parse = cached_task("call mom", true, at(23, 0));
+
+        assert!(!parse.is_expired(at(23, 59)));
+        assert!(parse.is_expired(at(23, 59) + Duration::minutes(2)));
+    }
+
+    #[test]
+    fn undated_entries_never_expire() {
+        let parse = cached_task("buy milk", false, at(23, 0));
+
+        assert!(!parse.is_expired(at(23, 0) + Duration::days(3)));
+    }
+
+    #[tokio::test]
+    async fn tomorrow_parse_misses_the_cache_after_midnight() {
+        let parser = offline_parser();
+        let input = "call mom tomorrow at 3pm";
+
+        parser.parse(input).await.unwrap();
+        let hit = parser.parse(input).await.unwrap();
+        assert!(matches!(hit.strategy, ParseStrategy::Cached));
+
+        // Moving the entry back a day is the same as the clock passing midnight
+        {
+            let mut cache = parser.cache.lock().await;
+            let entry = cache.entries.peek_mut(&cache_key(input)).unwrap();
+            entry.cached_at -= Duration::days(1);
+        }
+        let after_midnight = parser.parse(input).await.unwrap();
+        assert!(matches!(after_midnight.strategy, ParseStrategy::Regex));
+    }
+
+    #[tokio::test]
+    async fn dated_inputs_never_fuzzy_match() {
+        let parser = offline_parser();
+
+        parser.parse("meeting today").await.unwrap();
+        let tomorrow = parser.parse("meeting tomorrow").await.unwrap();
+
+        assert!(!matches!(tomorrow.strategy, ParseStrategy::Cached));
+        let ParsedItem::Task(task) = tomorrow.item else {
+            panic!("expected a task");
+        };
+        let due = task
+            .due_date
+            .unwrap()
+            .with_timezone(&timezone::zone())
+            .date_naive();
+        assert_eq!(due, timezone::now().date_naive() + Duration::days(1));
+    }
+
+    #[tokio::test]
+    async fn undated_typos_fuzzy_match() {
+        let parser = offline_parser();
+
+        parser.parse("buy milk").await.unwrap();
+        let typo = parser.parse("buy milkk").await.unwrap();
+
+        assert!(matches!(typo.strategy, ParseStrategy::Cached));
+    }
+
+    /// Every cached input is indexed under its first word, and nothing else is
+    fn assert_index_in_sync(cache: &ParseCache) {
+        let indexed: usize = cache.by_first_token.values().map(HashSet::len).sum();
+        assert_eq!(indexed, cache.entries.len());
+        for (token, inputs) in &cache.by_first_token {
+            assert!(!inputs.is_empty());
+            for input in inputs {
+                assert_eq!(&first_token(input), token);
+                assert!(cache.entries.contains(input));
+            }
+        }
+    }
+
+    #[test]
+    fn fuzzy_candidates_are_bounded_on_a_full_cache() {
+        let mut cache = ParseCache::new(NonZeroUsize::new(1000).unwrap());
+        for i in 0..1000 {
+            let input = format!("buy item {}", i);
+            cache.put(input.clone(), cached_task(&input, false, at(9, 0)));
+        }
+
+        assert_eq!(
+            cache.fuzzy_candidates("buy item 5000").count(),
+            MAX_FUZZY_COMPARISONS
+        );
+        assert_eq!(cache.fuzzy_candidates("call mom").count(), 0);
+        assert_index_in_sync(&cache);
+    }
+
+    #[test]
+    fn first_token_index_follows_eviction_and_pop() {
+        let mut cache = ParseCache::new(NonZeroUsize::new(2).unwrap());
+        for input in ["buy milk", "buy eggs", "call mom"] {
+            cache.put(input.to_string(), cached_task(input, false, at(9, 0)));
+        }
+        // "buy milk" was evicted to make room
+        assert!(!cache.entries.contains("buy milk"));
+        assert_index_in_sync(&cache);
+
+        // Replacing an entry in place keeps it indexed
+        cache.put(
+            "call mom".to_string(),
+            cached_task("call mom", false, at(10, 0)),
+        );
+        assert_index_in_sync(&cache);
+
+        cache.pop("buy eggs");
+        assert!(!cache.by_first_token.contains_key("buy"));
+        assert_index_in_sync(&cache);
+
+        cache.pop("not cached");
+        assert_index_in_sync(&cache);
+    }
+
+    #[test]
+    fn cache_key_ignores_case_and_spacing() {
+        assert_eq!(cache_key("Buy milk"), "buy milk");
+        assert_eq!(cache_key(" buy  milk "), "buy milk");
+        assert_eq!(cache_key("buy\tmilk"), "buy milk");
+    }
+
+    #[tokio::test]
+    async fn differently_typed_inputs_share_a_cache_entry() {
+        let parser = offline_parser();
+
+        parser.parse("Buy milk").await.unwrap();
+        for input in ["buy milk", " buy  milk "] {
+            let result = parser.parse(input).await.unwrap();
+            assert!(matches!(result.strategy, ParseStrategy::Cached));
+        }
+        assert_eq!(parser.cache_stats().await.0, 1);
+    }
+
+    fn title(item: &ParsedItem) -> &str {
+        match item {
+            ParsedItem::Task(task) => &task.title,
+            other => panic!("expected a task, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn cache_hit_keeps_the_casing_of_the_new_input() {
+        let parser = offline_parser();
+
+        let first = parser.parse("Buy milk").await.unwrap();
+        assert_eq!(title(&first.item), "Buy milk");
+
+        let second = parser.parse("buy  MILK").await.unwrap();
+        assert!(matches!(second.strategy, ParseStrategy::Cached));
+        assert_eq!(title(&second.item), "buy MILK");
+
+        parser.parse("Call Mom tomorrow").await.unwrap();
+        let dated = parser.parse("call mom tomorrow").await.unwrap();
+        assert!(matches!(dated.strategy, ParseStrategy::Cached));
+        assert_eq!(title(&dated.item), "call mom");
+    }
+
+    fn has_time(item: &ParsedItem) -> bool {
+        match item {
+            ParsedItem::Task(task) => task.has_time,
+            ParsedItem::Event(event) => event.has_time,
+            ParsedItem::Email(email) => email.has_time,
+        }
+    }
+
+    #[tokio::test]
+    async fn rule_layer_sets_has_time() {
+        let parser = offline_parser();
+
+        let date_only = parser.parse("review tomorrow").await.unwrap();
+        assert!(matches!(date_only.strategy, ParseStrategy::Regex));
+        assert!(!has_time(&date_only.item));
+
+        let timed = parser.parse("review tomorrow 3pm").await.unwrap();
+        assert!(matches!(timed.strategy, ParseStrategy::Regex));
+        assert!(has_time(&timed.item));
+
+        let event = parser.parse("workshop tomorrow 3pm-5pm").await.unwrap();
+        assert!(matches!(event.item, ParsedItem::Event(_)));
+        assert!(has_time(&event.item));
+    }
+
+    #[tokio::test]
+    async fn cache_layer_keeps_has_time() {
+        let parser = offline_parser();
+
+        for (input, timed) in [("review tomorrow", false), ("review tomorrow 3pm", true)] {
+            parser.parse(input).await.unwrap();
+            let cached = parser.parse(input).await.unwrap();
+            assert!(matches!(cached.strategy, ParseStrategy::Cached));
+            assert_eq!(has_time(&cached.item), timed, "{}", input);
+        }
+    }
+
+    #[tokio::test]
+    async fn fallback_layer_is_untimed() {
+        let parser = offline_parser();
+
+        let result = parser.parse("  ").await.unwrap();
+        assert!(matches!(result.strategy, ParseStrategy::Fallback));
+        assert!(!has_time(&result.item));
+    }
+
+    #[tokio::test]
+    async fn exported_cache_reimports_in_recency_order() {
+        let source = offline_parser();
+        for input in ["buy milk", "water plants", "call mom tomorrow"] {
+            source.parse(input).await.unwrap();
+        }
+        // Parsed yesterday, so "tomorrow" has already passed
+        {
+            let mut cache = source.cache.lock().await;
+            let entry = cache.entries.peek_mut("call mom tomorrow").unwrap();
+            entry.cached_at -= Duration::days(1);
+        }
+
+        let exported = source.export_cache(100).await;
+        let inputs: Vec<&str> = exported.iter().map(|e| e.input.as_str()).collect();
+        assert_eq!(inputs, ["water plants", "buy milk"]);
+
+        let target = offline_parser();
+        assert_eq!(target.import_cache(exported.clone()).await, 2);
+        let reexported: Vec<String> = target
+            .export_cache(100)
+            .await
+            .into_iter()
+            .map(|e| e.input)
+            .collect();
+        assert_eq!(reexported, ["water plants", "buy milk"]);
+
+        let hit = target.parse("Buy milk").await.unwrap();
+        assert!(matches!(hit.strategy, ParseStrategy::Cached));
+    }
+
+    #[tokio::test]
+    async fn import_skips_entries_that_expired_since_export() {
+        let source = offline_parser();
+        for input in ["buy milk", "call mom tomorrow"] {
+            source.parse(input).await.unwrap();
+        }
+        // Exported yesterday and imported today
+        let mut exported = source.export_cache(100).await;
+        for entry in &mut exported {
+            entry.cached_at = entry.cached_at.map(|at| at - Duration::days(1));
+        }
+
+        let target = offline_parser();
+        assert_eq!(target.import_cache(exported).await, 1);
+        let inputs: Vec<String> = target
+            .export_cache(100)
+            .await
+            .into_iter()
+            .map(|e| e.input)
+            .collect();
+        assert_eq!(inputs, ["buy milk"]);
+    }
+
+    #[tokio::test]
+    async fn export_respects_the_limit() {
+        let parser = offline_parser();
+        for input in ["buy milk", "water plants", "fold laundry"] {
+            parser.parse(input).await.unwrap();
+        }
+
+        let exported = parser.export_cache(2).await;
+        let inputs: Vec<&str> = exported.iter().map(|e| e.input.as_str()).collect();
+        assert_eq!(inputs, ["fold laundry", "water plants"]);
+    }
+}
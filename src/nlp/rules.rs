@@ -1,14 +1,16 @@
 use crate::nlp::types::{Event, ParsedItem, Priority, Task};
-use chrono::{DateTime, Datelike, Duration, Local, Utc};
+use crate::timezone;
+use chrono::{DateTime, Datelike, Duration, Utc};
 use chrono_english::{Dialect, parse_date_string};
+use chrono_tz::Tz;
 use nom::{
     IResult,
     branch::alt,
     bytes::complete::{tag, tag_no_case, take_while1},
-    character::complete::{char, digit1, multispace0, multispace1, space1},
-    combinator::{map, map_res, opt, recognize, value},
+    character::complete::{char, digit1, multispace0, multispace1, satisfy, space1},
+    combinator::{map, map_res, not, opt, recognize, value},
     multi::many0,
-    sequence::{pair, preceded, tuple},
+    sequence::{pair, preceded, terminated, tuple},
 };
 
 // ============================================================================
@@ -29,8 +31,9 @@ enum Segment {
 
 #[derive(Debug, Clone)]
 enum TemporalContext {
-    /// A resolved point in time (tomorrow, next friday, 5pm)
-    Point(DateTime<Utc>),
+    /// A resolved point in time (tomorrow, next friday, 5pm). `has_time` is
+    /// false for date-only phrases, whose clock time is just a default.
+    Point { at: DateTime<Utc>, has_time: bool },
     /// A resolved duration (for 2 hours)
     Duration(Duration),
     /// A time range (3pm-5pm) - implies both point and duration logic
@@ -60,6 +63,16 @@ impl RuleParser {
         Self::assemble(final_segments)
     }
 
+    /// Whether `input` contains any date or time expression the rule parser
+    /// recognizes ("tomorrow", "friday", "3pm", "in 2 hours", ...)
+    pub fn has_temporal(input: &str) -> bool {
+        parse_segments(input).is_ok_and(|(_, segments)| {
+            segments
+                .iter()
+                .any(|segment| matches!(segment, Segment::Temporal(_)))
+        })
+    }
+
     fn assemble(segments: Vec<Segment>) -> Option<ParsedItem> {
         let mut title_parts = Vec::new();
         let mut tags = Vec::new();
@@ -67,6 +80,7 @@ impl RuleParser {
 
         // Temporal assembly state
         let mut start_time: Option<DateTime<Utc>> = None;
+        let mut start_has_time = false;
         let mut end_time: Option<DateTime<Utc>> = None;
         let mut duration: Option<Duration> = None;
 
@@ -76,21 +90,31 @@ impl RuleParser {
                 Segment::Tag(t) => tags.push(t),
                 Segment::Priority(p) => priority = p,
                 Segment::Temporal(temp) => match temp {
-                    TemporalContext::Point(dt) => {
-                        // If we already have a start time, maybe this is end time?
-                        // For now, simpler logic: Last explicit date wins, or first?
-                        // Let's say first explicit date is start.
-                        if start_time.is_none() {
-                            start_time = Some(dt);
-                        } else {
-                            // If we have two points, assume start -> end
-                            end_time = Some(dt);
+                    TemporalContext::Point { at, has_time } => match start_time {
+                        None => {
+                            start_time = Some(at);
+                            start_has_time = has_time;
                         }
-                    }
+                        // "friday 3pm": a clock time fills in an earlier date-only point
+                        Some(date) if !start_has_time && has_time => {
+                            start_time = Some(with_time_of(date, at));
+                            start_has_time = true;
+                        }
+                        // If we have two points, assume start -> end
+                        Some(_) => end_time = Some(at),
+                    },
                     TemporalContext::Duration(d) => duration = Some(d),
                     TemporalContext::Range { start, end } => {
+                        // "tomorrow 3pm-5pm": keep the date, take the times
+                        let (start, end) = match start_time {
+                            Some(date) if !start_has_time => {
+                                (with_time_of(date, start), with_time_of(date, end))
+                            }
+                            _ => (start, end),
+                        };
                         start_time = Some(start);
                         end_time = Some(end);
+                        start_has_time = true;
                     }
                 },
             }
@@ -112,6 +136,7 @@ impl RuleParser {
                     end_time: Some(end),
                     location: None,
                     tags,
+                    has_time: start_has_time,
                 }));
             } else {
                 // It has a start/due date but no duration, likely a Task
@@ -121,6 +146,7 @@ impl RuleParser {
                     tags,
                     priority,
                     is_scheduled: true,
+                    has_time: start_has_time,
                 }));
             }
         }
@@ -137,6 +163,7 @@ impl RuleParser {
             tags,
             priority,
             is_scheduled: false,
+            has_time: false,
         }))
     }
 }
@@ -206,9 +233,9 @@ fn parse_text_segment(input: &str) -> IResult<&str, Segment> {
 fn parse_temporal_segment(input: &str) -> IResult<&str, Segment> {
     // We try various time strategies.
     // Note: We need to pass `now` down for resolution, or use a closure strategy.
-    // For simplicity here, we resolve using Local::now() inside the parser map.
+    // For simplicity here, we resolve using timezone::now() inside the parser map.
 
-    let now = Local::now();
+    let now = timezone::now();
 
     alt((
         // 1. Complex Phrases ("day after tomorrow", "3pm-5pm")
@@ -218,14 +245,18 @@ fn parse_temporal_segment(input: &str) -> IResult<&str, Segment> {
         map(parse_business_time(now), Segment::Temporal),
         // 3. Durations ("in 2 hours", "for 30 mins")
         map(parse_relative_duration(now), Segment::Temporal),
-        // 4. Chrono-English Delegation (Dates, Weekdays, "tomorrow")
+        // 4. Clock times ("at 5pm", "17:30")
+        map(parse_clock_time(now), Segment::Temporal),
+        // 5. Chrono-English Delegation (Dates, Weekdays, "tomorrow")
         // We must identify *valid* chrono strings first so we don't feed random title words
         map_res(parse_chrono_candidate, move |s| {
             // We use map_res to return a Result. If chrono fails, nom backtracks!
             match parse_date_string(s, now, Dialect::Us) {
-                Ok(dt) => Ok(Segment::Temporal(TemporalContext::Point(
-                    dt.with_timezone(&Utc),
-                ))),
+                // Candidates are date-only, so default the time to 9am
+                Ok(dt) => Ok(Segment::Temporal(TemporalContext::Point {
+                    at: at_default_hour(dt.with_timezone(&timezone::zone())),
+                    has_time: false,
+                })),
                 Err(_) => Err("chrono parse failed"),
             }
         }),
@@ -234,7 +265,7 @@ fn parse_temporal_segment(input: &str) -> IResult<&str, Segment> {
 
 /// Matches "day after tomorrow" specifically
 fn parse_day_after_tomorrow(
-    now: DateTime<Local>,
+    now: DateTime<Tz>,
 ) -> impl FnMut(&str) -> IResult<&str, TemporalContext> {
     move |input| {
         let (input, _) = tuple((
@@ -246,19 +277,18 @@ fn parse_day_after_tomorrow(
         ))(input)?;
 
         let target = now + Duration::days(2);
-        // Default to 9am
-        let dt = target
-            .date_naive()
-            .and_hms_opt(9, 0, 0)
-            .unwrap()
-            .and_local_timezone(Local)
-            .unwrap();
 
-        Ok((input, TemporalContext::Point(dt.with_timezone(&Utc))))
+        Ok((
+            input,
+            TemporalContext::Point {
+                at: at_default_hour(target),
+                has_time: false,
+            },
+        ))
     }
 }
 
-fn parse_time_range(now: DateTime<Local>) -> impl FnMut(&str) -> IResult<&str, TemporalContext> {
+fn parse_time_range(now: DateTime<Tz>) -> impl FnMut(&str) -> IResult<&str, TemporalContext> {
     move |input| {
         let (input, (start_h, start_m, start_ampm)) = parse_loose_time(input)?;
         let (input, _) = tuple((multispace0, alt((tag("-"), tag("–"))), multispace0))(input)?;
@@ -277,14 +307,14 @@ fn parse_time_range(now: DateTime<Local>) -> impl FnMut(&str) -> IResult<&str, T
             .date_naive()
             .and_hms_opt(s_hour, start_m, 0)
             .unwrap()
-            .and_local_timezone(Local)
+            .and_local_timezone(timezone::zone())
             .unwrap();
 
         let end_dt = now
             .date_naive()
             .and_hms_opt(e_hour, end_m, 0)
             .unwrap()
-            .and_local_timezone(Local)
+            .and_local_timezone(timezone::zone())
             .unwrap();
 
         Ok((
@@ -297,7 +327,7 @@ fn parse_time_range(now: DateTime<Local>) -> impl FnMut(&str) -> IResult<&str, T
     }
 }
 
-fn parse_business_time(now: DateTime<Local>) -> impl FnMut(&str) -> IResult<&str, TemporalContext> {
+fn parse_business_time(now: DateTime<Tz>) -> impl FnMut(&str) -> IResult<&str, TemporalContext> {
     move |input| {
         let (input, token) = alt((
             tag_no_case("eod"),
@@ -311,7 +341,7 @@ fn parse_business_time(now: DateTime<Local>) -> impl FnMut(&str) -> IResult<&str
                 .date_naive()
                 .and_hms_opt(17, 0, 0)
                 .unwrap()
-                .and_local_timezone(Local)
+                .and_local_timezone(timezone::zone())
                 .unwrap(),
             "eow" => {
                 let days_until_fri = (4i64 - now.weekday().num_days_from_monday() as i64 + 7) % 7;
@@ -319,7 +349,7 @@ fn parse_business_time(now: DateTime<Local>) -> impl FnMut(&str) -> IResult<&str
                     .date_naive()
                     .and_hms_opt(17, 0, 0)
                     .unwrap()
-                    .and_local_timezone(Local)
+                    .and_local_timezone(timezone::zone())
                     .unwrap()
             }
             "eom" => {
@@ -341,19 +371,57 @@ fn parse_business_time(now: DateTime<Local>) -> impl FnMut(&str) -> IResult<&str
                     .date_naive()
                     .and_hms_opt(17, 0, 0)
                     .unwrap()
-                    .and_local_timezone(Local)
+                    .and_local_timezone(timezone::zone())
                     .unwrap()
             }
             _ => unreachable!(),
         };
 
-        Ok((input, TemporalContext::Point(dt.with_timezone(&Utc))))
+        Ok((
+            input,
+            TemporalContext::Point {
+                at: dt.with_timezone(&Utc),
+                has_time: true,
+            },
+        ))
+    }
+}
+
+/// Matches a clock time on its own ("at 5pm", "5:30pm", "at 17:00"). Bare
+/// numbers need am/pm or minutes so "buy 3 apples" isn't read as 3 o'clock.
+fn parse_clock_time(now: DateTime<Tz>) -> impl FnMut(&str) -> IResult<&str, TemporalContext> {
+    move |input| {
+        let (input, _) = opt(pair(tag_no_case("at"), space1))(input)?;
+        let (rest, (hour, minute, is_pm)) = parse_loose_time(input)?;
+        let has_minutes = input[..input.len() - rest.len()].contains(':');
+        if is_pm.is_none() && !has_minutes {
+            return Err(nom::Err::Error(nom::error::Error::new(
+                input,
+                nom::error::ErrorKind::Verify,
+            )));
+        }
+
+        let dt = now
+            .date_naive()
+            .and_hms_opt(resolve_24h(hour, is_pm), minute, 0)
+            .and_then(|naive| naive.and_local_timezone(timezone::zone()).single())
+            .ok_or_else(|| {
+                nom::Err::Error(nom::error::Error::new(input, nom::error::ErrorKind::Verify))
+            })?;
+
+        Ok((
+            rest,
+            TemporalContext::Point {
+                at: dt.with_timezone(&Utc),
+                has_time: true,
+            },
+        ))
     }
 }
 
 /// Matches "in X mins", "for X hours"
 fn parse_relative_duration(
-    now: DateTime<Local>,
+    now: DateTime<Tz>,
 ) -> impl FnMut(&str) -> IResult<&str, TemporalContext> {
     move |input| {
         let (input, prefix) = alt((tag_no_case("in"), tag_no_case("for")))(input)?;
@@ -387,7 +455,13 @@ fn parse_relative_duration(
             // Apply 15-minute quantization
             let quantized = quantize_time(target_time, 15);
 
-            Ok((input, TemporalContext::Point(quantized)))
+            Ok((
+                input,
+                TemporalContext::Point {
+                    at: quantized,
+                    has_time: true,
+                },
+            ))
         }
     }
 }
@@ -433,6 +507,19 @@ fn parse_chrono_candidate(input: &str) -> IResult<&str, &str> {
             tag_no_case("today"),
             tag_no_case("yesterday"),
         )),
+        // Bare weekday names ("essay friday"), but not the start of a longer word
+        terminated(
+            alt((
+                tag_no_case("monday"),
+                tag_no_case("tuesday"),
+                tag_no_case("wednesday"),
+                tag_no_case("thursday"),
+                tag_no_case("friday"),
+                tag_no_case("saturday"),
+                tag_no_case("sunday"),
+            )),
+            not(satisfy(|c: char| c.is_alphanumeric())),
+        ),
         // 2. Relative days (tuple returns complex type, must squash to &str)
         recognize(tuple((
             alt((
@@ -456,11 +543,6 @@ fn parse_chrono_candidate(input: &str) -> IResult<&str, &str> {
                 tag_no_case("th"),
             ))),
         ))),
-        // 4. Explicit time (preceded returns complex type, must squash)
-        recognize(preceded(
-            pair(tag_no_case("at"), space1),
-            parse_loose_time, // parse_loose_time returns a tuple, recognize fixes it
-        )),
     ))(input)
 }
 
@@ -478,6 +560,26 @@ fn parse_loose_time(input: &str) -> IResult<&str, (u32, u32, Option<bool>)> {
     Ok((input, (hour, minute.unwrap_or(0), is_pm)))
 }
 
+/// The date of `dt` at the 9am default used for date-only phrases
+fn at_default_hour(dt: DateTime<Tz>) -> DateTime<Utc> {
+    dt.date_naive()
+        .and_hms_opt(9, 0, 0)
+        .and_then(|naive| naive.and_local_timezone(timezone::zone()).single())
+        .map(|local| local.with_timezone(&Utc))
+        .unwrap_or_else(|| dt.with_timezone(&Utc))
+}
+
+/// `date`'s local calendar day combined with `time`'s local clock time
+fn with_time_of(date: DateTime<Utc>, time: DateTime<Utc>) -> DateTime<Utc> {
+    let day = date.with_timezone(&timezone::zone()).date_naive();
+    let clock = time.with_timezone(&timezone::zone()).time();
+    day.and_time(clock)
+        .and_local_timezone(timezone::zone())
+        .single()
+        .map(|local| local.with_timezone(&Utc))
+        .unwrap_or(date)
+}
+
 fn resolve_24h(hour: u32, is_pm: Option<bool>) -> u32 {
     match (hour, is_pm) {
         (12, Some(true)) => 12, // 12 pm is noon
@@ -501,3 +603,57 @@ fn quantize_time(dt: DateTime<Utc>, grid_minutes: i64) -> DateTime<Utc> {
         dt + Duration::seconds(diff)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Timelike;
+
+    #[test]
+    fn has_temporal_spots_dates_and_times() {
+        assert!(RuleParser::has_temporal("meeting today"));
+        assert!(RuleParser::has_temporal("meeting tomorrow"));
+        assert!(RuleParser::has_temporal("essay friday"));
+        assert!(RuleParser::has_temporal("call at 3pm"));
+        assert!(!RuleParser::has_temporal("buy milk"));
+        assert!(!RuleParser::has_temporal("buy milkk"));
+    }
+
+    fn parse_task(input: &str) -> Task {
+        match RuleParser::try_parse(input) {
+            Some(ParsedItem::Task(task)) => task,
+            other => panic!("expected a task for {:?}, got {:?}", input, other),
+        }
+    }
+
+    #[test]
+    fn date_only_input_is_all_day() {
+        let task = parse_task("essay friday");
+        let due = task.due_date.unwrap().with_timezone(&timezone::zone());
+
+        assert_eq!(task.title, "essay");
+        assert_eq!(due.weekday(), chrono::Weekday::Fri);
+        assert!(!task.has_time);
+    }
+
+    #[test]
+    fn input_with_clock_time_is_timed() {
+        let task = parse_task("essay friday 3pm");
+        let due = task.due_date.unwrap().with_timezone(&timezone::zone());
+
+        assert_eq!(task.title, "essay");
+        assert_eq!(due.weekday(), chrono::Weekday::Fri);
+        assert_eq!((due.hour(), due.minute()), (15, 0));
+        assert!(task.has_time);
+    }
+
+    #[test]
+    fn time_known_reaches_the_task_columns() {
+        for (input, timed) in [("essay friday", false), ("essay friday 3pm", true)] {
+            let item = RuleParser::try_parse(input).unwrap();
+            let (_, scheduled_at, time_known, _, _) = crate::app::item_fields(item);
+            assert!(scheduled_at.is_some());
+            assert_eq!(time_known, timed, "{}", input);
+        }
+    }
+}
@@ -1,18 +1,47 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParseResult {
     pub item: ParsedItem,
+    /// Further items when the input described several ("email Sam and
+    /// schedule the review")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_items: Vec<ParsedItem>,
     pub strategy: ParseStrategy,
     pub confidence: f32,
     pub parse_time_ms: u64,
 }
 
+impl ParseResult {
+    /// Every parsed item, the primary one first
+    pub fn into_items(self) -> Vec<ParsedItem> {
+        let mut items = vec![self.item];
+        items.extend(self.extra_items);
+        items
+    }
+}
+
+/// A cached parse that can be handed between processes (daemon -> TUI)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheEntry {
+    pub input: String,
+    pub item: ParsedItem,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_items: Vec<ParsedItem>,
+    pub strategy: ParseStrategy,
+    pub confidence: f32,
+    /// Wall-clock time the entry was parsed, so the importer can expire it.
+    /// Daemons that predate it leave it out and only export fresh entries.
+    #[serde(default)]
+    pub cached_at: Option<NaiveDateTime>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ParsedItem {
     Task(Task),
     Event(Event),
+    Email(EmailAction),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +51,14 @@ pub struct Task {
     pub tags: Vec<String>,
     pub priority: Priority,
     pub is_scheduled: bool,
+    /// True when the input named a clock time, not just a day ("friday 3pm"
+    /// vs "friday"); date-only due dates carry a placeholder time
+    #[serde(default = "timed_by_default")]
+    pub has_time: bool,
+}
+
+fn timed_by_default() -> bool {
+    true
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,6 +68,41 @@ pub struct Event {
     pub end_time: Option<DateTime<Utc>>,
     pub location: Option<String>,
     pub tags: Vec<String>,
+    /// See `Task::has_time`
+    #[serde(default = "timed_by_default")]
+    pub has_time: bool,
+}
+
+/// An email to write ("email John about the report tomorrow"), stored as a
+/// task until sending is supported
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmailAction {
+    /// What to do: "email", "reply", "follow up", ...
+    pub action: String,
+    pub recipient: String,
+    pub subject: Option<String>,
+    pub scheduled_time: Option<DateTime<Utc>>,
+    /// See `Task::has_time`
+    #[serde(default = "timed_by_default")]
+    pub has_time: bool,
+    pub tags: Vec<String>,
+    pub priority: Priority,
+}
+
+impl EmailAction {
+    /// Task title naming the recipient: "Email John about the report"
+    pub fn task_title(&self) -> String {
+        let mut action = self.action.trim().to_string();
+        if let Some(first) = action.get(..1) {
+            action.replace_range(..1, &first.to_uppercase());
+        }
+        match self.subject.as_deref().map(str::trim) {
+            Some(subject) if !subject.is_empty() => {
+                format!("{} {} about {}", action, self.recipient, subject)
+            }
+            _ => format!("{} {}", action, self.recipient),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -41,6 +113,47 @@ pub enum Priority {
     Urgent,
 }
 
+impl Priority {
+    /// Integer stored in the `tasks.priority` column (0-3)
+    pub fn to_level(&self) -> i32 {
+        match self {
+            Priority::Urgent => 3,
+            Priority::High => 2,
+            Priority::Medium => 1,
+            Priority::Low => 0,
+        }
+    }
+
+    pub fn from_level(level: i32) -> Self {
+        match level {
+            3.. => Priority::Urgent,
+            2 => Priority::High,
+            1 => Priority::Medium,
+            _ => Priority::Low,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Priority::Urgent => "urgent",
+            Priority::High => "high",
+            Priority::Medium => "medium",
+            Priority::Low => "low",
+        }
+    }
+
+    /// Parse "low", "medium", "high" or "urgent" (case-insensitive)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "urgent" => Some(Priority::Urgent),
+            "high" => Some(Priority::High),
+            "medium" => Some(Priority::Medium),
+            "low" => Some(Priority::Low),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ParseStrategy {
     Cached,
@@ -0,0 +1,60 @@
+//! Where Triptych keeps its database and configuration
+
+use std::path::PathBuf;
+
+const DB_FILE: &str = "todo.db";
+
+/// Absolute path of the SQLite database: `$TRIPTYCH_DB` if set, otherwise
+/// `$XDG_DATA_HOME/triptych/todo.db` (`~/.local/share` when unset). Falls
+/// back to `todo.db` in the current directory when there's no home directory.
+pub fn database_path() -> PathBuf {
+    if let Some(path) = env_path("TRIPTYCH_DB") {
+        return absolute(path);
+    }
+    // The XDG spec says relative paths are invalid and should be ignored
+    let data_home = env_path("XDG_DATA_HOME")
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| env_path("HOME").map(|home| home.join(".local").join("share")));
+    match data_home {
+        Some(dir) => dir.join("triptych").join(DB_FILE),
+        None => absolute(PathBuf::from(DB_FILE)),
+    }
+}
+
+/// `sqlite:` URL of the database, creating its directory if needed
+pub fn database_url() -> std::io::Result<String> {
+    let path = database_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    Ok(format!("sqlite:{}", path.display()))
+}
+
+/// A database left in the current directory by versions that kept it there,
+/// if it isn't the one in use
+pub fn legacy_database_path() -> Option<PathBuf> {
+    let legacy = std::env::current_dir().ok()?.join(DB_FILE);
+    (legacy.is_file() && legacy != database_path()).then_some(legacy)
+}
+
+/// The theme file: `$TRIPTYCH_THEME` if set, otherwise
+/// `$XDG_CONFIG_HOME/triptych/theme.toml` (`~/.config` when unset)
+pub fn theme_path() -> Option<PathBuf> {
+    if let Some(path) = env_path("TRIPTYCH_THEME") {
+        return Some(absolute(path));
+    }
+    let config_home = env_path("XDG_CONFIG_HOME")
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| env_path("HOME").map(|home| home.join(".config")))?;
+    Some(config_home.join("triptych").join("theme.toml"))
+}
+
+fn env_path(name: &str) -> Option<PathBuf> {
+    std::env::var_os(name)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+fn absolute(path: PathBuf) -> PathBuf {
+    std::path::absolute(&path).unwrap_or(path)
+}
@@ -0,0 +1,229 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+
+/// Upper bound on candidates examined per rule, so a malformed rule can't spin
+const MAX_ITERATIONS: usize = 100_000;
+
+/// Format used to store EXDATE values in `events.exdates` (comma-separated)
+pub const EXDATE_FORMAT: &str = "%Y%m%dT%H%M%S";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// The subset of an RFC 5545 RRULE the calendar understands:
+/// FREQ, INTERVAL, BYDAY (plain weekdays), COUNT and UNTIL
+#[derive(Debug, Clone)]
+pub struct RecurrenceRule {
+    freq: Frequency,
+    interval: u32,
+    by_day: Vec<Weekday>,
+    count: Option<usize>,
+    until: Option<NaiveDateTime>,
+}
+
+impl RecurrenceRule {
+    /// Parse "FREQ=WEEKLY;BYDAY=MO,WE;INTERVAL=2". Accepts an optional
+    /// "RRULE:" prefix. Returns None for unsupported frequencies.
+    pub fn parse(rule: &str) -> Option<Self> {
+        let rule = rule.trim();
+        let rule = rule.strip_prefix("RRULE:").unwrap_or(rule);
+
+        let mut freq = None;
+        let mut interval = 1;
+        let mut by_day = Vec::new();
+        let mut count = None;
+        let mut until = None;
+
+        for part in rule.split(';') {
+            let Some((key, value)) = part.split_once('=') else {
+                continue;
+            };
+            match key.trim().to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    freq = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return None,
+                    })
+                }
+                "INTERVAL" => interval = value.parse().ok().filter(|n| *n > 0)?,
+                "BYDAY" => {
+                    by_day = value
+                        .split(',')
+                        .filter_map(|day| parse_weekday(day.trim()))
+                        .collect()
+                }
+                "COUNT" => count = value.parse().ok(),
+                "UNTIL" => until = parse_until(value),
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            freq: freq?,
+            interval,
+            by_day,
+            count,
+            until,
+        })
+    }
+
+    /// Start times of every occurrence of a series beginning at `dtstart`
+    /// whose span (`duration` long) overlaps [range_start, range_end).
+    /// Occurrences listed in `exdates` are dropped but still count toward COUNT.
+    pub fn occurrences_between(
+        &self,
+        dtstart: NaiveDateTime,
+        duration: Duration,
+        range_start: NaiveDateTime,
+        range_end: NaiveDateTime,
+        exdates: &[NaiveDateTime],
+    ) -> Vec<NaiveDateTime> {
+        let mut found = Vec::new();
+
+        for (seen, start) in self.candidates(dtstart).take(MAX_ITERATIONS).enumerate() {
+            if start >= range_end || self.until.is_some_and(|until| start > until) {
+                break;
+            }
+            if self.count.is_some_and(|count| seen >= count) {
+                break;
+            }
+
+            if start + duration > range_start && !exdates.contains(&start) {
+                found.push(start);
+            }
+        }
+
+        found
+    }
+
+    /// The first occurrence of a series beginning at `dtstart` that starts
+    /// after `after`, or None once COUNT or UNTIL has run out
+    pub fn next_after(
+        &self,
+        dtstart: NaiveDateTime,
+        after: NaiveDateTime,
+    ) -> Option<NaiveDateTime> {
+        self.candidates(dtstart)
+            .take(MAX_ITERATIONS)
+            .enumerate()
+            .take_while(|(seen, start)| {
+                self.count.is_none_or(|count| *seen < count)
+                    && self.until.is_none_or(|until| *start <= until)
+            })
+            .map(|(_, start)| start)
+            .find(|start| *start > after)
+    }
+
+    /// Whether the rule limits the series with COUNT
+    pub fn has_count(&self) -> bool {
+        self.count.is_some()
+    }
+
+    /// Every candidate start time in order, beginning with `dtstart`
+    fn candidates(&self, dtstart: NaiveDateTime) -> impl Iterator<Item = NaiveDateTime> + '_ {
+        let time = dtstart.time();
+        let first = dtstart.date();
+        let interval = self.interval as i64;
+
+        let dates: Box<dyn Iterator<Item = NaiveDate>> = match self.freq {
+            Frequency::Daily => Box::new(
+                (0..)
+                    .map_while(move |i| first.checked_add_signed(Duration::days(i * interval)))
+                    .filter(move |date| self.matches_by_day(*date)),
+            ),
+            Frequency::Weekly => {
+                // Weeks start on Monday (the RFC 5545 default WKST)
+                let week_start =
+                    first - Duration::days(first.weekday().num_days_from_monday() as i64);
+                let mut days = if self.by_day.is_empty() {
+                    vec![first.weekday()]
+                } else {
+                    self.by_day.clone()
+                };
+                days.sort_by_key(|day| day.num_days_from_monday());
+
+                Box::new(
+                    (0..)
+                        .map_while(move |i| {
+                            week_start.checked_add_signed(Duration::weeks(i * interval))
+                        })
+                        .flat_map(move |monday| {
+                            days.clone().into_iter().map(move |day| {
+                                monday + Duration::days(day.num_days_from_monday() as i64)
+                            })
+                        })
+                        .filter(move |date| *date >= first),
+                )
+            }
+            Frequency::Monthly => Box::new(
+                (0..)
+                    .map_while(move |i| add_months(first, i * interval))
+                    .flatten(),
+            ),
+            Frequency::Yearly => Box::new(
+                (0..)
+                    .map_while(move |i| add_months(first, i * interval * 12))
+                    .flatten(),
+            ),
+        };
+
+        dates.map(move |date| date.and_time(time))
+    }
+
+    fn matches_by_day(&self, date: NaiveDate) -> bool {
+        self.by_day.is_empty() || self.by_day.contains(&date.weekday())
+    }
+}
+
+/// Parse the stored comma-separated EXDATE list
+pub fn parse_exdates(stored: Option<&str>) -> Vec<NaiveDateTime> {
+    stored
+        .unwrap_or("")
+        .split(',')
+        .filter_map(|value| NaiveDateTime::parse_from_str(value.trim(), EXDATE_FORMAT).ok())
+        .collect()
+}
+
+/// `date` moved forward by `months`, keeping its day of month. The inner None
+/// marks a month without that day (e.g. Feb 30), which recurrences skip; the
+/// outer None means the date overflowed and iteration should stop.
+fn add_months(date: NaiveDate, months: i64) -> Option<Option<NaiveDate>> {
+    let total = date.year() as i64 * 12 + date.month0() as i64 + months;
+    let year = i32::try_from(total.div_euclid(12)).ok()?;
+    let month = total.rem_euclid(12) as u32 + 1;
+    NaiveDate::from_ymd_opt(year, month, 1)?;
+    Some(NaiveDate::from_ymd_opt(year, month, date.day()))
+}
+
+fn parse_weekday(day: &str) -> Option<Weekday> {
+    // Ordinal prefixes like "1MO" or "-1FR" (monthly rules) aren't supported
+    match day.to_ascii_uppercase().as_str() {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn parse_until(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim().trim_end_matches('Z');
+    NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S")
+        .ok()
+        .or_else(|| {
+            // A date-only UNTIL includes the whole day
+            NaiveDate::parse_from_str(value, "%Y%m%d").ok().map(|date| {
+                date.and_time(NaiveTime::MIN) + Duration::days(1) - Duration::seconds(1)
+            })
+        })
+}
@@ -2,7 +2,13 @@ mod cache;
 mod calendar;
 mod config;
 mod daemon;
+mod events;
+pub mod ical;
 mod ollama;
+mod status;
 
+pub use cache::warm_cache;
 pub use config::SyncConfig;
 pub use daemon::SyncDaemon;
+pub use events::AppEvent;
+pub use status::SyncStatus;
@@ -4,32 +4,42 @@ use sqlx::SqlitePool;
 use std::sync::Arc;
 use tokio::sync::broadcast;
 
+/// Number of most frequent inputs re-parsed when warming the cache
+const PRELOAD_LIMIT: i64 = 100;
+
+/// Re-parse the most frequently entered task inputs so they sit at the front
+/// of the NLP cache. The cache lock is only held per lookup, so concurrent
+/// parses interleave with the warm-up. Returns the number of inputs parsed.
+pub async fn warm_cache(db: &SqlitePool, nlp: &NLPParser) -> Result<usize> {
+    let rows: Vec<(String, i64)> = sqlx::query_as(
+        r#"
+        SELECT natural_language_input, COUNT(*) as count
+        FROM tasks
+        WHERE natural_language_input IS NOT NULL
+        GROUP BY natural_language_input
+        ORDER BY count DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(PRELOAD_LIMIT)
+    .fetch_all(db)
+    .await?;
+
+    let loaded = rows.len();
+    for (input, _count) in rows {
+        let _ = nlp.parse(&input).await;
+    }
+
+    Ok(loaded)
+}
+
 /// Preload frequently used NLP patterns from database into cache
 pub async fn preload_cache(
     db: SqlitePool,
     nlp: Arc<NLPParser>,
     mut shutdown_rx: broadcast::Receiver<()>,
 ) -> Result<()> {
-    let preload_task = tokio::spawn(async move {
-        let rows: Vec<(String, i64)> = sqlx::query_as(
-            r#"
-            SELECT natural_language_input, COUNT(*) as count
-            FROM tasks
-            WHERE natural_language_input IS NOT NULL
-            GROUP BY natural_language_input
-            ORDER BY count DESC
-            LIMIT 100
-            "#,
-        )
-        .fetch_all(&db)
-        .await?;
-
-        for (input, _count) in rows {
-            let _ = nlp.parse(&input).await;
-        }
-
-        Ok::<_, anyhow::Error>(())
-    });
+    let preload_task = tokio::spawn(async move { warm_cache(&db, &nlp).await });
 
     tokio::select! {
         result = preload_task => {
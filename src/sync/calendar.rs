@@ -1,13 +1,39 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use reqwest::{Client, Method};
 use sqlx::SqlitePool;
+use std::collections::HashSet;
+use std::sync::Arc;
 use tokio::sync::broadcast;
 use tokio::time::{Duration, interval};
 
+use super::config::CalDavConfig;
+use super::events::AppEvent;
+use super::ical;
+use super::status::SyncStatus;
+
+/// calendar-query REPORT asking for every VEVENT in the collection
+const CALENDAR_QUERY: &str = r#"<?xml version="1.0" encoding="utf-8" ?>
+<C:calendar-query xmlns:D="DAV:" xmlns:C="urn:ietf:params:xml:ns:caldav">
+  <D:prop>
+    <D:getetag/>
+    <C:calendar-data/>
+  </D:prop>
+  <C:filter>
+    <C:comp-filter name="VCALENDAR">
+      <C:comp-filter name="VEVENT"/>
+    </C:comp-filter>
+  </C:filter>
+</C:calendar-query>"#;
+
 /// Background calendar sync worker for CalDAV integration
 pub async fn calendar_sync_worker(
     db: SqlitePool,
+    config: CalDavConfig,
+    status: Arc<SyncStatus>,
+    events: broadcast::Sender<AppEvent>,
     mut shutdown_rx: broadcast::Receiver<()>,
 ) -> Result<()> {
+    let client = Client::new();
     let mut sync_interval = interval(Duration::from_secs(600));
 
     loop {
@@ -17,7 +43,11 @@ pub async fn calendar_sync_worker(
             }
 
             _ = sync_interval.tick() => {
-                let _ = sync_calendar(&db).await;
+                let outcome = sync_calendar(&db, &client, &config).await;
+                if outcome.is_ok() {
+                    let _ = events.send(AppEvent::CalendarSynced);
+                }
+                status.record_calendar_sync(outcome.map_err(|e| format!("{:#}", e)));
             }
         }
     }
@@ -25,12 +55,152 @@ pub async fn calendar_sync_worker(
     Ok(())
 }
 
-/// Sync calendar events from CalDAV server
-/// TODO: Implement CalDAV protocol
-async fn sync_calendar(db: &SqlitePool) -> Result<()> {
-    let _count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM events")
-        .fetch_one(db)
+/// Pull all events from the CalDAV collection and mirror them into `events`,
+/// keyed by UID. Local events from this calendar that the server no longer
+/// returns are deleted. Returns the number of events synced.
+async fn sync_calendar(db: &SqlitePool, client: &Client, config: &CalDavConfig) -> Result<usize> {
+    let report = Method::from_bytes(b"REPORT").expect("REPORT is a valid method");
+    let body = client
+        .request(report, &config.url)
+        .basic_auth(&config.username, Some(&config.password))
+        .header("Depth", "1")
+        .header("Content-Type", "application/xml; charset=utf-8")
+        .body(CALENDAR_QUERY)
+        .send()
+        .await
+        .context("CalDAV request failed")?
+        .error_for_status()
+        .context("CalDAV server rejected the calendar query")?
+        .text()
         .await?;
 
-    Ok(())
+    let events: Vec<_> = extract_calendar_data(&body)
+        .iter()
+        .flat_map(|ics| ical::parse_events(ics, Some(&config.url)))
+        .filter(|event| event.uid.is_some())
+        .collect();
+
+    let mut tx = db.begin().await?;
+
+    for event in &events {
+        sqlx::query(
+            "INSERT INTO events (uid, title, description, start_time, end_time, location, calendar_id, event_type, recurrence_rule, exdates)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+             ON CONFLICT(uid) DO UPDATE SET
+                title = excluded.title,
+                description = excluded.description,
+                start_time = excluded.start_time,
+                end_time = excluded.end_time,
+                location = excluded.location,
+                calendar_id = excluded.calendar_id,
+                event_type = excluded.event_type,
+                recurrence_rule = excluded.recurrence_rule,
+                exdates = excluded.exdates",
+        )
+        .bind(&event.uid)
+        .bind(&event.title)
+        .bind(&event.description)
+        .bind(event.start_time)
+        .bind(event.end_time)
+        .bind(&event.location)
+        .bind(&event.calendar_id)
+        .bind(&event.event_type)
+        .bind(&event.recurrence_rule)
+        .bind(&event.exdates)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    // Remove events that were deleted on the server
+    let remote_uids: HashSet<&str> = events.iter().filter_map(|e| e.uid.as_deref()).collect();
+    let local: Vec<(i64, String)> =
+        sqlx::query_as("SELECT id, uid FROM events WHERE calendar_id = ? AND uid IS NOT NULL")
+            .bind(&config.url)
+            .fetch_all(&mut *tx)
+            .await?;
+
+    for (id, uid) in local {
+        if remote_uids.contains(uid.as_str()) {
+            continue;
+        }
+        sqlx::query("UPDATE tasks SET scheduled_event_id = NULL WHERE scheduled_event_id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM events WHERE id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    tx.commit().await?;
+    Ok(events.len())
+}
+
+/// Pull the iCalendar payloads out of a multistatus response. Matching on the
+/// element's local name keeps this independent of the server's namespace prefix.
+fn extract_calendar_data(xml: &str) -> Vec<String> {
+    let mut payloads = Vec::new();
+    let mut rest = xml;
+
+    while let Some((_, content_start)) = find_tag(rest, false) {
+        let after_open = &rest[content_start..];
+        let Some((content_end, after_close)) = find_tag(after_open, true) else {
+            break;
+        };
+        payloads.push(decode_xml_text(&after_open[..content_end]));
+        rest = &after_open[after_close..];
+    }
+
+    payloads
+}
+
+/// Find a `calendar-data` tag. For an opening tag, returns the offset just past
+/// it; for a closing tag, returns its start and the offset just past it.
+fn find_tag(xml: &str, closing: bool) -> Option<(usize, usize)> {
+    let mut offset = 0;
+    while let Some(lt) = xml[offset..].find('<') {
+        let start = offset + lt;
+        let gt = start + xml[start..].find('>')?;
+        let tag = &xml[start + 1..gt];
+        offset = gt + 1;
+
+        if tag.starts_with("![CDATA[") {
+            // Skip over CDATA so markup-like text inside it isn't matched
+            offset = start + xml[start..].find("]]>")? + 3;
+            continue;
+        }
+
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or("");
+        let local_name = name.rsplit(':').next().unwrap_or(name);
+
+        if local_name == "calendar-data" && is_closing == closing && !tag.ends_with('/') {
+            return Some((start, offset));
+        }
+    }
+    None
+}
+
+fn decode_xml_text(content: &str) -> String {
+    let content = content.trim();
+    if let Some(inner) = content
+        .strip_prefix("<![CDATA[")
+        .and_then(|c| c.strip_suffix("]]>"))
+    {
+        return inner.to_string();
+    }
+
+    content
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#13;", "\r")
+        .replace("&#xD;", "\r")
+        .replace("&amp;", "&")
 }
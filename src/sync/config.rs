@@ -1,9 +1,12 @@
+use std::fmt;
+
 /// Configuration for the sync daemon
 #[derive(Debug, Clone)]
 pub struct SyncConfig {
     pub ollama_warmup_enabled: bool,
     pub cache_preload_enabled: bool,
     pub calendar_sync_enabled: bool,
+    pub caldav: Option<CalDavConfig>,
 }
 
 impl Default for SyncConfig {
@@ -12,16 +15,53 @@ impl Default for SyncConfig {
             ollama_warmup_enabled: true,
             cache_preload_enabled: true,
             calendar_sync_enabled: false,
+            caldav: None,
         }
     }
 }
 
 impl SyncConfig {
     pub fn from_env() -> Self {
+        let caldav = CalDavConfig::from_env();
         Self {
             ollama_warmup_enabled: true,
             cache_preload_enabled: true,
-            calendar_sync_enabled: false,
+            calendar_sync_enabled: caldav.is_some(),
+            caldav,
         }
     }
 }
+
+/// Credentials for a CalDAV calendar collection
+#[derive(Clone)]
+pub struct CalDavConfig {
+    /// URL of the calendar collection itself, e.g.
+    /// https://caldav.example.com/calendars/me/personal/
+    pub url: String,
+    pub username: String,
+    pub password: String,
+}
+
+impl CalDavConfig {
+    /// Read TRIPTYCH_CALDAV_URL, TRIPTYCH_CALDAV_USERNAME and
+    /// TRIPTYCH_CALDAV_PASSWORD; sync stays off unless all three are set
+    pub fn from_env() -> Option<Self> {
+        let var = |name| std::env::var(name).ok().filter(|v: &String| !v.is_empty());
+        Some(Self {
+            url: var("TRIPTYCH_CALDAV_URL")?,
+            username: var("TRIPTYCH_CALDAV_USERNAME")?,
+            password: var("TRIPTYCH_CALDAV_PASSWORD")?,
+        })
+    }
+}
+
+// Hand-written so the password never ends up in logs
+impl fmt::Debug for CalDavConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CalDavConfig")
+            .field("url", &self.url)
+            .field("username", &self.username)
+            .field("password", &"<redacted>")
+            .finish()
+    }
+}
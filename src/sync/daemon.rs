@@ -7,12 +7,16 @@ use tokio::task::JoinHandle;
 use tokio::time::Duration;
 
 use super::config::SyncConfig;
+use super::events::{self, AppEvent};
+use super::status::SyncStatus;
 use super::{cache, calendar, ollama};
 
 /// Handle for managing the background sync daemon
 pub struct SyncDaemon {
     shutdown_tx: broadcast::Sender<()>,
     tasks: Vec<JoinHandle<Result<()>>>,
+    status: Arc<SyncStatus>,
+    events: broadcast::Sender<AppEvent>,
 }
 
 impl SyncDaemon {
@@ -23,7 +27,11 @@ impl SyncDaemon {
         config: SyncConfig,
     ) -> Result<Self> {
         let (shutdown_tx, _) = broadcast::channel::<()>(1);
+        let (events_tx, _) = broadcast::channel::<AppEvent>(16);
         let mut tasks = Vec::new();
+        let status = Arc::new(SyncStatus::new(
+            config.calendar_sync_enabled && config.caldav.is_some(),
+        ));
 
         // Pre-warm Ollama on startup
         if config.ollama_warmup_enabled {
@@ -46,18 +54,48 @@ impl SyncDaemon {
             }));
         }
 
-        // Calendar sync
-        // Not yet finished
-        if config.calendar_sync_enabled {
+        // Watch for writes from other processes so the TUI can reload
+        {
             let shutdown_rx = shutdown_tx.subscribe();
             let db_clone = db.clone();
+            let events_tx = events_tx.clone();
 
             tasks.push(tokio::spawn(async move {
-                calendar::calendar_sync_worker(db_clone, shutdown_rx).await
+                events::database_watcher(db_clone, events_tx, shutdown_rx).await
             }));
         }
 
-        Ok(Self { shutdown_tx, tasks })
+        // CalDAV calendar sync
+        if config.calendar_sync_enabled
+            && let Some(caldav) = config.caldav.clone()
+        {
+            let shutdown_rx = shutdown_tx.subscribe();
+            let db_clone = db.clone();
+            let status = status.clone();
+            let events_tx = events_tx.clone();
+
+            tasks.push(tokio::spawn(async move {
+                calendar::calendar_sync_worker(db_clone, caldav, status, events_tx, shutdown_rx)
+                    .await
+            }));
+        }
+
+        Ok(Self {
+            shutdown_tx,
+            tasks,
+            status,
+            events: events_tx,
+        })
+    }
+
+    /// Shared view of what the workers have done, for the TUI footer
+    pub fn status(&self) -> Arc<SyncStatus> {
+        self.status.clone()
+    }
+
+    /// Live updates from the workers, for the TUI to reload on
+    pub fn subscribe(&self) -> broadcast::Receiver<AppEvent> {
+        self.events.subscribe()
     }
 
     /// Gracefully shutdown all background tasks
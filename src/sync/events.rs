@@ -0,0 +1,61 @@
+use anyhow::Result;
+use sqlx::SqlitePool;
+use tokio::sync::broadcast;
+use tokio::time::{Duration, interval};
+
+/// How often the database is checked for writes from other processes
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Something changed behind the TUI's back, so the open view should reload
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AppEvent {
+    /// Another connection committed a write, e.g. `triptych add` in another
+    /// terminal or the CLI daemon
+    DatabaseChanged,
+    /// The CalDAV worker stored a fresh copy of the calendar
+    CalendarSynced,
+}
+
+/// Publish `DatabaseChanged` whenever SQLite's `data_version` moves. It
+/// only changes for commits made by other connections, which includes other
+/// processes and, harmlessly, the TUI's own writes through the rest of the pool.
+pub async fn database_watcher(
+    db: SqlitePool,
+    events: broadcast::Sender<AppEvent>,
+    mut shutdown_rx: broadcast::Receiver<()>,
+) -> Result<()> {
+    // data_version is per connection, so keep the same one throughout
+    let mut conn = db.acquire().await?;
+    let mut last: i64 = sqlx::query_scalar("PRAGMA data_version")
+        .fetch_one(&mut *conn)
+        .await?;
+    let mut ticks = interval(WATCH_INTERVAL);
+
+    loop {
+        tokio::select! {
+            _ = shutdown_rx.recv() => {
+                break;
+            }
+
+            _ = ticks.tick() => {
+                let version: i64 = match sqlx::query_scalar("PRAGMA data_version")
+                    .fetch_one(&mut *conn)
+                    .await
+                {
+                    Ok(version) => version,
+                    Err(e) => {
+                        tracing::debug!("Couldn't check for database changes: {}", e);
+                        continue;
+                    }
+                };
+                if version != last {
+                    last = version;
+                    // Nobody listening just means the TUI isn't running
+                    let _ = events.send(AppEvent::DatabaseChanged);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,231 @@
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+use crate::app::Event;
+use crate::recurrence::EXDATE_FORMAT;
+
+/// Parse every VEVENT in an iCalendar document.
+///
+/// Times follow the app's wall-clock convention: UTC (`Z`) times are converted
+/// to local time, while floating and TZID times are taken as written. Date-only
+/// events become all-day events spanning midnight to midnight. Overrides of a
+/// single recurrence (RECURRENCE-ID) are skipped so they don't replace the
+/// series they belong to.
+pub fn parse_events(ics: &str, calendar_id: Option<&str>) -> Vec<Event> {
+    let mut events = Vec::new();
+    let mut current: Option<VEventBuilder> = None;
+    // Depth of components nested inside the VEVENT (e.g. VALARM)
+    let mut nested = 0;
+
+    for line in unfold_lines(ics) {
+        let Some((name, params, value)) = split_property(&line) else {
+            continue;
+        };
+
+        match (name.as_str(), value) {
+            ("BEGIN", v) if v.eq_ignore_ascii_case("VEVENT") && current.is_none() => {
+                current = Some(VEventBuilder::default());
+                nested = 0;
+            }
+            ("BEGIN", _) if current.is_some() => nested += 1,
+            ("END", v) if v.eq_ignore_ascii_case("VEVENT") && nested == 0 => {
+                if let Some(event) = current.take().and_then(|b| b.build(calendar_id)) {
+                    events.push(event);
+                }
+            }
+            ("END", _) if current.is_some() => nested -= 1,
+            _ => {
+                if nested == 0
+                    && let Some(builder) = current.as_mut()
+                {
+                    builder.set(&name, &params, value);
+                }
+            }
+        }
+    }
+
+    events
+}
+
+#[derive(Default)]
+struct VEventBuilder {
+    uid: Option<String>,
+    summary: Option<String>,
+    description: Option<String>,
+    location: Option<String>,
+    start: Option<(DateTime<Utc>, bool)>,
+    end: Option<(DateTime<Utc>, bool)>,
+    duration: Option<Duration>,
+    rrule: Option<String>,
+    exdates: Vec<DateTime<Utc>>,
+    is_override: bool,
+}
+
+impl VEventBuilder {
+    fn set(&mut self, name: &str, params: &str, value: &str) {
+        match name {
+            "UID" => self.uid = Some(value.to_string()),
+            "SUMMARY" => self.summary = Some(unescape_text(value)),
+            "DESCRIPTION" => self.description = Some(unescape_text(value)),
+            "LOCATION" => self.location = Some(unescape_text(value)),
+            "DTSTART" => self.start = parse_datetime(params, value),
+            "DTEND" => self.end = parse_datetime(params, value),
+            "DURATION" => self.duration = parse_duration(value),
+            "RRULE" => self.rrule = Some(value.to_string()),
+            // May repeat, and each line may list several comma-separated dates
+            "EXDATE" => self.exdates.extend(
+                value
+                    .split(',')
+                    .filter_map(|v| parse_datetime(params, v))
+                    .map(|(dt, _)| dt),
+            ),
+            "RECURRENCE-ID" => self.is_override = true,
+            _ => {}
+        }
+    }
+
+    fn build(self, calendar_id: Option<&str>) -> Option<Event> {
+        if self.is_override {
+            return None;
+        }
+
+        let (start_time, all_day) = self.start?;
+        let end_time = match (self.end, self.duration) {
+            (Some((end, _)), _) => end,
+            (None, Some(duration)) => start_time + duration,
+            // RFC 5545: a date-only event without DTEND lasts one day
+            (None, None) if all_day => start_time + Duration::days(1),
+            (None, None) => start_time,
+        };
+
+        Some(Event {
+            id: 0,
+            title: self
+                .summary
+                .filter(|s| !s.trim().is_empty())
+                .unwrap_or_else(|| "(untitled)".to_string()),
+            description: self.description,
+            start_time,
+            end_time,
+            location: self.location,
+            calendar_id: calendar_id.map(str::to_string),
+            created_at: Utc::now(),
+            event_type: Some(if all_day { "all_day" } else { "event" }.to_string()),
+            exdates: (!self.exdates.is_empty()).then(|| {
+                self.exdates
+                    .iter()
+                    .map(|dt| dt.naive_utc().format(EXDATE_FORMAT).to_string())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            }),
+            recurrence_rule: self.rrule,
+            uid: self.uid,
+        })
+    }
+}
+
+/// Join folded continuation lines (those starting with a space or tab)
+fn unfold_lines(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in ics.lines() {
+        let raw = raw.trim_end_matches('\r');
+        if let Some(rest) = raw.strip_prefix([' ', '\t'])
+            && let Some(last) = lines.last_mut()
+        {
+            last.push_str(rest);
+        } else if !raw.is_empty() {
+            lines.push(raw.to_string());
+        }
+    }
+    lines
+}
+
+/// Split "NAME;PARAM=x:value" into its upper-cased name, raw params and value
+fn split_property(line: &str) -> Option<(String, String, &str)> {
+    // The first colon outside a quoted parameter value ends the name
+    let mut in_quotes = false;
+    let colon = line.char_indices().find_map(|(i, c)| match c {
+        '"' => {
+            in_quotes = !in_quotes;
+            None
+        }
+        ':' if !in_quotes => Some(i),
+        _ => None,
+    })?;
+
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+    let (name, params) = head.split_once(';').unwrap_or((head, ""));
+    Some((
+        name.to_ascii_uppercase(),
+        params.to_ascii_uppercase(),
+        value,
+    ))
+}
+
+/// Parse DTSTART/DTEND, returning the time and whether it was date-only
+fn parse_datetime(params: &str, value: &str) -> Option<(DateTime<Utc>, bool)> {
+    let value = value.trim();
+
+    if params.contains("VALUE=DATE") && !params.contains("VALUE=DATE-TIME") || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        return Some((date.and_time(NaiveTime::MIN).and_utc(), true));
+    }
+
+    if let Some(utc) = value.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        let local = crate::timezone::wall_clock(naive.and_utc());
+        return Some((local.and_utc(), false));
+    }
+
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    Some((naive.and_utc(), false))
+}
+
+/// Parse an RFC 5545 duration such as "PT1H30M", "P1D" or "P2W"
+fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let (negative, value) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    let mut in_time = false;
+    for c in value.strip_prefix('P')?.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => in_time = true,
+            unit => {
+                let n: i64 = number.parse().ok()?;
+                number.clear();
+                total += match (unit, in_time) {
+                    ('W', false) => Duration::weeks(n),
+                    ('D', false) => Duration::days(n),
+                    ('H', true) => Duration::hours(n),
+                    ('M', true) => Duration::minutes(n),
+                    ('S', true) => Duration::seconds(n),
+                    _ => return None,
+                };
+            }
+        }
+    }
+
+    Some(if negative { -total } else { total })
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
@@ -0,0 +1,31 @@
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// What the background sync has done so far, shared with the TUI footer
+#[derive(Debug, Default)]
+pub struct SyncStatus {
+    /// Whether a CalDAV calendar is configured at all
+    pub calendar_enabled: bool,
+    last_calendar_sync: Mutex<Option<(Instant, Result<usize, String>)>>,
+}
+
+impl SyncStatus {
+    pub fn new(calendar_enabled: bool) -> Self {
+        Self {
+            calendar_enabled,
+            last_calendar_sync: Mutex::new(None),
+        }
+    }
+
+    /// Remember the outcome of a calendar sync: events synced, or the error
+    pub fn record_calendar_sync(&self, outcome: Result<usize, String>) {
+        if let Ok(mut last) = self.last_calendar_sync.lock() {
+            *last = Some((Instant::now(), outcome));
+        }
+    }
+
+    /// When the last calendar sync finished and how it went
+    pub fn last_calendar_sync(&self) -> Option<(Instant, Result<usize, String>)> {
+        self.last_calendar_sync.lock().ok()?.clone()
+    }
+}
@@ -0,0 +1,502 @@
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Okabe-Ito colors, distinguishable with the common forms of color blindness
+const CB_ORANGE: Color = Color::Rgb(230, 159, 0);
+const CB_SKY_BLUE: Color = Color::Rgb(86, 180, 233);
+const CB_BLUISH_GREEN: Color = Color::Rgb(0, 158, 115);
+const CB_YELLOW: Color = Color::Rgb(240, 228, 66);
+const CB_BLUE: Color = Color::Rgb(0, 114, 178);
+const CB_VERMILLION: Color = Color::Rgb(213, 94, 0);
+const CB_PURPLE: Color = Color::Rgb(204, 121, 167);
+
+/// Darker tones that stay readable on a white background
+const LIGHT_AMBER: Color = Color::Rgb(175, 95, 0);
+const LIGHT_TEAL: Color = Color::Rgb(0, 128, 128);
+const LIGHT_GREEN: Color = Color::Rgb(0, 128, 0);
+const LIGHT_NAVY: Color = Color::Rgb(0, 80, 160);
+const LIGHT_PLUM: Color = Color::Rgb(135, 0, 135);
+const LIGHT_SELECTION: Color = Color::Rgb(215, 215, 215);
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Palette {
+    /// Made for dark terminal backgrounds
+    #[default]
+    Default,
+    /// Darker colors for light terminal backgrounds
+    Light,
+    /// Bright colors and bold selection for low-vision use
+    HighContrast,
+    /// Deuteranopia-friendly: blue/orange/purple instead of red/green
+    Colorblind,
+}
+
+impl Palette {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "default" | "dark" => Some(Palette::Default),
+            "light" => Some(Palette::Light),
+            "high-contrast" | "highcontrast" | "contrast" => Some(Palette::HighContrast),
+            "colorblind" | "color-blind" | "deuteranopia" => Some(Palette::Colorblind),
+            _ => None,
+        }
+    }
+}
+
+/// A theme file: an optional base palette plus colors for individual roles
+/// and block types, e.g. `error = "#d70000"` under `[colors]`
+#[derive(Debug, Default, Deserialize)]
+struct ThemeFile {
+    palette: Option<String>,
+    #[serde(default)]
+    colors: HashMap<String, String>,
+    #[serde(default)]
+    blocks: HashMap<String, String>,
+}
+
+/// Roles a theme file can set under `[colors]`
+const ROLES: &[&str] = &[
+    "priority_high",
+    "priority_medium",
+    "priority_low",
+    "text",
+    "muted",
+    "accent",
+    "highlight",
+    "selection",
+    "cursor",
+    "popup_bg",
+    "success",
+    "error",
+    "warning",
+    "overdue",
+    "event",
+    "all_day",
+    "block_default",
+];
+
+/// Colors the TUI uses for meaning (priorities, block types, success/error,
+/// selection), resolved from the selected palette and any theme file overrides
+#[derive(Debug, Clone, Default)]
+pub struct Theme {
+    pub palette: Palette,
+    colors: HashMap<String, Color>,
+    blocks: HashMap<String, Color>,
+}
+
+impl Theme {
+    /// Load the theme file (TRIPTYCH_THEME, or `triptych/theme.toml` in the
+    /// config directory) and pick the palette from TRIPTYCH_PALETTE, falling
+    /// back to the file's `palette` and then the default
+    pub fn from_env() -> Self {
+        let mut theme = Self::default();
+        let mut palette = None;
+
+        if let Some(path) = crate::paths::theme_path() {
+            match std::fs::read_to_string(&path)
+                .map(|content| toml::from_str::<ThemeFile>(&content))
+            {
+                Ok(Ok(file)) => {
+                    palette = file.palette.as_deref().and_then(|name| {
+                        let palette = Palette::from_name(name);
+                        if palette.is_none() {
+                            tracing::warn!(
+                                "⚠️  Ignoring invalid palette \"{}\" in {:?}",
+                                name,
+                                path
+                            );
+                        }
+                        palette
+                    });
+                    theme.colors = parse_colors(file.colors, Some(ROLES), &path);
+                    theme.blocks = parse_colors(file.blocks, None, &path);
+                }
+                Ok(Err(e)) => tracing::warn!("⚠️  Ignoring theme file {:?}: {}", path, e),
+                // A missing file just means the built-in colors
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => tracing::warn!("⚠️  Couldn't read theme file {:?}: {}", path, e),
+            }
+        }
+
+        if let Ok(name) = std::env::var("TRIPTYCH_PALETTE") {
+            match Palette::from_name(&name) {
+                Some(from_env) => palette = Some(from_env),
+                None => tracing::warn!("⚠️  Ignoring invalid TRIPTYCH_PALETTE \"{}\"", name),
+            }
+        }
+        theme.palette = palette.unwrap_or_default();
+        theme
+    }
+
+    fn role(&self, name: &str, builtin: Color) -> Color {
+        self.colors.get(name).copied().unwrap_or(builtin)
+    }
+
+    /// Priority label color: urgent/high, medium, low
+    pub fn priority_color(&self, priority: i32) -> Color {
+        let (role, builtin) = match (self.palette, priority) {
+            (Palette::Default, 3) => ("priority_high", Color::Red),
+            (Palette::Default, 2) => ("priority_medium", Color::Yellow),
+            (Palette::Default, 1) => ("priority_low", Color::Blue),
+            (Palette::Light, 3) => ("priority_high", Color::Red),
+            (Palette::Light, 2) => ("priority_medium", LIGHT_AMBER),
+            (Palette::Light, 1) => ("priority_low", LIGHT_NAVY),
+            (Palette::HighContrast, 3) => ("priority_high", Color::LightRed),
+            (Palette::HighContrast, 2) => ("priority_medium", Color::LightYellow),
+            (Palette::HighContrast, 1) => ("priority_low", Color::LightCyan),
+            (Palette::Colorblind, 3) => ("priority_high", CB_ORANGE),
+            (Palette::Colorblind, 2) => ("priority_medium", CB_YELLOW),
+            (Palette::Colorblind, 1) => ("priority_low", CB_SKY_BLUE),
+            _ => return self.text(),
+        };
+        self.role(role, builtin)
+    }
+
+    /// Calendar task color: only medium and higher priorities stand out
+    pub fn task_color(&self, priority: i32) -> Color {
+        if priority >= 2 {
+            self.priority_color(priority)
+        } else {
+            self.text()
+        }
+    }
+
+    /// Task description color by category in the task lists
+    pub fn category_color(&self, category: Option<&str>) -> Color {
+        match category {
+            Some("deepwork" | "admin" | "learning" | "fitness") => {
+                self.block_color(category.unwrap_or_default())
+            }
+            _ => self.text(),
+        }
+    }
+
+    /// Schedule block color. Types without a color of their own (including
+    /// user-defined ones) fall back to the `block_default` role so they still
+    /// render.
+    pub fn block_color(&self, block_type: &str) -> Color {
+        self.blocks
+            .get(block_type)
+            .copied()
+            .or_else(|| self.builtin_block_color(block_type))
+            .unwrap_or_else(|| self.role("block_default", self.text()))
+    }
+
+    fn builtin_block_color(&self, block_type: &str) -> Option<Color> {
+        let color = match self.palette {
+            Palette::Default => match block_type {
+                "deepwork" | "deepwork_input" | "deepwork_output" => Color::Blue,
+                "class" => Color::Green,
+                "training" | "fitness" => Color::Red,
+                "learning" | "review" => Color::Cyan,
+                "admin" => Color::Yellow,
+                "bio-maintenance" | "meal" => Color::Magenta,
+                "relax" | "recovery" => Color::LightGreen,
+                "winddown" => Color::DarkGray,
+                "break" => Color::Gray,
+                "social" => Color::LightBlue,
+                "planning" => Color::LightYellow,
+                "project" => Color::LightMagenta,
+                _ => return None,
+            },
+            Palette::Light => match block_type {
+                "deepwork" | "deepwork_input" | "deepwork_output" => Color::Blue,
+                "class" => LIGHT_GREEN,
+                "training" | "fitness" => Color::Red,
+                "learning" | "review" => LIGHT_TEAL,
+                "admin" | "planning" => LIGHT_AMBER,
+                "bio-maintenance" | "meal" => Color::Magenta,
+                "relax" | "recovery" => Color::Green,
+                "winddown" | "break" => Color::DarkGray,
+                "social" => LIGHT_NAVY,
+                "project" => LIGHT_PLUM,
+                _ => return None,
+            },
+            Palette::HighContrast => match block_type {
+                "deepwork" | "deepwork_input" | "deepwork_output" => Color::LightBlue,
+                "class" => Color::LightGreen,
+                "training" | "fitness" => Color::LightRed,
+                "learning" | "review" => Color::LightCyan,
+                "admin" => Color::LightYellow,
+                "bio-maintenance" | "meal" => Color::LightMagenta,
+                "relax" | "recovery" => Color::Green,
+                "winddown" | "break" => Color::Gray,
+                "social" => Color::Cyan,
+                "planning" => Color::Yellow,
+                "project" => Color::Magenta,
+                _ => return None,
+            },
+            Palette::Colorblind => match block_type {
+                "deepwork" | "deepwork_input" | "deepwork_output" => CB_BLUE,
+                "class" => CB_ORANGE,
+                "training" | "fitness" => CB_VERMILLION,
+                "learning" | "review" => CB_SKY_BLUE,
+                "admin" => CB_YELLOW,
+                "bio-maintenance" | "meal" => CB_PURPLE,
+                "relax" | "recovery" => CB_BLUISH_GREEN,
+                "winddown" => Color::DarkGray,
+                "break" => Color::Gray,
+                "social" => Color::LightBlue,
+                "planning" => Color::LightYellow,
+                "project" => Color::LightMagenta,
+                _ => return None,
+            },
+        };
+        Some(color)
+    }
+
+    /// Regular text
+    pub fn text(&self) -> Color {
+        self.role(
+            "text",
+            match self.palette {
+                Palette::Light => Color::Reset,
+                Palette::Default | Palette::HighContrast | Palette::Colorblind => Color::White,
+            },
+        )
+    }
+
+    /// Secondary text: hints, times, completed and blocked tasks
+    pub fn muted(&self) -> Color {
+        self.role(
+            "muted",
+            match self.palette {
+                Palette::HighContrast => Color::Gray,
+                Palette::Default | Palette::Light | Palette::Colorblind => Color::DarkGray,
+            },
+        )
+    }
+
+    /// Tags and column headers
+    pub fn accent(&self) -> Color {
+        self.role(
+            "accent",
+            match self.palette {
+                Palette::Light => LIGHT_TEAL,
+                Palette::HighContrast => Color::LightCyan,
+                Palette::Default | Palette::Colorblind => Color::Cyan,
+            },
+        )
+    }
+
+    /// Text being typed, the focused form field, today and the current time
+    pub fn highlight(&self) -> Color {
+        self.role(
+            "highlight",
+            match self.palette {
+                Palette::Light => LIGHT_AMBER,
+                Palette::HighContrast => Color::LightYellow,
+                Palette::Default | Palette::Colorblind => Color::Yellow,
+            },
+        )
+    }
+
+    /// The selected row in the task lists
+    pub fn selection(&self) -> Style {
+        let style = Style::default().add_modifier(Modifier::BOLD);
+        match self.palette {
+            Palette::HighContrast => style
+                .fg(self.role("selection", Color::Black))
+                .bg(Color::LightYellow),
+            Palette::Default | Palette::Light | Palette::Colorblind => {
+                style.fg(self.role("selection", Color::Blue))
+            }
+        }
+    }
+
+    /// Background of the selected calendar cell
+    pub fn cursor(&self) -> Color {
+        self.role(
+            "cursor",
+            match self.palette {
+                Palette::Light => LIGHT_SELECTION,
+                Palette::HighContrast => Color::Blue,
+                Palette::Default | Palette::Colorblind => Color::DarkGray,
+            },
+        )
+    }
+
+    /// Background of popups drawn over the calendar
+    pub fn popup_bg(&self) -> Color {
+        self.role(
+            "popup_bg",
+            match self.palette {
+                Palette::Light => Color::Reset,
+                Palette::Default | Palette::HighContrast | Palette::Colorblind => Color::Black,
+            },
+        )
+    }
+
+    /// Synced calendar events
+    pub fn event(&self) -> Color {
+        self.role(
+            "event",
+            match self.palette {
+                Palette::Light => LIGHT_NAVY,
+                Palette::Colorblind => CB_SKY_BLUE,
+                Palette::Default | Palette::HighContrast => Color::LightCyan,
+            },
+        )
+    }
+
+    /// All-day events and tasks without a time
+    pub fn all_day(&self) -> Color {
+        self.role(
+            "all_day",
+            match self.palette {
+                Palette::Light => LIGHT_PLUM,
+                Palette::HighContrast => Color::LightMagenta,
+                Palette::Colorblind => CB_PURPLE,
+                Palette::Default => Color::Magenta,
+            },
+        )
+    }
+
+    /// Positive feedback: scheduled dates and status messages
+    pub fn success(&self) -> Color {
+        self.role(
+            "success",
+            match self.palette {
+                Palette::Default => Color::Green,
+                Palette::Light => LIGHT_GREEN,
+                Palette::HighContrast => Color::LightGreen,
+                Palette::Colorblind => CB_SKY_BLUE,
+            },
+        )
+    }
+
+    /// Errors, conflicts and urgent tasks in the calendar
+    pub fn error(&self) -> Color {
+        self.role(
+            "error",
+            match self.palette {
+                Palette::Default | Palette::Light => Color::Red,
+                Palette::HighContrast => Color::LightRed,
+                Palette::Colorblind => CB_ORANGE,
+            },
+        )
+    }
+
+    /// The conflict marker in the task list
+    pub fn warning(&self) -> Color {
+        self.role(
+            "warning",
+            match self.palette {
+                Palette::Default | Palette::HighContrast => Color::LightRed,
+                Palette::Light => LIGHT_AMBER,
+                Palette::Colorblind => CB_VERMILLION,
+            },
+        )
+    }
+
+    /// The [OVERDUE] marker and past due dates; the error color unless set
+    pub fn overdue(&self) -> Color {
+        self.role("overdue", self.error())
+    }
+}
+
+/// Parse `name = "color"` pairs, warning about (and skipping) unknown names
+/// when `known` is given and about colors ratatui can't parse
+fn parse_colors(
+    entries: HashMap<String, String>,
+    known: Option<&[&str]>,
+    path: &std::path::Path,
+) -> HashMap<String, Color> {
+    entries
+        .into_iter()
+        .filter_map(|(name, value)| {
+            if known.is_some_and(|known| !known.contains(&name.as_str())) {
+                tracing::warn!("⚠️  Ignoring unknown theme role \"{}\" in {:?}", name, path);
+                return None;
+            }
+            match Color::from_str(&value) {
+                Ok(color) => Some((name, color)),
+                Err(_) => {
+                    tracing::warn!(
+                        "⚠️  Ignoring invalid color \"{}\" for \"{}\" in {:?}",
+                        value,
+                        name,
+                        path
+                    );
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KNOWN_BLOCK_TYPES: &[&str] = &[
+        "deepwork", "class", "training", "learning", "admin", "meal", "relax",
+    ];
+
+    fn with_palette(palette: Palette) -> Theme {
+        Theme {
+            palette,
+            ..Theme::default()
+        }
+    }
+
+    #[test]
+    fn colorblind_palette_recolors_known_block_types() {
+        let default = with_palette(Palette::Default);
+        let colorblind = with_palette(Palette::Colorblind);
+
+        for block_type in KNOWN_BLOCK_TYPES {
+            assert_ne!(
+                default.block_color(block_type),
+                colorblind.block_color(block_type),
+                "{}",
+                block_type
+            );
+        }
+        // Class and training are green and red by default
+        assert_ne!(
+            colorblind.block_color("class"),
+            colorblind.block_color("training")
+        );
+        assert_ne!(default.priority_color(3), colorblind.priority_color(3));
+    }
+
+    #[test]
+    fn unknown_block_types_fall_back_to_text() {
+        let colorblind = with_palette(Palette::Colorblind);
+        assert_eq!(colorblind.block_color("gardening"), colorblind.text());
+    }
+
+    #[test]
+    fn theme_file_block_colors_win_over_the_palette() {
+        let theme = Theme {
+            palette: Palette::Colorblind,
+            blocks: HashMap::from([("class".to_string(), Color::White)]),
+            ..Theme::default()
+        };
+        assert_eq!(theme.block_color("class"), Color::White);
+        assert_eq!(theme.block_color("training"), CB_VERMILLION);
+    }
+
+    #[test]
+    fn imported_block_types_have_builtin_colors_in_every_palette() {
+        for palette in [
+            Palette::Default,
+            Palette::Light,
+            Palette::HighContrast,
+            Palette::Colorblind,
+        ] {
+            let theme = with_palette(palette);
+            for block_type in ["relax", "winddown", "recovery"] {
+                assert!(
+                    theme.builtin_block_color(block_type).is_some(),
+                    "{:?} has no color for {}",
+                    palette,
+                    block_type
+                );
+            }
+        }
+    }
+}
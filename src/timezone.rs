@@ -0,0 +1,79 @@
+//! The timezone Triptych plans in. Times are stored as wall-clock values
+//! ("3pm" is saved as 15:00), so reading the clock, parsing "today" and
+//! converting synced events all go through the one zone chosen here.
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use std::sync::OnceLock;
+
+static ZONE: OnceLock<Tz> = OnceLock::new();
+
+/// The IANA zone called `name`, like "Europe/Berlin" (or "utc" in any case)
+pub fn parse(name: &str) -> Option<Tz> {
+    if name.eq_ignore_ascii_case("utc") {
+        return Some(Tz::UTC);
+    }
+    name.parse().ok()
+}
+
+/// Plan in `name` (from `--tz`), falling back to TRIPTYCH_TZ, then to `TZ`
+/// and the system zone. Returns false if `name` isn't a known zone.
+pub fn init(name: Option<&str>) -> bool {
+    let zone = match name {
+        Some(name) => match parse(name) {
+            Some(zone) => zone,
+            None => return false,
+        },
+        None => match std::env::var("TRIPTYCH_TZ") {
+            Ok(name) if name.is_empty() => system(),
+            Ok(name) => parse(&name).unwrap_or_else(|| {
+                tracing::warn!("⚠️  Ignoring unknown TRIPTYCH_TZ \"{}\"", name);
+                system()
+            }),
+            Err(_) => system(),
+        },
+    };
+    ZONE.get_or_init(|| zone);
+    true
+}
+
+/// The zone named by `TZ`, else the one the system is set to, else UTC
+fn system() -> Tz {
+    if let Ok(name) = std::env::var("TZ")
+        && let Some(zone) = parse(name.trim_start_matches(':'))
+    {
+        return zone;
+    }
+    iana_time_zone::get_timezone()
+        .ok()
+        .and_then(|name| parse(&name))
+        .unwrap_or(Tz::UTC)
+}
+
+/// The planning zone; the system's until `init` picks one
+pub fn zone() -> Tz {
+    *ZONE.get_or_init(system)
+}
+
+/// The current time in the planning zone
+pub fn now() -> DateTime<Tz> {
+    Utc::now().with_timezone(&zone())
+}
+
+/// The wall-clock time `at` reads as in the planning zone
+pub fn wall_clock(at: DateTime<Utc>) -> NaiveDateTime {
+    at.with_timezone(&zone()).naive_local()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_iana_names_and_utc() {
+        assert_eq!(parse("Europe/Berlin"), Some(Tz::Europe__Berlin));
+        assert_eq!(parse("utc"), Some(Tz::UTC));
+        assert_eq!(parse("Mars/Olympus_Mons"), None);
+        assert_eq!(parse("../etc/passwd"), None);
+    }
+}
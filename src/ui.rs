@@ -1,29 +1,143 @@
-use crate::app::{App, BlockFormField, CalendarInputMode, InputMode, ScheduleBlock, ViewMode};
+use crate::app::{
+    App, BlockFormField, CalendarData, CalendarInputMode, EventSegment, InputMode, ScheduleBlock,
+    ViewMode, parse_time_string,
+};
+use crate::display::{DayLabel, DisplayConfig};
+use crate::theme::Theme;
 use chrono::{Datelike, Duration, NaiveDate, NaiveTime, Timelike};
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    text::{Line, Span},
-    widgets::{Block, Borders, Cell, Clear, List, ListItem, ListState, Paragraph, Row, Table},
+    text::{Line, Span, Text},
+    widgets::{
+        Block, Borders, Cell, Clear, List, ListItem, ListState, Paragraph, Row, Table, TableState,
+    },
 };
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Where the last frame drew the parts of the screen that respond to the
+/// mouse, so clicks can be mapped back to tasks and calendar cells
+#[derive(Debug, Default, Clone)]
+pub struct ScreenLayout {
+    /// Inside of the to-do list's border, if it's on screen
+    task_list: Option<Rect>,
+    /// Index of the to-do list's top row, kept across frames so the list
+    /// only scrolls when the selection would leave it
+    task_list_offset: usize,
+    /// Week grid day columns, left to right
+    week_columns: Vec<Rect>,
+    /// Day of the week (0 = Monday) in the first column; narrow terminals
+    /// show fewer than seven
+    week_first_day: usize,
+    /// Visible week grid slot rows as (first line, height, slot index)
+    week_rows: Vec<(u16, u16, usize)>,
+}
+
+impl ScreenLayout {
+    /// Task index under a screen position, and whether it hit the checkbox
+    pub fn task_at(&self, app: &App, column: u16, row: u16) -> Option<(usize, bool)> {
+        let area = self.task_list?;
+        if !area.contains(ratatui::layout::Position::new(column, row)) {
+            return None;
+        }
+        let index = self.task_list_offset + (row - area.y) as usize;
+        let task = app.tasks.get(index)?;
+
+        // The highlight symbol and subtask indent come before "[ ]"
+        let depth = app.task_depths.get(&task.id).copied().unwrap_or(0) as u16;
+        let checkbox = area.x + HIGHLIGHT_SYMBOL.len() as u16 + depth * 2;
+        Some((index, (checkbox..checkbox + 3).contains(&column)))
+    }
+
+    /// Tasks that fit in the list at once, for paging
+    pub fn task_page_size(&self) -> usize {
+        self.task_list
+            .map_or(10, |area| (area.height as usize).max(1))
+    }
+
+    /// Week grid (day column, slot) under a screen position
+    pub fn week_cell_at(&self, column: u16, row: u16) -> Option<(usize, usize)> {
+        let day = self
+            .week_columns
+            .iter()
+            .position(|rect| (rect.x..rect.right()).contains(&column))?
+            + self.week_first_day;
+        let slot = self
+            .week_rows
+            .iter()
+            .find(|(y, height, _)| (*y..y + height).contains(&row))?
+            .2;
+        Some((day, slot))
+    }
+}
+
+const HIGHLIGHT_SYMBOL: &str = "> ";
+
+/// Below this size the views can't lay themselves out, so only a notice is drawn
+const MIN_WIDTH: u16 = 50;
+const MIN_HEIGHT: u16 = 12;
+
+/// Narrowest week grid day column worth drawing: a marker, a time and a
+/// truncated title
+const MIN_DAY_WIDTH: u16 = 14;
+const TIME_COLUMN_WIDTH: u16 = 8;
 
-pub fn ui(f: &mut Frame, app: &App) {
+/// Draw the current view. `previous` is the layout of the last frame, whose
+/// list scroll position carries over.
+pub fn ui(f: &mut Frame, app: &App, previous: &ScreenLayout) -> ScreenLayout {
+    let mut layout = ScreenLayout {
+        task_list_offset: previous.task_list_offset,
+        ..ScreenLayout::default()
+    };
+    let area = f.area();
+    if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+        render_too_small(f, area);
+        return layout;
+    }
     match app.view_mode {
-        ViewMode::TodoList => render_todo_view(f, app),
-        ViewMode::Calendar => render_calendar_view(f, app),
+        ViewMode::TodoList => render_todo_view(f, app, &mut layout),
+        ViewMode::Calendar => render_calendar_view(f, app, &mut layout),
+        ViewMode::Day => render_day_view(f, app),
+        ViewMode::Month => render_month_view(f, app),
     }
+    layout
+}
+
+fn render_too_small(f: &mut Frame, area: Rect) {
+    f.render_widget(Clear, area);
+    let text = format!(
+        "Terminal too small: {}x{} (need {}x{})",
+        area.width, area.height, MIN_WIDTH, MIN_HEIGHT
+    );
+    let y = area.y + area.height / 2;
+    let line = Rect::new(area.x, y, area.width, area.bottom() - y);
+    f.render_widget(
+        Paragraph::new(text)
+            .alignment(ratatui::layout::Alignment::Center)
+            .wrap(ratatui::widgets::Wrap { trim: true }),
+        line,
+    );
 }
 
-fn render_todo_view(f: &mut Frame, app: &App) {
+fn render_todo_view(f: &mut Frame, app: &App, layout: &mut ScreenLayout) {
     f.render_widget(Clear, f.area());
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
-        .constraints([Constraint::Min(3), Constraint::Length(3)].as_ref())
+        .constraints(
+            [
+                Constraint::Min(3),
+                Constraint::Length(3),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
         .split(f.area());
+    render_footer(f, app, chunks[2]);
 
+    let local_now = crate::timezone::now().naive_local();
     let items: Vec<ListItem> = app
         .tasks
         .iter()
@@ -37,93 +151,247 @@ fn render_todo_view(f: &mut Frame, app: &App) {
                 Vec::new()
             };
 
-            // Build the display line with colors and indicators
-            let mut spans = vec![Span::raw(format!("{} ", status))];
+            // Build the display line with colors and indicators, subtasks
+            // indented under their parent
+            let depth = app.task_depths.get(&task.id).copied().unwrap_or(0);
+            let mut spans = vec![Span::raw(format!("{}{} ", "  ".repeat(depth), status))];
+
+            let blocked = !task.completed && app.blocked_task_ids.contains(&task.id);
+            if blocked {
+                spans.push(Span::raw("🔒 "));
+            }
+
+            if app.conflicting_task_ids.contains(&task.id) {
+                spans.push(Span::styled(
+                    "⚠ ",
+                    Style::default()
+                        .fg(app.display.theme.warning())
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
 
             // Add priority indicator with text
-            match task.priority {
-                3 => spans.push(Span::styled("[HIGH] ", Style::default().fg(Color::Red))),
-                2 => spans.push(Span::styled("[MED] ", Style::default().fg(Color::Yellow))),
-                1 => spans.push(Span::styled("[LOW] ", Style::default().fg(Color::Blue))),
-                _ => {}
+            let priority_label = match task.priority {
+                3 => Some("[HIGH] "),
+                2 => Some("[MED] "),
+                1 => Some("[LOW] "),
+                _ => None,
+            };
+            if let Some(label) = priority_label {
+                spans.push(Span::styled(
+                    label,
+                    Style::default().fg(app.display.theme.priority_color(task.priority)),
+                ));
             }
 
             // Add schedule indicator with date and time info
             if let Some(scheduled) = task.scheduled_at {
-                let now = chrono::Utc::now();
-                let scheduled_date = scheduled.date_naive();
-                let today = now.date_naive();
-                let tomorrow = today + chrono::Duration::days(1);
-
-                let time_str = scheduled.format("%l:%M%P").to_string().trim().to_string();
-
-                let date_text = if scheduled_date == today {
-                    format!("[TODAY {}]", time_str)
-                } else if scheduled_date == tomorrow {
-                    format!("[TMR {}]", time_str)
+                // Date-only tasks carry a placeholder time that shouldn't be shown
+                let day_text = match app.display.day_label(scheduled.naive_utc(), local_now) {
+                    DayLabel::Today => "TODAY".to_string(),
+                    DayLabel::Tomorrow => "TMR".to_string(),
+                    DayLabel::Date(date) => app.display.format_date(date),
+                };
+                let date_text = if task.scheduled_time_known {
+                    format!(
+                        "[{} {}]",
+                        day_text,
+                        app.display.format_time(scheduled.time())
+                    )
                 } else {
-                    format!("[{} {}]", scheduled.format("%m/%d"), time_str)
+                    format!("[{}]", day_text)
                 };
 
-                spans.push(Span::styled(
-                    format!("{} ", date_text),
-                    Style::default().fg(Color::Green),
-                ));
+                if app.is_overdue(task, local_now) {
+                    spans.push(Span::styled(
+                        "[OVERDUE] ",
+                        Style::default()
+                            .fg(app.display.theme.overdue())
+                            .add_modifier(Modifier::BOLD),
+                    ));
+                    spans.push(Span::styled(
+                        format!("{} ", date_text),
+                        Style::default().fg(app.display.theme.overdue()),
+                    ));
+                } else {
+                    spans.push(Span::styled(
+                        format!("{} ", date_text),
+                        Style::default().fg(app.display.theme.success()),
+                    ));
+                }
             }
 
-            // Add description with category color
-            let category_color = match task.task_category.as_deref() {
-                Some("deepwork") => Color::Blue,
-                Some("admin") => Color::Yellow,
-                Some("learning") => Color::Cyan,
-                Some("fitness") => Color::Red,
-                _ => Color::White,
+            // Add description with category color, dimmed while blocked
+            let category_color = app
+                .display
+                .theme
+                .category_color(task.task_category.as_deref());
+            let description_style = if blocked {
+                Style::default()
+                    .fg(app.display.theme.muted())
+                    .add_modifier(Modifier::DIM)
+            } else {
+                Style::default().fg(category_color)
             };
-            spans.push(Span::styled(
-                task.description.as_str(),
-                Style::default().fg(category_color),
-            ));
+            spans.push(Span::styled(task.description.as_str(), description_style));
 
             // Add tags
             if !tags.is_empty() {
                 spans.push(Span::styled(
                     format!(" #{}", tags.join(" #")),
-                    Style::default().fg(Color::Cyan),
+                    Style::default().fg(app.display.theme.accent()),
                 ));
             }
 
+            if task.notes.is_some() {
+                spans.push(Span::raw(" 📝"));
+            }
+            if task.recurrence_rule.is_some() {
+                spans.push(Span::raw(" 🔁"));
+            }
+
             ListItem::new(Line::from(spans))
         })
         .collect();
 
-    let mut state = ListState::default();
+    let overdue_label = match app.overdue_count(local_now) {
+        0 => String::new(),
+        count => format!(" ({} overdue)", count),
+    };
+
+    let mut state = ListState::default().with_offset(layout.task_list_offset);
     state.select(Some(app.selected));
 
     let tasks_list = List::new(items)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title("To-Do (q: quit, a: add, x: delete, s: schedule, k/j: move, ENTER: toggle)"),
-        )
-        .highlight_style(
-            Style::default()
-                .fg(Color::Blue)
-                .add_modifier(Modifier::BOLD),
+                .title(format!("To-Do [{} order]{} (q: quit, a: add, A: add subtask, n: notes, /: search, x: delete, X: clear done, u: undo, v: event, s: schedule, r: reschedule, b: plan into block, p: priority, o: sort, k/j: move, K/J: reorder, ENTER: toggle)", app.sort_mode.name(), overdue_label)),
         )
-        .highlight_symbol("> ");
+        .highlight_style(app.display.theme.selection())
+        .highlight_symbol(HIGHLIGHT_SYMBOL);
 
     f.render_stateful_widget(tasks_list, chunks[0], &mut state);
+    layout.task_list = Some(chunks[0].inner(ratatui::layout::Margin::new(1, 1)));
+    layout.task_list_offset = state.offset();
 
     match app.input_mode {
         InputMode::Editing => {
             let input_box = Paragraph::new(app.input_buffer.as_str())
-                .style(Style::default().fg(Color::Yellow))
+                .style(Style::default().fg(app.display.theme.highlight()))
                 .block(
                     Block::default()
                         .borders(Borders::ALL)
-                        .title("New Task (Enter to save, Esc to cancel) - Try: 'Submit report tomorrow #work urgent'"),
+                        .title("New Task (Enter to save, Tab: complete #tag, Esc to cancel) - Try: 'Submit report tomorrow #work urgent'"),
                 );
             f.render_widget(input_box, chunks[1]);
+            render_tag_completions(f, app, chunks[1]);
+
+            f.set_cursor_position(ratatui::layout::Position {
+                x: chunks[1].x + app.input_buffer.chars().count() as u16 + 1,
+                y: chunks[1].y + 1,
+            });
+        }
+        InputMode::EditingNotes => render_notes_editor(f, app),
+        InputMode::ConfirmingClear => render_clear_confirmation(f, app),
+        InputMode::AddingSubtask => {
+            let parent = app
+                .tasks
+                .get(app.selected)
+                .map(|task| task.description.as_str())
+                .unwrap_or_default();
+            let input_box = Paragraph::new(app.input_buffer.as_str())
+                .style(Style::default().fg(app.display.theme.highlight()))
+                .block(Block::default().borders(Borders::ALL).title(format!(
+                    "New subtask of \"{}\" (Enter to save, Esc to cancel)",
+                    parent
+                )));
+            f.render_widget(input_box, chunks[1]);
+
+            f.set_cursor_position(ratatui::layout::Position {
+                x: chunks[1].x + app.input_buffer.chars().count() as u16 + 1,
+                y: chunks[1].y + 1,
+            });
+        }
+        InputMode::Planning => {
+            let (title, title_style) = match &app.input_error {
+                Some(err) => (
+                    format!("{} (Esc to cancel)", err),
+                    Style::default().fg(app.display.theme.error()),
+                ),
+                None if app.cached_block_types.is_empty() => (
+                    "Plan into block type (Enter to plan, Esc to cancel) - no blocks defined yet"
+                        .to_string(),
+                    Style::default(),
+                ),
+                None => (
+                    format!(
+                        "Plan into block type (Enter to plan, Esc to cancel) - {}",
+                        app.cached_block_types.join(", ")
+                    ),
+                    Style::default(),
+                ),
+            };
+            let input_box = Paragraph::new(app.input_buffer.as_str())
+                .style(Style::default().fg(app.display.theme.highlight()))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(Span::styled(title, title_style)),
+                );
+            f.render_widget(input_box, chunks[1]);
+
+            f.set_cursor_position(ratatui::layout::Position {
+                x: chunks[1].x + app.input_buffer.chars().count() as u16 + 1,
+                y: chunks[1].y + 1,
+            });
+        }
+        InputMode::Rescheduling => {
+            let (title, title_style) = match &app.input_error {
+                Some(err) => (
+                    format!("{} (Esc to cancel)", err),
+                    Style::default().fg(app.display.theme.error()),
+                ),
+                None => (
+                    "Reschedule (Enter to save, Esc to cancel) - Try: 'tomorrow at 3pm' or 'next monday'"
+                        .to_string(),
+                    Style::default(),
+                ),
+            };
+            let input_box = Paragraph::new(app.input_buffer.as_str())
+                .style(Style::default().fg(app.display.theme.highlight()))
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(Span::styled(title, title_style)),
+                );
+            f.render_widget(input_box, chunks[1]);
+
+            f.set_cursor_position(ratatui::layout::Position {
+                x: chunks[1].x + app.input_buffer.chars().count() as u16 + 1,
+                y: chunks[1].y + 1,
+            });
+        }
+        InputMode::Searching => {
+            render_search_results(f, app, chunks[0]);
+
+            let title = if app.input_buffer.trim().is_empty() {
+                "Search tasks, events and emails (Esc to cancel)".to_string()
+            } else {
+                format!(
+                    "Search - {} match{} (Enter to open, Up/Down to choose, Esc to cancel)",
+                    app.search_results.len(),
+                    if app.search_results.len() == 1 {
+                        ""
+                    } else {
+                        "es"
+                    }
+                )
+            };
+            let input_box = Paragraph::new(app.input_buffer.as_str())
+                .style(Style::default().fg(app.display.theme.highlight()))
+                .block(Block::default().borders(Borders::ALL).title(title));
+            f.render_widget(input_box, chunks[1]);
 
             f.set_cursor_position(ratatui::layout::Position {
                 x: chunks[1].x + app.input_buffer.chars().count() as u16 + 1,
@@ -135,7 +403,7 @@ fn render_todo_view(f: &mut Frame, app: &App) {
                 && instant.elapsed() < std::time::Duration::from_secs(3)
             {
                 let status = Paragraph::new(msg.as_str())
-                    .style(Style::default().fg(Color::Green))
+                    .style(Style::default().fg(app.display.theme.success()))
                     .block(Block::default().borders(Borders::ALL));
                 f.render_widget(status, chunks[1]);
             }
@@ -143,44 +411,223 @@ fn render_todo_view(f: &mut Frame, app: &App) {
     }
 }
 
-fn render_calendar_view(f: &mut Frame, app: &App) {
+/// Tags matching the `#` word being typed, in a box just above the input
+/// and lined up with the word; Tab takes the first
+fn render_tag_completions(f: &mut Frame, app: &App, input: Rect) {
+    let completions = app.tag_completions();
+    if completions.is_empty() {
+        return;
+    }
+
+    let lines: Vec<Line> = completions
+        .iter()
+        .enumerate()
+        .map(|(index, (tag, count))| {
+            let style = if index == 0 {
+                Style::default()
+                    .fg(app.display.theme.highlight())
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(app.display.theme.accent())
+            };
+            Line::from(vec![
+                Span::styled(format!("#{}", tag), style),
+                Span::styled(
+                    format!(" {}", count),
+                    Style::default().fg(app.display.theme.muted()),
+                ),
+            ])
+        })
+        .collect();
+
+    let width = (lines.iter().map(Line::width).max().unwrap_or(0) as u16 + 2).min(input.width);
+    let height = (lines.len() as u16 + 2).min(input.y);
+    let word_start = app.input_buffer.chars().count()
+        - app
+            .input_buffer
+            .rsplit(char::is_whitespace)
+            .next()
+            .map_or(0, |word| word.chars().count());
+    let x = (input.x + 1 + word_start as u16).min(input.right().saturating_sub(width));
+    let area = Rect::new(x, input.y - height, width, height);
+
+    f.render_widget(Clear, area);
+    f.render_widget(
+        Paragraph::new(lines)
+            .style(Style::default().bg(app.display.theme.popup_bg()))
+            .block(Block::default().borders(Borders::ALL)),
+        area,
+    );
+}
+
+/// Popup asking whether to move the completed tasks to the trash
+fn render_clear_confirmation(f: &mut Frame, app: &App) {
+    // Half the width, and tall enough for the question and the keys
+    let columns = centered_rect(50, 100, f.area());
+    let height = 5.min(columns.height);
+    let area = Rect::new(
+        columns.x,
+        columns.y + (columns.height - height) / 2,
+        columns.width,
+        height,
+    );
+    f.render_widget(Clear, area);
+
+    let count = app.tasks.iter().filter(|task| task.completed).count();
+    let text = format!(
+        "Move {} completed task{} to the trash?\n\ny: clear, any other key: cancel",
+        count,
+        if count == 1 { "" } else { "s" }
+    );
+    let popup = Paragraph::new(text)
+        .alignment(ratatui::layout::Alignment::Center)
+        .wrap(ratatui::widgets::Wrap { trim: true })
+        .style(
+            Style::default()
+                .fg(app.display.theme.warning())
+                .bg(app.display.theme.popup_bg()),
+        )
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Clear completed"),
+        );
+    f.render_widget(popup, area);
+}
+
+/// Multi-line notes editor for the selected task, drawn over the list. The
+/// cursor always sits at the end of the text, which scrolls to keep it visible.
+fn render_notes_editor(f: &mut Frame, app: &App) {
+    let area = centered_rect(70, 60, f.area());
+    f.render_widget(Clear, area);
+
+    let description = app
+        .tasks
+        .get(app.selected)
+        .map(|task| task.description.as_str())
+        .unwrap_or_default();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            "Notes: {} (Enter: new line, Ctrl+S: save, Esc: cancel)",
+            description
+        ))
+        .style(Style::default().bg(app.display.theme.popup_bg()));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let lines: Vec<&str> = app.input_buffer.split('\n').collect();
+    let last_line = lines.len() as u16 - 1;
+    let scroll = last_line.saturating_sub(inner.height.saturating_sub(1));
+
+    let editor = Paragraph::new(app.input_buffer.as_str())
+        .style(Style::default().fg(app.display.theme.highlight()))
+        .scroll((scroll, 0));
+    f.render_widget(editor, inner);
+
+    let column = lines.last().map_or(0, |line| line.chars().count()) as u16;
+    f.set_cursor_position(ratatui::layout::Position {
+        x: inner.x + column.min(inner.width.saturating_sub(1)),
+        y: inner.y + last_line - scroll,
+    });
+}
+
+/// Search hits drawn over the task list, most recent first
+fn render_search_results(f: &mut Frame, app: &App, area: Rect) {
+    f.render_widget(Clear, area);
+
+    let items: Vec<ListItem> = app
+        .search_results
+        .iter()
+        .map(|result| {
+            let status = if result.completed { "[✓]" } else { "[ ]" };
+            let mut spans = vec![
+                Span::raw(format!("{} ", status)),
+                Span::styled(
+                    format!("{:<6}", result.kind.name()),
+                    Style::default().fg(app.display.theme.accent()),
+                ),
+            ];
+            if let Some(at) = result.at {
+                spans.push(Span::styled(
+                    format!("[{}] ", app.display.format_date(at.date_naive())),
+                    Style::default().fg(app.display.theme.success()),
+                ));
+            }
+            spans.push(Span::raw(result.title.as_str()));
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let mut state = ListState::default();
+    state.select((!app.search_results.is_empty()).then_some(app.search_selected));
+
+    let results = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Search Results"),
+        )
+        .highlight_style(app.display.theme.selection())
+        .highlight_symbol("> ");
+
+    f.render_stateful_widget(results, area, &mut state);
+}
+
+fn render_calendar_view(f: &mut Frame, app: &App, layout: &mut ScreenLayout) {
     f.render_widget(Clear, f.area());
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
-        .constraints([Constraint::Min(3)].as_ref())
+        .constraints(
+            [
+                Constraint::Min(3),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
         .split(f.area());
+    render_footer(f, app, chunks[2]);
+
+    let grid = build_calendar_grid(app);
 
-    let calendar_data = build_calendar_grid(app);
+    // Show as many days as fit, keeping the selected one on screen
+    let day_count = week_days_that_fit(chunks[0].width);
+    let first_day = (app.selected_day() / day_count * day_count).min(7 - day_count);
+    let shown_days = first_day..first_day + day_count;
+    layout.week_first_day = first_day;
 
     // Check if calendar is empty (no blocks and no tasks)
-    let is_empty =
-        calendar_data.schedule_blocks.is_empty() && calendar_data.scheduled_tasks.is_empty();
+    let is_empty = grid.data.schedule_blocks.is_empty()
+        && grid.data.scheduled_tasks.is_empty()
+        && grid.data.all_day.is_empty();
 
     // Build header with weekday names
     let header_strings: Vec<String> = std::iter::once("Time".to_string())
         .chain(
-            calendar_data
-                .days
+            grid.days[shown_days.clone()]
                 .iter()
-                .map(|d| d.format("%a %m/%d").to_string()),
+                .map(|d| app.display.format_day(*d)),
         )
         .collect();
 
-    let today = chrono::Local::now().naive_local().date();
+    let today = app
+        .display
+        .logical_date(crate::timezone::now().naive_local());
 
     let header_cells: Vec<Cell> = header_strings
         .iter()
         .enumerate()
         .map(|(idx, h)| {
-            let style = if idx > 0 && calendar_data.days[idx - 1] == today {
+            let style = if idx > 0 && grid.days[first_day + idx - 1] == today {
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(app.display.theme.highlight())
                     .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
             } else {
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(app.display.theme.accent())
                     .add_modifier(Modifier::BOLD)
             };
             Cell::from(h.as_str()).style(style)
@@ -189,61 +636,204 @@ fn render_calendar_view(f: &mut Frame, app: &App) {
 
     let header = Row::new(header_cells).height(1).bottom_margin(1);
 
-    // Build rows for each time slot with cursor highlight
-    let rows: Vec<Row> = calendar_data
-        .time_slots
-        .iter()
-        .enumerate()
-        .map(|(slot_idx, slot)| {
-            let mut cells = vec![Cell::from(slot.time_label.clone())];
+    // Date-only tasks sit above the hourly grid instead of a placeholder slot
+    let all_day_row = build_all_day_row(&grid, &grid.days[shown_days.clone()]);
 
-            for day_idx in 0..7 {
-                let mut cell_content = build_cell_content(&calendar_data, day_idx, &slot.time);
+    // Hour slots have room for a second line (block times, event end)
+    let row_height = if grid.single_line() { 1 } else { 2 };
 
-                // Highlight selected cell
-                if day_idx == app.selected_day
+    // The slot and column holding the current time, if this week shows it
+    let now = grid.now();
+
+    // Build rows for each time slot with cursor highlight
+    let slot_rows = grid.time_slots.iter().enumerate().map(|(slot_idx, slot)| {
+        let is_now_row = now.is_some_and(|(_, now_slot)| now_slot == slot_idx);
+        let mut cells = vec![if is_now_row {
+            Cell::from(format!("{}◄", slot.time_label)).style(now_style(&app.display.theme))
+        } else {
+            Cell::from(slot.time_label.clone())
+        }];
+
+        for day_idx in shown_days.clone() {
+            let mut cell_content = build_cell_content(&grid, day_idx, &slot.time);
+            if is_now_row
+                && now.is_some_and(|(now_day, _)| now_day == day_idx)
+                && get_cell_text(&grid, day_idx, &slot.time).is_empty()
+            {
+                cell_content = Cell::from("◄ now").style(now_style(&app.display.theme));
+            }
+
+            // Highlight selected cell
+            if day_idx == app.selected_day()
+                && slot_idx == app.selected_time_slot
+                && app.calendar_input_mode == CalendarInputMode::Navigate
+            {
+                let display_text = if is_empty
                     && slot_idx == app.selected_time_slot
-                    && app.calendar_input_mode == CalendarInputMode::Navigate
+                    && day_idx == app.selected_day()
                 {
-                    let display_text = if is_empty
-                        && slot_idx == app.selected_time_slot
-                        && day_idx == app.selected_day
-                    {
-                        "[n: add block]".to_string()
-                    } else {
-                        // Get existing text or empty
-                        get_cell_text(&calendar_data, day_idx, &slot.time)
-                    };
-                    cell_content = Cell::from(display_text).style(
-                        Style::default()
-                            .bg(Color::DarkGray)
-                            .add_modifier(Modifier::BOLD),
-                    );
-                }
-
-                cells.push(cell_content);
+                    "[n: add block]".to_string()
+                } else {
+                    // Get existing text or empty
+                    get_cell_text(&grid, day_idx, &slot.time)
+                };
+                cell_content = Cell::from(display_text).style(
+                    Style::default()
+                        .bg(app.display.theme.cursor())
+                        .add_modifier(Modifier::BOLD),
+                );
             }
 
-            Row::new(cells).height(2)
-        })
-        .collect();
+            cells.push(cell_content);
+        }
 
-    // Calculate column widths: time column + 7 day columns
-    let widths = vec![Constraint::Length(8)]
+        Row::new(cells).height(row_height)
+    });
+    let rows: Vec<Row> = std::iter::once(all_day_row).chain(slot_rows).collect();
+
+    // Scroll so the selected slot sits mid-screen; borders, header and the
+    // all-day row take six lines
+    let visible = (chunks[0].height.saturating_sub(6) / row_height) as usize;
+    let selected_row = 1 + app.selected_time_slot;
+    let offset = selected_row
+        .saturating_sub(visible / 2)
+        .min(rows.len().saturating_sub(visible));
+    let mut state = TableState::default().with_offset(offset);
+
+    // Calculate column widths: time column + the day columns that fit
+    let widths = vec![Constraint::Length(TIME_COLUMN_WIDTH)]
         .into_iter()
-        .chain(std::iter::repeat_n(Constraint::Fill(1), 7))
+        .chain(std::iter::repeat_n(Constraint::Fill(1), day_count))
         .collect::<Vec<_>>();
 
-    let title = "Weekly Calendar (t: todo, h/l/j/k: move, H/L: week, n: block, s: schedule, a: add task, q: quit)".to_string();
+    let title = if day_count < 7 {
+        format!(
+            "Weekly Calendar, {}-{} (too narrow for the whole week; h/l: move, t: todo, q: quit)",
+            grid.days[first_day].weekday(),
+            grid.days[first_day + day_count - 1].weekday()
+        )
+    } else {
+        "Weekly Calendar (t: todo, v: view, h/l/j/k: move, H/L: week, </>: shift task, n: block, Enter: edit, d: del block, s: schedule, a: add task, q: quit)".to_string()
+    };
+
+    // Record where the rows and day columns land for mouse clicks: past the
+    // border, the header and its margin, then the two-line all-day row
+    let inner = chunks[0].inner(ratatui::layout::Margin::new(1, 1));
+    layout.week_columns = Layout::horizontal(widths.clone())
+        .spacing(1)
+        .split(inner)
+        .iter()
+        .skip(1)
+        .copied()
+        .collect();
+    let mut y = inner.y + 2;
+    for row in offset..rows.len() {
+        let height = if row == 0 { 2 } else { row_height };
+        if y >= inner.bottom() {
+            break;
+        }
+        if row > 0 {
+            layout.week_rows.push((y, height, row - 1));
+        }
+        y += height;
+    }
 
     let table = Table::new(rows, widths)
         .header(header)
         .block(Block::default().borders(Borders::ALL).title(title))
         .column_spacing(1);
 
-    f.render_widget(table, chunks[0]);
+    f.render_stateful_widget(table, chunks[0], &mut state);
+    render_block_legend(f, app, chunks[1]);
+    render_calendar_overlay(f, app);
+}
+
+/// One-line summary under every view: task counts, whether Ollama is
+/// parsing, and how the calendar sync is doing
+fn render_footer(f: &mut Frame, app: &App, area: Rect) {
+    let theme = &app.display.theme;
+    let dim = Style::default().fg(theme.muted());
+
+    let done = app.tasks.iter().filter(|t| t.completed).count();
+    let overdue = app.overdue_count(crate::timezone::now().naive_local());
+    let mut spans = vec![Span::styled(
+        format!("{} tasks · {} done · ", app.tasks.len(), done),
+        dim,
+    )];
+    spans.push(if overdue > 0 {
+        Span::styled(
+            format!("{} overdue", overdue),
+            Style::default().fg(theme.overdue()),
+        )
+    } else {
+        Span::styled("0 overdue", dim)
+    });
+
+    spans.push(Span::styled("  │  NLP: ", dim));
+    spans.push(if app.is_ollama_available() {
+        Span::styled("Ollama", Style::default().fg(theme.success()))
+    } else {
+        Span::styled("regex only", Style::default().fg(theme.warning()))
+    });
+
+    spans.push(Span::styled("  │  Sync: ", dim));
+    spans.push(match app.sync_status.as_deref() {
+        Some(status) if status.calendar_enabled => match status.last_calendar_sync() {
+            Some((at, Ok(count))) => Span::styled(
+                format!("✓ {} events {}", count, time_ago(at.elapsed())),
+                Style::default().fg(theme.success()),
+            ),
+            Some((at, Err(e))) => Span::styled(
+                format!("✗ failed {}: {}", time_ago(at.elapsed()), e),
+                Style::default().fg(theme.error()),
+            ),
+            None => Span::styled("syncing…", dim),
+        },
+        _ => Span::styled("off", dim),
+    });
+
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// "just now", "5m ago" or "2h ago"
+fn time_ago(elapsed: std::time::Duration) -> String {
+    match elapsed.as_secs() {
+        secs if secs < 60 => "just now".to_string(),
+        secs if secs < 3600 => format!("{}m ago", secs / 60),
+        secs => format!("{}h ago", secs / 3600),
+    }
+}
 
-    // Render overlays based on calendar input mode
+/// Legend of block types actually present in the schedule, replaced for a
+/// few seconds by the latest status message
+fn render_block_legend(f: &mut Frame, app: &App, area: Rect) {
+    if let Some((msg, instant)) = &app.status_message
+        && instant.elapsed() < std::time::Duration::from_secs(3)
+    {
+        let status =
+            Paragraph::new(msg.as_str()).style(Style::default().fg(app.display.theme.warning()));
+        f.render_widget(status, area);
+        return;
+    }
+
+    let legend: Vec<Span> = app
+        .cached_block_types
+        .iter()
+        .flat_map(|block_type| {
+            [
+                Span::styled(
+                    format!("■ {}", block_type),
+                    get_block_style(&app.display.theme, block_type),
+                ),
+                Span::raw("  "),
+            ]
+        })
+        .collect();
+    f.render_widget(Paragraph::new(Line::from(legend)), area);
+}
+
+/// Render overlays based on calendar input mode
+fn render_calendar_overlay(f: &mut Frame, app: &App) {
     match app.calendar_input_mode {
         CalendarInputMode::BlockForm => render_block_form_popup(f, app),
         CalendarInputMode::TaskPicker => render_task_picker(f, app),
@@ -252,11 +842,363 @@ fn render_calendar_view(f: &mut Frame, app: &App) {
     }
 }
 
-struct CalendarGrid {
+/// Single day in 15-minute slots, with full titles and descriptions
+fn render_day_view(f: &mut Frame, app: &App) {
+    f.render_widget(Clear, f.area());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints(
+            [
+                Constraint::Min(3),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
+        .split(f.area());
+    render_footer(f, app, chunks[2]);
+
+    let grid = build_calendar_grid(app);
+    let date = app.calendar_anchor;
+
+    let all_day = grid
+        .data
+        .all_day
+        .iter()
+        .filter(|(d, _, _)| *d == date)
+        .map(|(_, title, _)| format!("◇ {}", title))
+        .chain(hidden_summary(&grid, date))
+        .collect::<Vec<_>>()
+        .join("  ");
+    let mut rows = vec![
+        Row::new(vec![
+            Cell::from("all day").style(Style::default().fg(app.display.theme.muted())),
+            Cell::from(all_day).style(Style::default().fg(app.display.theme.all_day())),
+        ])
+        .bottom_margin(1),
+    ];
+
+    let first_hour = app.display.calendar_hours.start;
+    let selected_start = app.selected_cell_time();
+    let selected_end = selected_start + Duration::minutes(app.display.calendar_slot_minutes as i64);
+    let now = crate::timezone::now().naive_local();
+    for quarter in 0..(app.display.calendar_hours.len() as u32 * 4) {
+        let time =
+            NaiveTime::from_hms_opt(first_hour + quarter / 4, (quarter % 4) * 15, 0).unwrap();
+        let is_now =
+            date == now.date() && now.time() >= time && (now.time() - time) < Duration::minutes(15);
+        let label = if is_now {
+            let label = if time.minute() == 0 {
+                app.display.format_hour(time)
+            } else {
+                format!("  :{:02}", time.minute())
+            };
+            Cell::from(format!("{}◄", label)).style(now_style(&app.display.theme))
+        } else if time.minute() == 0 {
+            Cell::from(app.display.format_hour(time))
+        } else {
+            Cell::from(format!("  :{:02}", time.minute()))
+                .style(Style::default().fg(app.display.theme.muted()))
+        };
+
+        let mut row = Row::new(vec![
+            label,
+            Cell::from(Line::from(day_slot_spans(&grid, date, time))),
+        ]);
+        // The selected slot may end at midnight, which wraps to 00:00
+        let selected =
+            time >= selected_start && (time < selected_end || selected_end == NaiveTime::MIN);
+        if selected && app.calendar_input_mode == CalendarInputMode::Navigate {
+            row = row.style(Style::default().bg(app.display.theme.cursor()));
+        }
+        rows.push(row);
+    }
+
+    // Scroll so the selected slot sits mid-screen
+    let visible = chunks[0].height.saturating_sub(2) as usize;
+    let selected_row =
+        1 + (selected_start.num_seconds_from_midnight() / 60 - first_hour * 60) as usize / 15;
+    let offset = selected_row
+        .saturating_sub(visible.saturating_sub(4) / 2)
+        .min(rows.len().saturating_sub(visible));
+    let mut state = TableState::default().with_offset(offset);
+
+    let title = format!(
+        "{} (t: todo, v: view, h/l: day, H/L: week, j/k: slot, n: block, Enter: edit, d: del block, s: schedule, a: add task, q: quit)",
+        app.display.format_day(date)
+    );
+    let table = Table::new(rows, [Constraint::Length(8), Constraint::Fill(1)])
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .column_spacing(1);
+
+    f.render_stateful_widget(table, chunks[0], &mut state);
+    render_block_legend(f, app, chunks[1]);
+    render_calendar_overlay(f, app);
+}
+
+/// Block, events and tasks in the 15-minute slot starting at `time`. Items are
+/// labelled in the slot they start in (or the first slot, if they start before
+/// the grid) and continue with a bar below.
+fn day_slot_spans(grid: &CalendarGrid, date: NaiveDate, time: NaiveTime) -> Vec<Span<'static>> {
+    let slot_end = time + Duration::minutes(15);
+    let first_slot = grid.time_slots[0].time;
+    let starts_here = |start: NaiveTime| {
+        (start >= time && start < slot_end) || (start < first_slot && time == first_slot)
+    };
+    let mut spans = Vec::new();
+
+    let block = grid.data.schedule_blocks.iter().find_map(|(d, block)| {
+        let start = parse_time_string(&block.start_time)?;
+        let end = parse_time_string(&block.end_time)?;
+        (*d == date && start < slot_end && end > time).then_some((block, start, end))
+    });
+    if let Some((block, start, end)) = block {
+        let text = if starts_here(start) {
+            format!(
+                "[{}] {} {}–{}",
+                block.block_type,
+                block.title,
+                grid.display.format_time(start),
+                grid.display.format_time(end)
+            )
+        } else {
+            "│".to_string()
+        };
+        spans.push(Span::styled(
+            text,
+            get_block_style(&grid.display.theme, &block.block_type),
+        ));
+    }
+
+    for segment in grid.data.events.iter().filter(|segment| {
+        segment.date == date && segment.start < slot_end && segment.end.is_none_or(|end| end > time)
+    }) {
+        let text = if starts_here(segment.start) {
+            let until = match segment.end {
+                Some(end) => grid.display.format_time(end),
+                None => "next day".to_string(),
+            };
+            format!(
+                "◆ {} {}–{}",
+                segment.title,
+                grid.display.format_time(segment.start),
+                until
+            )
+        } else {
+            "│".to_string()
+        };
+        spans.push(Span::styled(
+            text,
+            Style::default().fg(grid.display.theme.event()),
+        ));
+    }
+
+    for (_, _, description, priority) in grid
+        .data
+        .scheduled_tasks
+        .iter()
+        .filter(|(d, t, _, _)| *d == date && *t >= time && *t < slot_end)
+    {
+        let color = grid.display.theme.task_color(*priority);
+        spans.push(Span::styled(
+            format!("● {}", description),
+            Style::default().fg(color),
+        ));
+    }
+
+    // Separate items in the same slot
+    let count = spans.len();
+    spans
+        .into_iter()
+        .enumerate()
+        .flat_map(|(i, span)| {
+            let gap = (i + 1 < count).then(|| Span::raw("  "));
+            std::iter::once(span).chain(gap)
+        })
+        .collect()
+}
+
+/// Month grid with counts of events (◆), tasks (●) and all-day items (◇) per day
+fn render_month_view(f: &mut Frame, app: &App) {
+    f.render_widget(Clear, f.area());
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints(
+            [
+                Constraint::Min(3),
+                Constraint::Length(1),
+                Constraint::Length(1),
+            ]
+            .as_ref(),
+        )
+        .split(f.area());
+    render_footer(f, app, chunks[2]);
+
+    let grid = build_calendar_grid(app);
+    let anchor = app.calendar_anchor;
+    let today = app
+        .display
+        .logical_date(crate::timezone::now().naive_local());
+
+    let header = Row::new(grid.days.iter().take(7).map(|day| {
+        Cell::from(day.weekday().to_string()).style(
+            Style::default()
+                .fg(app.display.theme.accent())
+                .add_modifier(Modifier::BOLD),
+        )
+    }))
+    .height(1)
+    .bottom_margin(1);
+
+    // Share the height left after borders and header between the weeks
+    let weeks = (grid.days.len() / 7).max(1) as u16;
+    let row_height = (chunks[0].height.saturating_sub(4) / weeks).max(2);
+    let rows: Vec<Row> = grid
+        .days
+        .chunks(7)
+        .map(|week| {
+            Row::new(
+                week.iter()
+                    .map(|day| build_month_cell(&grid, *day, anchor, today)),
+            )
+            .height(row_height)
+        })
+        .collect();
+
+    let title = format!(
+        "{} (t: todo, v: view, h/l: month, j/k: week, Enter: day view, q: quit)",
+        anchor.format("%B %Y")
+    );
+    let table = Table::new(rows, [Constraint::Fill(1); 7])
+        .header(header)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .column_spacing(1);
+
+    f.render_widget(table, chunks[0]);
+
+    let legend = Line::from(vec![
+        Span::styled("◆ events  ", Style::default().fg(app.display.theme.event())),
+        Span::raw("● tasks  "),
+        Span::styled(
+            "◇ all day",
+            Style::default().fg(app.display.theme.all_day()),
+        ),
+    ]);
+    f.render_widget(Paragraph::new(legend), chunks[1]);
+}
+
+fn build_month_cell<'a>(
+    grid: &CalendarGrid,
+    day: NaiveDate,
+    anchor: NaiveDate,
+    today: NaiveDate,
+) -> Cell<'a> {
+    let events = grid.data.events.iter().filter(|s| s.date == day).count();
+    let tasks = grid
+        .data
+        .scheduled_tasks
+        .iter()
+        .filter(|(d, _, _, _)| *d == day)
+        .count();
+    let all_day = grid
+        .data
+        .all_day
+        .iter()
+        .filter(|(d, _, _)| *d == day)
+        .count();
+
+    let number_style = if day == today {
+        Style::default()
+            .fg(grid.display.theme.highlight())
+            .add_modifier(Modifier::BOLD | Modifier::UNDERLINED)
+    } else if day.month() != anchor.month() {
+        Style::default().fg(grid.display.theme.muted())
+    } else {
+        Style::default()
+    };
+
+    let mut badges = Vec::new();
+    if events > 0 {
+        badges.push(Span::styled(
+            format!("◆{} ", events),
+            Style::default().fg(grid.display.theme.event()),
+        ));
+    }
+    if tasks > 0 {
+        badges.push(Span::raw(format!("●{} ", tasks)));
+    }
+    if all_day > 0 {
+        badges.push(Span::styled(
+            format!("◇{}", all_day),
+            Style::default().fg(grid.display.theme.all_day()),
+        ));
+    }
+
+    let cell = Cell::from(Text::from(vec![
+        Line::styled(day.day().to_string(), number_style),
+        Line::from(badges),
+    ]));
+    if day == anchor {
+        cell.style(Style::default().bg(grid.display.theme.cursor()))
+    } else {
+        cell
+    }
+}
+
+struct CalendarGrid<'a> {
     days: Vec<NaiveDate>,
     time_slots: Vec<TimeSlot>,
-    schedule_blocks: Vec<(NaiveDate, ScheduleBlock)>,
-    scheduled_tasks: Vec<(NaiveDate, NaiveTime, String, i32)>,
+    data: &'a CalendarData,
+    display: &'a DisplayConfig,
+}
+
+impl CalendarGrid<'_> {
+    /// End of the slot starting at `slot_time`, and whether it wrapped past midnight
+    fn slot_end(&self, slot_time: &NaiveTime) -> (NaiveTime, bool) {
+        let (end, wrapped) = slot_time
+            .overflowing_add_signed(Duration::minutes(self.display.calendar_slot_minutes as i64));
+        (end, wrapped != 0)
+    }
+
+    /// Whether `time` falls in the slot starting at `slot_time`
+    fn in_slot(&self, time: NaiveTime, slot_time: &NaiveTime) -> bool {
+        let (end, wrapped) = self.slot_end(slot_time);
+        time >= *slot_time && (wrapped || time < end)
+    }
+
+    /// (column, slot) of the current local time, when the grid shows today
+    /// and the time falls within its hours
+    fn now(&self) -> Option<(usize, usize)> {
+        let now = crate::timezone::now().naive_local();
+        let day = self.days.iter().position(|day| *day == now.date())?;
+        let slot = self
+            .time_slots
+            .iter()
+            .position(|slot| self.in_slot(now.time(), &slot.time))?;
+        Some((day, slot))
+    }
+
+    /// Sub-hour rows are one line tall, so labels can't wrap onto a second line
+    fn single_line(&self) -> bool {
+        self.display.calendar_slot_minutes < 60
+    }
+
+    /// Open tasks scheduled in the slot starting at `slot_time` on `day`
+    fn slot_tasks(
+        &self,
+        day: NaiveDate,
+        slot_time: &NaiveTime,
+    ) -> Vec<&(NaiveDate, NaiveTime, String, i32)> {
+        self.data
+            .scheduled_tasks
+            .iter()
+            .filter(|(d, t, _, _)| *d == day && self.in_slot(*t, slot_time))
+            .collect()
+    }
 }
 
 struct TimeSlot {
@@ -264,87 +1206,156 @@ struct TimeSlot {
     time_label: String,
 }
 
-fn build_calendar_grid(app: &App) -> CalendarGrid {
-    // Calculate week start (Monday)
-    let today = chrono::Local::now().naive_local().date();
-    let week_offset = app.calendar_week_offset.unwrap_or(0);
-    let start_of_week = today + Duration::weeks(week_offset)
-        - Duration::days(today.weekday().num_days_from_monday() as i64);
-
-    // Generate 7 days starting from Monday
-    let days: Vec<NaiveDate> = (0..7).map(|i| start_of_week + Duration::days(i)).collect();
-
-    // Generate time slots (7am - 11pm in 1-hour increments)
-    let time_slots: Vec<TimeSlot> = (7..23)
-        .map(|hour| {
-            let time = NaiveTime::from_hms_opt(hour, 0, 0).unwrap();
-            TimeSlot {
-                time,
-                time_label: time.format("%I%p").to_string().to_lowercase(),
-            }
+fn build_calendar_grid(app: &App) -> CalendarGrid<'_> {
+    // The selected week, or the whole weeks covering the selected month
+    let days = app.visible_days();
+
+    // Slots of the configured length over the configured hours (hourly,
+    // 7am - 11pm by default); sub-hour slots are labelled like ":30"
+    let time_slots: Vec<TimeSlot> = (0..app.display.calendar_slot_count())
+        .map(|index| {
+            let time = app.display.calendar_slot_time(index);
+            let time_label = if time.minute() == 0 {
+                app.display.format_hour(time)
+            } else {
+                format!("  :{:02}", time.minute())
+            };
+            TimeSlot { time, time_label }
         })
         .collect();
 
-    // Use cached data from app
-    let schedule_blocks = app.cached_schedule_blocks.clone();
-    let scheduled_tasks = app.cached_scheduled_tasks.clone();
-
     CalendarGrid {
         days,
         time_slots,
-        schedule_blocks,
-        scheduled_tasks,
+        data: &app.calendar_data,
+        display: &app.display,
     }
 }
 
-fn build_cell_content<'a>(grid: &CalendarGrid, day_idx: usize, slot_time: &NaiveTime) -> Cell<'a> {
-    let day = grid.days[day_idx];
+/// One row listing each of `days`' date-only tasks and all-day events; extras
+/// collapse into "+N"
+fn build_all_day_row<'a>(grid: &CalendarGrid, days: &[NaiveDate]) -> Row<'a> {
+    let mut cells =
+        vec![Cell::from("all day").style(Style::default().fg(grid.display.theme.muted()))];
+
+    for day in days {
+        let titles: Vec<&str> = grid
+            .data
+            .all_day
+            .iter()
+            .filter(|(d, _, _)| d == day)
+            .map(|(_, title, _)| title.as_str())
+            .collect();
+
+        let text = match titles.as_slice() {
+            [] => String::new(),
+            [only] => format!("◇ {}", truncate_text(only, 12)),
+            [first, rest @ ..] => format!("◇ {} +{}", truncate_text(first, 9), rest.len()),
+        };
+        let mut spans = vec![Span::styled(
+            text,
+            Style::default().fg(grid.display.theme.all_day()),
+        )];
+        if let Some(hidden) = hidden_summary(grid, *day) {
+            spans.push(Span::styled(
+                format!(" {}", hidden),
+                Style::default().fg(grid.display.theme.muted()),
+            ));
+        }
+        cells.push(Cell::from(Line::from(spans)));
+    }
 
-    // Parse time strings to NaiveTime for comparison
-    let schedule_block = grid.schedule_blocks.iter().find(|(d, block)| {
-        *d == day && {
-            // Parse start_time and end_time strings to NaiveTime
-            if let (Some(start), Some(end)) = (
-                parse_time_string(&block.start_time),
-                parse_time_string(&block.end_time),
-            ) {
-                start <= *slot_time && end > *slot_time
-            } else {
-                false
-            }
+    Row::new(cells).height(1).bottom_margin(1)
+}
+
+/// "↑2 earlier ↓1 later" for the day's tasks and events that fall entirely
+/// outside the grid's hours, so they don't silently disappear
+fn hidden_summary(grid: &CalendarGrid, day: NaiveDate) -> Option<String> {
+    let first = grid.time_slots.first()?.time;
+    // None when the grid runs to midnight, so nothing can come later
+    let last_end = grid
+        .time_slots
+        .last()
+        .map(|slot| grid.slot_end(&slot.time))
+        .filter(|(_, wrapped)| !wrapped)
+        .map(|(end, _)| end);
+    let is_later = |time: NaiveTime| last_end.is_some_and(|end| time >= end);
+
+    let (mut earlier, mut later) = (0, 0);
+    for (_, time, _, _) in grid.data.scheduled_tasks.iter().filter(|(d, ..)| *d == day) {
+        if *time < first {
+            earlier += 1;
+        } else if is_later(*time) {
+            later += 1;
         }
-    });
+    }
+    for segment in grid.data.events.iter().filter(|seg| seg.date == day) {
+        if segment.end.is_some_and(|end| end <= first) {
+            earlier += 1;
+        } else if is_later(segment.start) {
+            later += 1;
+        }
+    }
 
-    // Check if there's a scheduled task at this time
-    let task = grid
-        .scheduled_tasks
-        .iter()
-        .find(|(d, t, _, _)| *d == day && t.hour() == slot_time.hour());
+    let parts: Vec<String> = [(earlier, "↑", "earlier"), (later, "↓", "later")]
+        .into_iter()
+        .filter(|(count, _, _)| *count > 0)
+        .map(|(count, arrow, word)| format!("{}{} {}", arrow, count, word))
+        .collect();
+    (!parts.is_empty()).then(|| parts.join(" "))
+}
+
+fn build_cell_content<'a>(grid: &CalendarGrid, day_idx: usize, slot_time: &NaiveTime) -> Cell<'a> {
+    let day = grid.days[day_idx];
+    let schedule_block = find_schedule_block(grid, day, slot_time);
+
+    // Check if there are scheduled tasks in this slot
+    let tasks = grid.slot_tasks(day, slot_time);
+    let task = tasks.first();
+
+    // Events take the cell unless a task is scheduled there
+    if task.is_none()
+        && let Some(segment) = find_event_segment(grid, day, slot_time)
+    {
+        return Cell::from(event_cell_text(grid, segment, slot_time))
+            .style(Style::default().fg(grid.display.theme.event()));
+    }
 
     match (schedule_block, task) {
-        (Some((_, block)), Some((_, _, task_desc, priority))) => {
+        (Some(block), Some((_, _, task_desc, priority))) => {
             // Task scheduled in this block - high priority overrides block color
             let style = if *priority >= 3 {
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+                Style::default()
+                    .fg(grid.display.theme.error())
+                    .add_modifier(Modifier::BOLD)
             } else {
-                get_block_style(&block.block_type).add_modifier(Modifier::BOLD)
+                get_block_style(&grid.display.theme, &block.block_type).add_modifier(Modifier::BOLD)
             };
-            Cell::from(format!("● {}", truncate_text(task_desc, 12))).style(style)
+            Cell::from(task_cell_text(
+                "●",
+                tasks[0].1,
+                slot_time,
+                task_desc,
+                tasks.len(),
+            ))
+            .style(style)
         }
-        (Some((_, block)), None) => {
+        (Some(block), None) => {
             // Empty schedule block
-            let style = get_block_style(&block.block_type);
-            Cell::from(format!("[{}]", block.block_type)).style(style)
+            let style = get_block_style(&grid.display.theme, &block.block_type);
+            Cell::from(block_cell_text(grid, block, slot_time)).style(style)
         }
         (None, Some((_, _, task_desc, priority))) => {
             // Task without schedule block - use priority color
-            let color = match priority {
-                3 => Color::Red,
-                2 => Color::Yellow,
-                _ => Color::White,
-            };
-            Cell::from(format!("• {}", truncate_text(task_desc, 12)))
-                .style(Style::default().fg(color))
+            let color = grid.display.theme.task_color(*priority);
+            Cell::from(task_cell_text(
+                "•",
+                tasks[0].1,
+                slot_time,
+                task_desc,
+                tasks.len(),
+            ))
+            .style(Style::default().fg(color))
         }
         (None, None) => {
             // Empty cell
@@ -353,82 +1364,192 @@ fn build_cell_content<'a>(grid: &CalendarGrid, day_idx: usize, slot_time: &Naive
     }
 }
 
-fn parse_time_string(time_str: &str) -> Option<NaiveTime> {
-    // Assuming format is "HH:MM:SS" or "HH:MM"
-    if time_str.contains(':') {
-        let parts: Vec<&str> = time_str.split(':').collect();
-        if parts.len() >= 2 {
-            let hour: u32 = parts[0].parse().ok()?;
-            let minute: u32 = parts[1].parse().ok()?;
-            let second: u32 = if parts.len() > 2 {
-                parts[2].parse().ok()?
-            } else {
-                0
-            };
-            NaiveTime::from_hms_opt(hour, minute, second)
-        } else {
-            None
-        }
-    } else {
-        None
-    }
-}
-
-fn get_block_style(block_type: &str) -> Style {
-    let color = match block_type {
-        "deepwork" | "deepwork_input" | "deepwork_output" => Color::Blue,
-        "class" => Color::Green,
-        "training" | "fitness" => Color::Red,
-        "learning" => Color::Cyan,
-        "admin" => Color::Yellow,
-        "bio-maintenance" | "meal" => Color::Magenta,
-        "break" => Color::Gray,
-        "social" => Color::LightBlue,
-        "planning" => Color::LightYellow,
-        "project" => Color::LightMagenta,
-        _ => Color::White,
+/// Find a schedule block overlapping the slot starting at `slot_time`
+fn find_schedule_block<'a>(
+    grid: &'a CalendarGrid,
+    day: NaiveDate,
+    slot_time: &NaiveTime,
+) -> Option<&'a ScheduleBlock> {
+    let (slot_end, wrapped) = grid.slot_end(slot_time);
+    grid.data
+        .schedule_blocks
+        .iter()
+        .find(|(d, block)| {
+            *d == day
+                && match (
+                    parse_time_string(&block.start_time),
+                    parse_time_string(&block.end_time),
+                ) {
+                    (Some(start), Some(end)) => (wrapped || start < slot_end) && end > *slot_time,
+                    _ => false,
+                }
+        })
+        .map(|(_, block)| block)
+}
+
+/// Whether something starting at `start` gets its label in this slot. Anything
+/// starting before the grid's first hour is labelled in the first row.
+fn starts_in_slot(grid: &CalendarGrid, start: NaiveTime, slot_time: &NaiveTime) -> bool {
+    let first_slot = grid.time_slots[0].time;
+    grid.in_slot(start, slot_time) || (start < first_slot && *slot_time == first_slot)
+}
+
+/// Block type and time range on the block's first row, a continuation bar below
+fn block_cell_text(grid: &CalendarGrid, block: &ScheduleBlock, slot_time: &NaiveTime) -> String {
+    let (Some(start), Some(end)) = (
+        parse_time_string(&block.start_time),
+        parse_time_string(&block.end_time),
+    ) else {
+        return format!("[{}]", block.block_type);
     };
-    Style::default().fg(color).bg(Color::Reset)
+
+    if starts_in_slot(grid, start, slot_time) {
+        format!(
+            "{}[{}]{}{}–{}",
+            start_offset(start, slot_time),
+            block.block_type,
+            if grid.single_line() { " " } else { "\n" },
+            grid.display.format_time(start),
+            grid.display.format_time(end)
+        )
+    } else {
+        "│".to_string()
+    }
+}
+
+/// Find an event segment overlapping the slot starting at `slot_time`
+fn find_event_segment<'a>(
+    grid: &'a CalendarGrid,
+    day: NaiveDate,
+    slot_time: &NaiveTime,
+) -> Option<&'a EventSegment> {
+    // The last slot of the day ends at midnight, which wraps to 00:00
+    let (slot_end, wrapped) = grid.slot_end(slot_time);
+    grid.data.events.iter().find(|seg| {
+        seg.date == day
+            && (wrapped || seg.start < slot_end)
+            && seg.end.is_none_or(|end| end > *slot_time)
+    })
+}
+
+/// Title and end time on the event's first visible row of the day, a
+/// continuation bar below it
+fn event_cell_text(grid: &CalendarGrid, segment: &EventSegment, slot_time: &NaiveTime) -> String {
+    if starts_in_slot(grid, segment.start, slot_time) {
+        let until = match segment.end {
+            Some(end) => grid.display.format_time(end),
+            None => "next day".to_string(),
+        };
+        let separator = if grid.single_line() { " " } else { "\n" };
+        format!(
+            "◆ {}{}{}→ {}",
+            start_offset(segment.start, slot_time),
+            truncate_text(&segment.title, 12),
+            separator,
+            until
+        )
+    } else {
+        "│".to_string()
+    }
+}
+
+/// Week grid day columns that fit in `width`, between one and seven
+fn week_days_that_fit(width: u16) -> usize {
+    // Borders, then the time column and one space before each column
+    let available = width.saturating_sub(2 + TIME_COLUMN_WIDTH);
+    ((available / (MIN_DAY_WIDTH + 1)) as usize).clamp(1, 7)
 }
 
+/// The current-time marker in the week and day views
+fn now_style(theme: &Theme) -> Style {
+    Style::default()
+        .fg(theme.highlight())
+        .add_modifier(Modifier::BOLD)
+}
+
+fn get_block_style(theme: &Theme, block_type: &str) -> Style {
+    Style::default()
+        .fg(theme.block_color(block_type))
+        .bg(Color::Reset)
+}
+
+/// The first task in a slot, with "+N" for any others sharing it
+fn task_cell_text(
+    marker: &str,
+    time: NaiveTime,
+    slot_time: &NaiveTime,
+    description: &str,
+    count: usize,
+) -> String {
+    let offset = start_offset(time, slot_time);
+    if count > 1 {
+        format!(
+            "{} {}{} +{}",
+            marker,
+            offset,
+            truncate_text(description, 9),
+            count - 1
+        )
+    } else {
+        format!("{} {}{}", marker, offset, truncate_text(description, 12))
+    }
+}
+
+/// ":30 " for something starting partway into the slot at `slot_time`, so a
+/// 10:30 class doesn't look like it starts at 10:00; empty on the boundary
+fn start_offset(start: NaiveTime, slot_time: &NaiveTime) -> String {
+    if start > *slot_time {
+        format!(":{:02} ", start.minute())
+    } else {
+        String::new()
+    }
+}
+
+/// Shorten `text` to at most `max_len` terminal columns, ending in "..." when
+/// there's room. Cuts between characters, so emoji and CJK text are safe.
 fn truncate_text(text: &str, max_len: usize) -> String {
-    if text.len() > max_len {
-        format!("{}...", &text[..max_len - 3])
+    if text.width() <= max_len {
+        return text.to_string();
+    }
+    let (budget, ellipsis) = if max_len > 3 {
+        (max_len - 3, "...")
     } else {
-        text.to_string()
+        (max_len, "")
+    };
+
+    let mut truncated = String::new();
+    let mut width = 0;
+    for c in text.chars() {
+        width += c.width().unwrap_or(0);
+        if width > budget {
+            break;
+        }
+        truncated.push(c);
     }
+    truncated.push_str(ellipsis);
+    truncated
 }
 
 fn get_cell_text(grid: &CalendarGrid, day_idx: usize, slot_time: &NaiveTime) -> String {
     let day = grid.days[day_idx];
+    let schedule_block = find_schedule_block(grid, day, slot_time);
 
-    let schedule_block = grid.schedule_blocks.iter().find(|(d, block)| {
-        *d == day && {
-            if let (Some(start), Some(end)) = (
-                parse_time_string(&block.start_time),
-                parse_time_string(&block.end_time),
-            ) {
-                start <= *slot_time && end > *slot_time
-            } else {
-                false
-            }
-        }
-    });
+    let tasks = grid.slot_tasks(day, slot_time);
+    let task = tasks.first();
 
-    let task = grid
-        .scheduled_tasks
-        .iter()
-        .find(|(d, t, _, _)| *d == day && t.hour() == slot_time.hour());
+    if task.is_none()
+        && let Some(segment) = find_event_segment(grid, day, slot_time)
+    {
+        return event_cell_text(grid, segment, slot_time);
+    }
 
     match (schedule_block, task) {
-        (Some((_, _block)), Some((_, _, task_desc, _))) => {
-            format!("● {}", truncate_text(task_desc, 12))
-        }
-        (Some((_, block)), None) => {
-            format!("[{}]", block.block_type)
+        (Some(_), Some((_, time, task_desc, _))) => {
+            task_cell_text("●", *time, slot_time, task_desc, tasks.len())
         }
-        (None, Some((_, _, task_desc, _))) => {
-            format!("• {}", truncate_text(task_desc, 12))
+        (Some(block), None) => block_cell_text(grid, block, slot_time),
+        (None, Some((_, time, task_desc, _))) => {
+            task_cell_text("•", *time, slot_time, task_desc, tasks.len())
         }
         (None, None) => String::new(),
     }
@@ -460,8 +1581,15 @@ fn render_block_form_popup(f: &mut Frame, app: &App) {
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .title("New Schedule Block (Tab: next, Enter: save, Esc: cancel)")
-        .style(Style::default().bg(Color::Black));
+        .title(format!(
+            "{} Schedule Block (Tab: next, Enter: save, Esc: cancel)",
+            if app.block_form.editing.is_some() {
+                "Edit"
+            } else {
+                "New"
+            }
+        ))
+        .style(Style::default().bg(app.display.theme.popup_bg()));
 
     let inner = block.inner(area);
     f.render_widget(block, area);
@@ -480,9 +1608,9 @@ fn render_block_form_popup(f: &mut Frame, app: &App) {
     let form = &app.block_form;
 
     let highlight = Style::default()
-        .fg(Color::Yellow)
+        .fg(app.display.theme.highlight())
         .add_modifier(Modifier::BOLD);
-    let normal = Style::default().fg(Color::White);
+    let normal = Style::default().fg(app.display.theme.text());
 
     // Block Type field
     let bt_style = if form.active_field == BlockFormField::BlockType {
@@ -528,7 +1656,7 @@ fn render_task_picker(f: &mut Frame, app: &App) {
     let block = Block::default()
         .borders(Borders::ALL)
         .title("Schedule Task (j/k: navigate, Enter: assign, Esc: cancel)")
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(app.display.theme.popup_bg()));
 
     let inner = block.inner(area);
     f.render_widget(block, area);
@@ -537,7 +1665,7 @@ fn render_task_picker(f: &mut Frame, app: &App) {
 
     if unscheduled.is_empty() {
         let msg = Paragraph::new("No unscheduled tasks available.")
-            .style(Style::default().fg(Color::Gray));
+            .style(Style::default().fg(app.display.theme.muted()));
         f.render_widget(msg, inner);
         return;
     }
@@ -546,13 +1674,10 @@ fn render_task_picker(f: &mut Frame, app: &App) {
         .iter()
         .enumerate()
         .map(|(idx, task)| {
-            let category_color = match task.task_category.as_deref() {
-                Some("deepwork") => Color::Blue,
-                Some("admin") => Color::Yellow,
-                Some("learning") => Color::Cyan,
-                Some("fitness") => Color::Red,
-                _ => Color::White,
-            };
+            let category_color = app
+                .display
+                .theme
+                .category_color(task.task_category.as_deref());
 
             let prefix = if idx == app.task_picker_selected {
                 "> "
@@ -576,7 +1701,7 @@ fn render_task_picker(f: &mut Frame, app: &App) {
 
     let list = List::new(items).highlight_style(
         Style::default()
-            .fg(Color::Yellow)
+            .fg(app.display.theme.highlight())
             .add_modifier(Modifier::BOLD),
     );
 
@@ -591,20 +1716,20 @@ fn render_calendar_task_input(f: &mut Frame, app: &App) {
     let time = app.selected_cell_time();
     let title = format!(
         "Add Task at {} {} (Enter: save, Esc: cancel)",
-        date.format("%a %m/%d"),
-        time.format("%I:%M%p").to_string().to_lowercase()
+        app.display.format_day(date),
+        app.display.format_time(time)
     );
 
     let block = Block::default()
         .borders(Borders::ALL)
         .title(title)
-        .style(Style::default().bg(Color::Black));
+        .style(Style::default().bg(app.display.theme.popup_bg()));
 
     let inner = block.inner(area);
     f.render_widget(block, area);
 
-    let input_text =
-        Paragraph::new(app.input_buffer.as_str()).style(Style::default().fg(Color::Yellow));
+    let input_text = Paragraph::new(app.input_buffer.as_str())
+        .style(Style::default().fg(app.display.theme.highlight()));
     f.render_widget(input_text, inner);
 
     f.set_cursor_position(ratatui::layout::Position {
@@ -612,3 +1737,40 @@ fn render_calendar_task_input(f: &mut Frame, app: &App) {
         y: inner.y,
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncate_text_cuts_between_multibyte_characters() {
+        // "é" is two bytes; a byte-based cut at 5 would land inside it
+        assert_eq!(truncate_text("café latte", 7), "café...");
+        assert_eq!(truncate_text("🎉🎉🎉🎉🎉", 7), "🎉🎉...");
+    }
+
+    #[test]
+    fn truncate_text_counts_double_width_cjk() {
+        let text = "日本語のテキスト";
+        assert_eq!(truncate_text(text, 9), "日本語...");
+        assert_eq!(truncate_text(text, 8), "日本...");
+        assert!(truncate_text(text, 8).width() <= 8);
+    }
+
+    #[test]
+    fn truncate_text_handles_tiny_limits() {
+        assert_eq!(truncate_text("hello", 0), "");
+        assert_eq!(truncate_text("hello", 1), "h");
+        assert_eq!(truncate_text("hello", 2), "he");
+        assert_eq!(truncate_text("hello", 3), "hel");
+        assert_eq!(truncate_text("日本", 1), "");
+        assert_eq!(truncate_text("日本", 3), "日");
+    }
+
+    #[test]
+    fn truncate_text_keeps_text_at_the_limit() {
+        assert_eq!(truncate_text("hello", 5), "hello");
+        assert_eq!(truncate_text("日本語", 6), "日本語");
+        assert_eq!(truncate_text("hello!", 5), "he...");
+    }
+}